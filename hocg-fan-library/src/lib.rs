@@ -1,7 +1,10 @@
 use hocg_fan_sim::library::GlobalLibrary;
 
+mod report;
 mod sets;
 
+pub use report::text_report;
+
 pub fn setup_library() -> GlobalLibrary {
     let mut library = GlobalLibrary::default();
     sets::append_sets(&mut library);
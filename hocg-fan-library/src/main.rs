@@ -2,11 +2,18 @@ use std::io::Write;
 
 use bincode::config;
 use flate2::{write::GzEncoder, Compression};
-use hocg_fan_library::setup_library;
+use hocg_fan_library::{setup_library, text_report};
 
 fn main() {
     let library = setup_library();
 
+    // compare the generated effect text with the official text, for manual review
+    if std::env::args().any(|a| a == "--text-report") {
+        std::fs::write("hocg-fan-lib-text-report.md", text_report(&library)).unwrap();
+        println!("done");
+        return;
+    }
+
     // generate the library file
     let config = config::standard();
     let bin = bincode::encode_to_vec(library, config).unwrap();
@@ -0,0 +1,139 @@
+use std::fmt::Write;
+
+use hocg_fan_sim::{
+    card_effects::text::EffectText,
+    cards::{Card, CardEffect, CardEffectCondition},
+    library::GlobalLibrary,
+};
+
+/// Compare the text generated from each effect with the official card text, for manual review
+pub fn text_report(library: &GlobalLibrary) -> String {
+    let mut report = String::new();
+    writeln!(report, "# Card text report").unwrap();
+
+    let mut cards: Vec<_> = library.cards.values().collect();
+    cards.sort_by_key(|c| c.card_number());
+
+    for card in cards {
+        let entries = card_entries(card);
+        if entries.is_empty() {
+            continue;
+        }
+
+        writeln!(report).unwrap();
+        writeln!(report, "## {} - {}", card.card_number(), card.name()).unwrap();
+        for (name, official, generated) in entries {
+            writeln!(report).unwrap();
+            writeln!(report, "### {name}").unwrap();
+            writeln!(report, "- official:  {}", official.replace("\\n", " ")).unwrap();
+            writeln!(report, "- generated: {generated}").unwrap();
+            writeln!(report, "- diff:      {}", word_diff(&official, &generated)).unwrap();
+        }
+    }
+
+    report
+}
+
+/// (name, official text, generated text) for each effect of the card
+fn card_entries(card: &Card) -> Vec<(String, String, String)> {
+    match card {
+        Card::OshiHoloMember(oshi) => oshi
+            .skills
+            .iter()
+            .map(|s| {
+                let name = format!("oshi skill: {}", s.name);
+                (
+                    name,
+                    s.text.clone(),
+                    generated_text(&s.condition, &s.effect),
+                )
+            })
+            .collect(),
+        Card::HoloMember(member) => member
+            .abilities
+            .iter()
+            .map(|a| {
+                let name = format!("ability: {}", a.name);
+                (
+                    name,
+                    a.text.clone(),
+                    generated_text(&a.condition, &a.effect),
+                )
+            })
+            .chain(member.arts.iter().filter(|a| !a.text.is_empty()).map(|a| {
+                let name = format!("art: {}", a.name);
+                (
+                    name,
+                    a.text.clone(),
+                    generated_text(&a.condition, &a.effect),
+                )
+            }))
+            .collect(),
+        Card::Support(support) => {
+            // the official text covers all the effects of the support card
+            let generated = support
+                .effects
+                .iter()
+                .map(|e| generated_text(&e.condition, &e.effect))
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            vec![("support".into(), support.text.clone(), generated)]
+        }
+        Card::Cheer(_) => vec![],
+    }
+}
+
+fn generated_text(condition: &CardEffectCondition, effect: &CardEffect) -> String {
+    [condition.to_text(), effect.to_text()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// word level diff, "[-removed-]" are only in the official text, "{+added+}" only in the generated text
+fn word_diff(official: &str, generated: &str) -> String {
+    let official: Vec<_> = official
+        .replace("\\n", " ")
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+    let generated: Vec<_> = generated.split_whitespace().map(String::from).collect();
+    let normalize = |w: &str| {
+        w.trim_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+    };
+    let official_norm: Vec<_> = official.iter().map(|w| normalize(w)).collect();
+    let generated_norm: Vec<_> = generated.iter().map(|w| normalize(w)).collect();
+
+    // longest common subsequence
+    let (n, m) = (official.len(), generated.len());
+    let mut lcs = vec![vec![0; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if official_norm[i] == generated_norm[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && official_norm[i] == generated_norm[j] {
+            diff.push(generated[j].clone());
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            diff.push(format!("{{+{}+}}", generated[j]));
+            j += 1;
+        } else {
+            diff.push(format!("[-{}-]", official[i]));
+            i += 1;
+        }
+    }
+    diff.join(" ")
+}
@@ -18,6 +18,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::BloomEffect,
             name: "The Fruits of Pekora's Growth...".into(),
             text: "Attach 1 Cheer card from your Cheer deck to your Center or Collab position holomem.".into(),
+            limit: UsageLimit::Unlimited,
             condition: (r"
                 any from main_stage is_member
             ")
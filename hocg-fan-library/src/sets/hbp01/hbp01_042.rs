@@ -104,20 +104,16 @@ mod tests {
         expected_state.player_1.archive =
             ["c_0711".into(), "c_0611".into(), "c_0511".into()].into();
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
             .card_modifiers
             .entry("c_0211".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0004".into(),
+                id: "m_0003".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
@@ -183,20 +179,16 @@ mod tests {
         expected_state.player_1.archive =
             ["c_0711".into(), "c_0611".into(), "c_0511".into()].into();
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
             .card_modifiers
             .entry("c_0211".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0004".into(),
+                id: "m_0003".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
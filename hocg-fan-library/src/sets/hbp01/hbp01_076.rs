@@ -24,7 +24,7 @@ pub fn card() -> Card {
             text: "Deal 10 special damage to one of your opponent's Back position holomem (if that holomem is Knocked Out this way, your opponent does not lose Life).".into(),
             condition: vec![],
             effect: (r"
-                let $back = select_one from opponent_back_stage is_member
+                let $back = select_one from opponent back_stage is_member
                 add_mod $back no_life_loss this_effect
                 deal_special_damage $back 10
             ")
@@ -19,11 +19,12 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: r#"Memories of a Snowy Mountain"#.into(),
             text: "Roll a six-sided die: If the result is odd, Knock Out 1 of your opponent's Back position holomem that has 40 or more damage marked on them (if that holomem is Knocked Out this way, your opponent does not lose Life).".into(),
+            limit: UsageLimit::Unlimited,
             condition: vec![],
             effect: (r"
                 let $roll = roll_dice
                 if is_odd $roll (
-                    let $back = select_one from opponent_back_stage (is_member and dmg_amount >= 40)
+                    let $back = select_one from opponent back_stage (is_member and dmg_amount >= 40)
                     add_mod $back no_life_loss this_effect
                     knock_out $back
                 )
@@ -120,14 +121,10 @@ mod tests {
                 },
             )]);
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0003".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state.card_damage_markers.remove(&"c_0312".into());
 
         assert_eq!(expected_state, game.game.state);
@@ -24,10 +24,10 @@ pub fn card() -> Card {
             text: "Roll a six-sided die: If the result is even, deal 20 special damage to one of your opponent's Back position holomem (if that holomem is Knocked Out this way, your opponent does not lose Life).".into(),
             condition: vec![],
             effect: (r"
-                if exists from opponent_back_stage (
+                if exists from opponent back_stage (
                     let $roll = roll_dice
                     if is_even $roll (
-                        let $back = select_one from opponent_back_stage is_member
+                        let $back = select_one from opponent back_stage is_member
                         add_mod $back no_life_loss this_effect
                         deal_special_damage $back 20
                     )
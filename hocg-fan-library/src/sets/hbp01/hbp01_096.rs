@@ -18,11 +18,12 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: r#"That Is "Adventure""#.into(),
             text: "Roll a six-sided die: If the result is even, you may search your deck for a Buzz holomem, reveal it, and put it into your hand. Then shuffle your deck.".into(),
+            limit: UsageLimit::Unlimited,
             condition: vec![],
             effect: (r"
                 let $roll = roll_dice
                 if is_even $roll (
-                    let $choice = select_one from main_deck (is_member and is_attribute_buzz)
+                    let $choice = select_one from main_deck (is_member and has_attribute buzz)
                     reveal $choice
                     send_to hand $choice
                     shuffle main_deck
@@ -18,6 +18,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: r#"That Is "Me""#.into(),
             text: "Attach 1 Cheer card from your Archive to 1 of your holomem.".into(),
+            limit: UsageLimit::Unlimited,
             condition: (r"
                 any from stage is_member
             ")
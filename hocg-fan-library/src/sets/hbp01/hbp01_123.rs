@@ -8,13 +8,15 @@ pub fn card() -> Card {
         limited: false,
         text: r#"When an ability of holomem this fan is attached to caused you to roll a six sided die, you may archive this fan: Reroll the die once.\n\nThis fan may only be attached to "Usada Pekora". You may attach any number of copies of this fan to a single holomem."#.into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::Attach],
-            condition: (r"
-                all attach_target is_named_usada_pekora
-            ").parse_effect().expect("hBP01-123"),
+            condition: (r#"
+                all attach_target is_named "Usada Pekora"
+            "#).parse_effect().expect("hBP01-123"),
             effect: vec![],
         },
         SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::OnAfterRollDice],
             condition: (r"
                 any attached_to event_origin is_card this_card
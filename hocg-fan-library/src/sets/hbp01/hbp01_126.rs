@@ -8,16 +8,18 @@ pub fn card() -> Card {
         limited: false,
         text: r#"When the holomem this Fan is attached to uses Arts, this Fan may be treated as a {R} Cheer.\n\nWhen the holomem this Fan is attached to receives damage, they receive 10 more damage.\n\nYou may only attach this Fan to "Omaru Polka". You may attach any number of copies of this Fan to a single holomem."#.into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::Attach],
-            condition: (r"
-                all attach_target is_named_omaru_polka
-            ").parse_effect().expect("hBP01-126"),
+            condition: (r#"
+                all attach_target is_named "Omaru Polka"
+            "#).parse_effect().expect("hBP01-126"),
             effect: (r"
                 add_mod this_card as_art_cost 1 red while_attached this_card
                 add_mod attach_target recv_more_dmg 10 while_attached this_card
             ").parse_effect().expect("hBP01-126"),
         },
         SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::OnBeforePerformArt],
             condition: (r"
                 any attached_to event_origin is_card this_card
@@ -14,6 +14,7 @@ pub fn card() -> Card {
                 name: "Replacement".into(),
                 cost: 1,
                 text: "[Once per turn] Move one Cheer card attached to one of your holomem to another of your holomem.".into(),
+                limit: Default::default(),
                 triggers: vec![Trigger::ActivateInMainStep],
                 condition: (r"
                     2 <= count from stage
@@ -31,16 +32,17 @@ pub fn card() -> Card {
                 name: "So You're the Enemy?".into(),
                 cost: 2,
                 text: "[Once per game] Switch 1 of your opponent's Back position holomem with their Center position holomem. Until end of turn, your White Center position holomem have +50 to their Arts.".into(),
+                limit: Default::default(),
                 triggers: vec![Trigger::ActivateInMainStep],
                 condition: (r"
-                    exists from opponent_center_stage
-                    exists from opponent_back_stage
+                    exists from opponent center_stage
+                    exists from opponent back_stage
                 ").parse_effect().unwrap(),
                 effect: (r"
-                    let $back_mem = select_one from opponent_back_stage is_member
-                    let $center_mem = from opponent_center_stage
-                    send_to opponent_back_stage $center_mem
-                    send_to opponent_center_stage $back_mem
+                    let $back_mem = select_one from opponent back_stage is_member
+                    let $center_mem = from opponent center_stage
+                    send_to opponent back_stage $center_mem
+                    send_to opponent center_stage $back_mem
                     add_zone_mod center_stage when is_color white deal_more_dmg 50 this_turn
                 ").parse_effect().unwrap(),
             }],
@@ -155,7 +157,7 @@ mod tests {
             .push((
                 Zone::CenterStage,
                 Modifier {
-                    id: "m_0002".into(),
+                    id: "m_0001".into(),
                     kind: ModifierKind::Conditional(
                         Box::new(Condition::IsColor(card_effects::Color::White)),
                         Box::new(ModifierKind::DealMoreDamage(50)),
@@ -165,23 +167,15 @@ mod tests {
             ));
         // expected_state.card_modifiers
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .push(Modifier {
-                id: "m_0001".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            });
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .push(Modifier {
-                id: "m_0003".into(),
-                kind: ModifierKind::PreventOshiSkill(1),
-                life_time: LifeTime::ThisGame,
-            });
+            .insert("oshi_skill 1".into(), LifeTime::ThisGame);
         // expected_state.card_damage_markers
         // expected_state.event_span
 
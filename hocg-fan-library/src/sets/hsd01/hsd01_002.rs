@@ -11,6 +11,7 @@ pub fn card() -> Card {
             name: "In My Left Hand, a Map".into(),
             cost: 3,
             text: "[Once per turn] You may use this skill when one of your holomem's abilities instructs you to roll a six-sided die: Declare a number from 1 to 6. You may use the declared number as the result of your die roll.".into(),
+            limit: Default::default(),
             triggers: vec![
                 Trigger::OnBeforeRollDice
             ],
@@ -27,6 +28,7 @@ pub fn card() -> Card {
             name: "In My Right Hand, a Mic".into(),
             cost: 3,
             text: "[Once per game] Attach any number of Cheer cards from your Archive to one of your Green holomem.".into(),
+            limit: Default::default(),
             triggers: vec![Trigger::ActivateInMainStep],
             condition: (r"
                  any from stage is_member and is_color green
@@ -142,21 +144,15 @@ mod tests {
             )]);
         // expected_state.card_modifiers
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([
-                Modifier {
-                    id: "m_0003".into(),
-                    kind: ModifierKind::PreventOshiSkill(0),
-                    life_time: LifeTime::ThisTurn,
-                },
-                Modifier {
-                    id: "m_0004".into(),
-                    kind: ModifierKind::PreventOshiSkill(1),
-                    life_time: LifeTime::ThisGame,
-                },
-            ]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
+        expected_state
+            .card_usages
+            .entry("c_0111".into())
+            .or_default()
+            .insert("oshi_skill 1".into(), LifeTime::ThisGame);
         // expected_state.card_damage_markers
         // expected_state.event_span
 
@@ -16,6 +16,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: "Let's Dance!".into(),
             text: "Until end of turn, your Center position holomem gains +20 to their Arts.".into(),
+            limit: UsageLimit::Unlimited,
             condition: vec![],
             effect: (r"
                 add_zone_mod center_stage deal_more_dmg 20 this_turn
@@ -31,11 +31,11 @@ pub fn card() -> Card {
                 text: "If a [AZKi] holomem is on your Stage, this Art deals 50 additional damage."
                     .into(),
                 condition: vec![],
-                effect: (r"
-                if any from stage is_member and is_named_azki (
+                effect: (r#"
+                if any from stage is_member and is_named "AZKi" (
                     add_mod this_card deal_more_dmg 50 this_art
                 )
-            ")
+            "#)
                 .parse_effect()
                 .expect("hSD01-006"),
             },
@@ -256,20 +256,16 @@ mod tests {
                 },
             ));
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .push(Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(1),
-                life_time: LifeTime::ThisGame,
-            });
+            .insert("oshi_skill 1".into(), LifeTime::ThisGame);
         expected_state
             .card_modifiers
             .entry("c_0211".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0004".into(),
+                id: "m_0003".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
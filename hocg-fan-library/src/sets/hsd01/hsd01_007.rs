@@ -16,6 +16,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: "HOPE".into(),
             text: "Look at your holoPOWER. You may reveal a card from among your holoPOWER and put it into your hand. Then put 1 card from your hand onto your holoPOWER.".into(),
+            limit: UsageLimit::Unlimited,
             condition: (r"
                 exists from holo_power
             ").parse_effect().expect("hSD01-007"),
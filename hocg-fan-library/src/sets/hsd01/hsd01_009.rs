@@ -16,6 +16,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: "Expanding Map".into(),
             text: "Roll a six-sided die: If the result is 4 or less, attach the top card of your Cheer Deck to one of your Back position holomem. If the result is 1, you may also move this holomem to the Backstage.".into(),
+            limit: UsageLimit::Unlimited,
             condition: vec![],
             effect: (r"
                 let $roll = roll_dice
@@ -114,14 +115,10 @@ mod tests {
                 },
             )]);
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .push(Modifier {
-                id: "m_0003".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            });
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
 
         assert_eq!(expected_state, game.game.state);
     }
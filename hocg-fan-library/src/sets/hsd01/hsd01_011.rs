@@ -20,14 +20,14 @@ pub fn card() -> Card {
             special_damage: Some((Color::Blue, 50)),
             text: "If there is a [Tokino Sora] holomem on your Stage, attach 1 card from the top of your Cheer Deck to one of your holomem.".into(),
             condition: vec![],
-            effect: (r"
-                if any from stage is_member and is_named_tokino_sora (
+            effect: (r#"
+                if any from stage is_member and is_named "Tokino Sora" (
                     let $cheer = from_top 1 cheer_deck
                     reveal $cheer
                     let $mem = select_one from stage is_member
                     attach_cards $cheer $mem
                 )
-            ").parse_effect().expect("hSD01-011"),
+            "#).parse_effect().expect("hSD01-011"),
         },
         HoloMemberArt {
             name: "Destiny Song".into(),
@@ -180,20 +180,16 @@ mod tests {
         expected_state.player_1.archive =
             ["c_0711".into(), "c_0611".into(), "c_0511".into()].into();
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
             .card_modifiers
             .entry("c_0211".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0005".into(),
+                id: "m_0004".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
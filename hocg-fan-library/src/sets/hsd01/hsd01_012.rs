@@ -16,6 +16,7 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: "Let's Draw Together!".into(),
             text: "Attach one {W} Cheer or {G} Cheer from your Archive to your Center position holomem.".into(),
+            limit: UsageLimit::Unlimited,
             condition: (r"
                 all from center_stage is_member
             ").parse_effect().expect("hSD01-012"),
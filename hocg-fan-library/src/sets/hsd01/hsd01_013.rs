@@ -107,20 +107,16 @@ mod tests {
             .attachments
             .extend([(CardRef::from("c_0611"), "c_0311".into())]);
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
             .card_modifiers
             .entry("c_0311".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0003".into(),
+                id: "m_0002".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
@@ -189,20 +185,16 @@ mod tests {
             ["c_0911".into(), "c_0811".into(), "c_0711".into()].into();
         expected_state.player_1.hand = ["c_0211".into()].into();
         expected_state
-            .card_modifiers
+            .card_usages
             .entry("c_0111".into())
             .or_default()
-            .extend([Modifier {
-                id: "m_0002".into(),
-                kind: ModifierKind::PreventOshiSkill(0),
-                life_time: LifeTime::ThisTurn,
-            }]);
+            .insert("oshi_skill 0".into(), LifeTime::ThisTurn);
         expected_state
             .card_modifiers
             .entry("c_0311".into())
             .or_default()
             .extend([Modifier {
-                id: "m_0003".into(),
+                id: "m_0002".into(),
                 kind: ModifierKind::PreventAllArts,
                 life_time: LifeTime::ThisTurn,
             }]);
@@ -16,18 +16,19 @@ pub fn card() -> Card {
             kind: MemberAbilityKind::CollabEffect,
             name: "SoAzKo".into(),
             text: "⯀ When this card collabs with [Tokino Sora], draw a card.\n⯀ When this card collabs with [AZKi], attach the top card of your Cheer Deck to your Center position holomem.".into(),
+            limit: UsageLimit::Unlimited,
             condition: vec![],
-            effect: (r"
+            effect: (r#"
                 let $center_mem = filter from center_stage is_member
-                if all $center_mem is_named_tokino_sora (
+                if all $center_mem is_named "Tokino Sora" (
                     draw 1
                 )
-                if all $center_mem is_named_azki (
+                if all $center_mem is_named "AZKi" (
                     let $cheer = from_top 1 cheer_deck
                     reveal $cheer
                     attach_cards $cheer $center_mem
                 )
-            ").parse_effect().expect("hSD01-015"),
+            "#).parse_effect().expect("hSD01-015"),
         }],
         arts: vec![HoloMemberArt {
             name: "Pure, Pure, Pure!".into(),
@@ -8,6 +8,7 @@ pub fn card() -> Card {
         limited: true,
         text: "Draw 3 cards.".into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::PlayFromHand],
             condition: vec![],
             effect: (r"
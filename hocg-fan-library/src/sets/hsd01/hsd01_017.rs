@@ -8,6 +8,7 @@ pub fn card() -> Card {
         limited: true,
         text: "You can use this card only if you have 1 or more card in hand, not including this card.\n\n Shuffle your hand into your deck, then draw 5 cards.".into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::PlayFromHand],
             condition: (r"
                     1 <= count filter from hand is_not_card this_card
@@ -8,6 +8,7 @@ pub fn card() -> Card {
         limited: false,
         text: "Look at the top 5 cards of your deck. You may reveal a LIMITED Support card from among them and put it into your hand. Put the rest on the bottom of your deck in any order.".into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::PlayFromHand],
             condition: vec![],
             effect: (r"
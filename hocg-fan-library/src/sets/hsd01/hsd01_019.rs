@@ -8,6 +8,7 @@ pub fn card() -> Card {
         limited: true,
         text: "You can use this card only if you Archive 1 Cheer card attached to your holomem.\n\n Search your deck for a non-Buzz 1st or 2nd holomem, reveal it, and put it into your hand. Then shuffle your deck.".into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::PlayFromHand],
             condition: (r"
                     any from stage has_cheers
@@ -16,7 +17,7 @@ pub fn card() -> Card {
                     let $mem = select_one from stage is_member and has_cheers
                     let $cheer = select_one attached_to $mem is_cheer
                     send_to archive $cheer
-                    let $cond = ((is_level_first or is_level_second) and not is_attribute_buzz) 
+                    let $cond = (((is_level first) or (is_level second)) and not (has_attribute buzz)) 
                     let $choice = select_one from main_deck $cond
                     reveal $choice
                     send_to hand $choice
@@ -8,6 +8,7 @@ pub fn card() -> Card {
         limited: false,
         text: "Roll a six-sided die: If the result is 3 or greater, attach a Cheer card from your Archive to one of your holomem.".into(),
         effects: vec![SupportEffect {
+            limit: UsageLimit::Unlimited,
             triggers: vec![Trigger::PlayFromHand],
             condition: vec![],
             effect: (r"
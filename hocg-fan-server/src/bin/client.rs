@@ -0,0 +1,97 @@
+use std::{env, time::Duration};
+
+use hocg_fan_server::{
+    default_loadouts,
+    lobby::{join_game, rejoin_game},
+};
+use hocg_fan_sim::client::{websocket, Client, DefaultEventHandler};
+use hocg_fan_sim::library::load_library;
+use hocg_fan_sim::prompters::DefaultPrompter;
+use time::macros::format_description;
+use tracing::info;
+use tracing_subscriber::{fmt::time::LocalTime, EnvFilter};
+
+static DEFAULT_URL: &str = "ws://127.0.0.1:8080";
+const RECONNECTION_ATTEMPTS: usize = 10;
+
+/// Join a game on a hocg-fan-server, and play it from the terminal
+#[tokio::main]
+async fn main() {
+    env::set_var("RUST_BACKTRACE", "1");
+    env::set_var("RUST_LOG", "DEBUG");
+
+    // setup logs
+    let file_appender = tracing_appender::rolling::daily("logs", "hocg-fan-client.log");
+    let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
+    tracing_subscriber::fmt()
+        .with_timer(LocalTime::new(format_description!(
+            "[year]-[month]-[day] [hour repr:24]:[minute]:[second].[subsecond digits:4]"
+        )))
+        .with_writer(non_blocking)
+        .with_ansi(false)
+        // enable thread id to be emitted
+        .with_thread_ids(true)
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+
+    load_library(&include_bytes!("../../../hocg-fan-lib.gz")[..]).await;
+
+    // hocg-fan-client <name> [table] [url]
+    // without a table, the player is matched with anyone in the queue
+    let args: Vec<String> = env::args().skip(1).collect();
+    let Some(name) = args.first().cloned() else {
+        eprintln!("usage: hocg-fan-client <name> [table] [url]");
+        return;
+    };
+    let table = args.get(1).cloned();
+    let url = args.get(2).cloned().unwrap_or(DEFAULT_URL.into());
+
+    info!("connecting to {url}");
+    let channels = match websocket::connect(&url).await {
+        Ok(channels) => channels,
+        Err(e) => {
+            eprintln!("could not connect to {url}: {e}");
+            return;
+        }
+    };
+    println!("connected to {url}, waiting for an opponent...");
+
+    let (loadout, _) = default_loadouts();
+    let session = match join_game(&channels, name, loadout, table).await {
+        Ok((player, opponent, session)) => {
+            println!("playing as {player:?} against {opponent}");
+            session
+        }
+        Err(e) => {
+            eprintln!("could not join a game: {e}");
+            return;
+        }
+    };
+
+    let mut client =
+        Client::new(channels, DefaultEventHandler::new(), DefaultPrompter::new()).await;
+    let mut outcome = client.play().await;
+    // the game waits a while for the player to come back
+    'reconnect: while client.disconnected {
+        println!("disconnected from {url}, reconnecting...");
+        for _ in 0..RECONNECTION_ATTEMPTS {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            let Ok(channels) = websocket::connect(&url).await else {
+                continue;
+            };
+            match rejoin_game(&channels, session.clone()).await {
+                Ok(_) => {
+                    client.reconnect(channels);
+                    outcome = client.play().await;
+                    continue 'reconnect;
+                }
+                Err(e) => {
+                    eprintln!("could not reconnect: {e}");
+                    break 'reconnect;
+                }
+            }
+        }
+        break;
+    }
+    println!("game over: {outcome:?}");
+}
@@ -0,0 +1,778 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_channel::{Receiver, Sender};
+use hocg_fan_sim::{
+    events::{ClientReceive, ClientSend},
+    gameplay::{GameDirector, GameOutcome, Player},
+    handshake::{Handshake, HandshakeError, HandshakeResponse},
+    library::{library, load_library, Loadout},
+    lobby::{LobbyRequest, LobbyResponse, SessionToken, TableInfo},
+    spectators::Spectators,
+    timing::TimingRules,
+    transport::PlayerTransport,
+};
+use tracing::{debug, info};
+
+use crate::session::{new_session_token, ResumableTransport, RECONNECTION_GRACE_PERIOD};
+
+pub type PlayerId = u32;
+pub type SharedLobby = Arc<Mutex<Lobby>>;
+
+/// tables that did not start a game are closed after that
+pub const ABANDONED_TABLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// the spectators are that many events behind the players
+pub const DEFAULT_SPECTATOR_DELAY: usize = 10;
+/// the players who walk away lose the game, instead of stalling it
+pub const DEFAULT_TIMING_RULES: TimingRules = TimingRules {
+    intent_timeout: Some(Duration::from_secs(2 * 60)),
+    clock: Some(Duration::from_secs(30 * 60)),
+    turn_limit: Some(Duration::from_secs(5 * 60)),
+};
+
+/// Sent to the task handling the connection of a player
+pub enum LobbyNotification {
+    Response(LobbyResponse),
+    /// hand over the connection to the match
+    StartMatch {
+        player: Player,
+        opponent: String,
+        session: SessionToken,
+        handover: Sender<(Player, Box<dyn PlayerTransport>)>,
+    },
+    /// hand over the connection to the spectators of the match
+    Spectate {
+        table: String,
+        join: Sender<Box<dyn PlayerTransport>>,
+    },
+}
+
+struct LobbyPlayer {
+    name: String,
+    loadout: Loadout,
+    notify: Sender<LobbyNotification>,
+    table: Option<String>,
+    queued: bool,
+}
+
+struct Table {
+    name: String,
+    players: Vec<PlayerId>,
+    ready: Vec<PlayerId>,
+    last_activity: Instant,
+}
+
+/// A match that can be spectated, until the end of the game
+struct RunningMatch {
+    players: Vec<String>,
+    spectate: Sender<Box<dyn PlayerTransport>>,
+}
+
+/// Both players are ready, waiting for their connections
+pub struct PendingMatch {
+    pub table: String,
+    pub player_1: Loadout,
+    pub player_2: Loadout,
+    transports: Receiver<(Player, Box<dyn PlayerTransport>)>,
+    reconnections: [Receiver<Box<dyn PlayerTransport>>; 2],
+    reconnection_grace: Duration,
+    timing: TimingRules,
+    spectators: Spectators,
+}
+
+impl PendingMatch {
+    /// Run the match until the end, usually in its own task
+    pub async fn run(self) -> Option<GameOutcome> {
+        let (mut p1, mut p2) = (None, None);
+        while p1.is_none() || p2.is_none() {
+            let Ok((player, transport)) = self.transports.recv().await else {
+                info!("match {}: a player left before the start", self.table);
+                return None;
+            };
+            match player {
+                Player::One => p1 = Some(transport),
+                _ => p2 = Some(transport),
+            }
+        }
+
+        info!("match {}: starting", self.table);
+        let [r1, r2] = self.reconnections;
+        let p1 = ResumableTransport::new(
+            Player::One,
+            p1.expect("checked above"),
+            r1,
+            self.reconnection_grace,
+        );
+        let p2 = ResumableTransport::new(
+            Player::Two,
+            p2.expect("checked above"),
+            r2,
+            self.reconnection_grace,
+        );
+        let mut game = GameDirector::setup(&self.player_1, &self.player_2, p1, p2).await;
+        game.spectators = Some(self.spectators);
+        game.enable_timing(self.timing);
+        if game.start_game().await.is_ok() {
+            while game.next_step().await.is_ok() {}
+        }
+
+        let outcome = game.game.game_outcome();
+        info!("match {}: {outcome:?}", self.table);
+        outcome
+    }
+}
+
+/// In-memory store of the players waiting for a game
+pub struct Lobby {
+    players: HashMap<PlayerId, LobbyPlayer>,
+    tables: BTreeMap<String, Table>,
+    running: BTreeMap<String, RunningMatch>,
+    sessions: HashMap<SessionToken, (Player, Sender<Box<dyn PlayerTransport>>)>,
+    queue: VecDeque<PlayerId>,
+    next_player_id: PlayerId,
+    next_queue_table: u32,
+    matches: Sender<PendingMatch>,
+    /// for the next matches
+    pub spectator_delay: usize,
+    /// for the next matches
+    pub reconnection_grace: Duration,
+    /// for the next matches
+    pub timing: TimingRules,
+}
+
+impl Lobby {
+    pub fn new() -> (SharedLobby, Receiver<PendingMatch>) {
+        let (matches, pending) = async_channel::unbounded();
+        let lobby = Lobby {
+            players: HashMap::new(),
+            tables: BTreeMap::new(),
+            running: BTreeMap::new(),
+            sessions: HashMap::new(),
+            queue: VecDeque::new(),
+            next_player_id: 1,
+            next_queue_table: 1,
+            matches,
+            spectator_delay: DEFAULT_SPECTATOR_DELAY,
+            reconnection_grace: RECONNECTION_GRACE_PERIOD,
+            timing: DEFAULT_TIMING_RULES,
+        };
+        (Arc::new(Mutex::new(lobby)), pending)
+    }
+
+    /// the loadout should already be validated
+    pub fn register(
+        &mut self,
+        name: String,
+        loadout: Loadout,
+        notify: Sender<LobbyNotification>,
+    ) -> Result<PlayerId, String> {
+        if name.trim().is_empty() {
+            return Err("the name cannot be empty".into());
+        }
+        if self.players.values().any(|p| p.name == name) {
+            return Err(format!("the name {name} is already used"));
+        }
+
+        let id = self.next_player_id;
+        self.next_player_id += 1;
+        self.players.insert(
+            id,
+            LobbyPlayer {
+                name,
+                loadout,
+                notify,
+                table: None,
+                queued: false,
+            },
+        );
+        self.respond(id, LobbyResponse::Registered);
+        Ok(id)
+    }
+
+    pub fn handle_request(&mut self, id: PlayerId, request: LobbyRequest) {
+        let Some(player) = self.players.get(&id) else {
+            return;
+        };
+        let busy = player.table.is_some() || player.queued;
+
+        match request {
+            LobbyRequest::Register { .. } | LobbyRequest::Reconnect { .. } => {
+                self.error(id, "already registered")
+            }
+            LobbyRequest::ListTables => {
+                self.running.retain(|_, m| !m.spectate.is_closed());
+                let tables = self
+                    .tables
+                    .values()
+                    .map(|t| self.table_info(t))
+                    .chain(self.running.iter().map(|(name, m)| TableInfo {
+                        name: name.clone(),
+                        players: m.players.clone(),
+                        ready: m.players.clone(),
+                        playing: true,
+                    }))
+                    .collect();
+                self.respond(id, LobbyResponse::Tables(tables));
+            }
+            LobbyRequest::CreateTable { table } => {
+                if busy {
+                    self.error(id, "already at a table or in the queue");
+                } else if table.trim().is_empty() {
+                    self.error(id, "the table name cannot be empty");
+                } else if self.tables.contains_key(&table) || self.running.contains_key(&table) {
+                    self.error(id, &format!("the table {table} already exists"));
+                } else {
+                    self.join_table(id, table);
+                }
+            }
+            LobbyRequest::JoinTable { table } => match self.tables.get(&table) {
+                _ if busy => self.error(id, "already at a table or in the queue"),
+                None => self.error(id, &format!("the table {table} does not exist")),
+                Some(t) if t.players.len() >= 2 => {
+                    self.error(id, &format!("the table {table} is full"))
+                }
+                Some(_) => self.join_table(id, table),
+            },
+            LobbyRequest::JoinQueue => {
+                if busy {
+                    self.error(id, "already at a table or in the queue");
+                } else if let Some(opponent) = self.queue.pop_front() {
+                    if let Some(opponent) = self.players.get_mut(&opponent) {
+                        opponent.queued = false;
+                    }
+                    let table = format!("queue-{}", self.next_queue_table);
+                    self.next_queue_table += 1;
+                    self.join_table(opponent, table.clone());
+                    self.join_table(id, table);
+                } else {
+                    self.queue.push_back(id);
+                    self.player_mut(id).queued = true;
+                    self.respond(id, LobbyResponse::Queued);
+                }
+            }
+            LobbyRequest::Leave => {
+                if self.leave(id) {
+                    self.respond(id, LobbyResponse::Left);
+                } else {
+                    self.error(id, "not at a table or in the queue");
+                }
+            }
+            LobbyRequest::Ready => self.ready(id),
+            LobbyRequest::Spectate { table } => match self.spectate(&table) {
+                _ if busy => self.error(id, "already at a table or in the queue"),
+                Some(join) => {
+                    // the spectator leaves the lobby
+                    let player = self.players.remove(&id).expect("is registered");
+                    let _ = player
+                        .notify
+                        .try_send(LobbyNotification::Spectate { table, join });
+                }
+                None => self.error(id, &format!("no game is played at the table {table}")),
+            },
+        }
+    }
+
+    /// the player of the game, and where to hand over the new connection
+    pub fn reconnect(
+        &mut self,
+        session: &SessionToken,
+    ) -> Option<(Player, Sender<Box<dyn PlayerTransport>>)> {
+        self.sessions
+            .get(session)
+            .filter(|(_, reconnect)| !reconnect.is_closed())
+            .cloned()
+    }
+
+    /// where to hand over the connection of a spectator of the game at the table
+    pub fn spectate(&self, table: &str) -> Option<Sender<Box<dyn PlayerTransport>>> {
+        self.running
+            .get(table)
+            .filter(|m| !m.spectate.is_closed())
+            .map(|m| m.spectate.clone())
+    }
+
+    /// the connection is closed
+    pub fn disconnect(&mut self, id: PlayerId) {
+        self.leave(id);
+        self.players.remove(&id);
+    }
+
+    /// close the tables without activity, returns the number of closed tables
+    pub fn cleanup(&mut self, timeout: Duration) -> usize {
+        // the spectators and the sessions are gone with the game
+        self.running.retain(|_, m| !m.spectate.is_closed());
+        self.sessions.retain(|_, (_, s)| !s.is_closed());
+
+        let abandoned = self
+            .tables
+            .values()
+            .filter(|t| t.last_activity.elapsed() >= timeout)
+            .map(|t| t.name.clone())
+            .collect::<Vec<_>>();
+
+        for name in &abandoned {
+            info!("closing abandoned table {name}");
+            let table = self.tables.remove(name).expect("it's in the tables");
+            for id in table.players {
+                self.player_mut(id).table = None;
+                self.respond(id, LobbyResponse::Left);
+            }
+        }
+        abandoned.len()
+    }
+
+    fn player_mut(&mut self, id: PlayerId) -> &mut LobbyPlayer {
+        self.players.get_mut(&id).expect("should be registered")
+    }
+
+    fn respond(&self, id: PlayerId, response: LobbyResponse) {
+        if let Some(player) = self.players.get(&id) {
+            // the connection could be closing, it will disconnect
+            let _ = player
+                .notify
+                .try_send(LobbyNotification::Response(response));
+        }
+    }
+
+    fn error(&self, id: PlayerId, message: &str) {
+        self.respond(id, LobbyResponse::Error(message.into()));
+    }
+
+    fn table_info(&self, table: &Table) -> TableInfo {
+        let name = |id: &PlayerId| self.players[id].name.clone();
+        TableInfo {
+            name: table.name.clone(),
+            players: table.players.iter().map(name).collect(),
+            ready: table.ready.iter().map(name).collect(),
+            playing: false,
+        }
+    }
+
+    fn update_table(&mut self, name: &str) {
+        let Some(table) = self.tables.get_mut(name) else {
+            return;
+        };
+        table.last_activity = Instant::now();
+
+        let table = &self.tables[name];
+        let info = self.table_info(table);
+        for id in &table.players {
+            self.respond(*id, LobbyResponse::TableUpdated(info.clone()));
+        }
+    }
+
+    fn join_table(&mut self, id: PlayerId, name: String) {
+        let table = self.tables.entry(name.clone()).or_insert_with(|| Table {
+            name: name.clone(),
+            players: Vec::new(),
+            ready: Vec::new(),
+            last_activity: Instant::now(),
+        });
+        table.players.push(id);
+        // everyone confirms again with the new player
+        table.ready.clear();
+        self.player_mut(id).table = Some(name.clone());
+        self.update_table(&name);
+    }
+
+    fn leave(&mut self, id: PlayerId) -> bool {
+        let Some(player) = self.players.get_mut(&id) else {
+            return false;
+        };
+
+        if player.queued {
+            player.queued = false;
+            self.queue.retain(|p| *p != id);
+            return true;
+        }
+
+        let Some(name) = player.table.take() else {
+            return false;
+        };
+        if let Some(table) = self.tables.get_mut(&name) {
+            table.players.retain(|p| *p != id);
+            table.ready.clear();
+            if table.players.is_empty() {
+                debug!("removing empty table {name}");
+                self.tables.remove(&name);
+            } else {
+                self.update_table(&name);
+            }
+        }
+        true
+    }
+
+    fn ready(&mut self, id: PlayerId) {
+        let Some(name) = self.players[&id].table.clone() else {
+            return self.error(id, "not at a table");
+        };
+        let table = self
+            .tables
+            .get_mut(&name)
+            .expect("the player is at the table");
+        if table.players.len() < 2 {
+            return self.error(id, "waiting for an opponent");
+        }
+        if !table.ready.contains(&id) {
+            table.ready.push(id);
+        }
+        let start = table.ready.len() == table.players.len();
+        self.update_table(&name);
+
+        if start {
+            self.start_match(&name);
+        }
+    }
+
+    fn start_match(&mut self, name: &str) {
+        let table = self.tables.remove(name).expect("the table is ready");
+        let (handover, transports) = async_channel::bounded(2);
+
+        // the players are in the game, they leave the lobby
+        let p1 = self
+            .players
+            .remove(&table.players[0])
+            .expect("is at the table");
+        let p2 = self
+            .players
+            .remove(&table.players[1])
+            .expect("is at the table");
+        let reconnections = [async_channel::bounded(1), async_channel::bounded(1)];
+        for ((player, notify, opponent), (reconnect, _)) in [
+            (Player::One, &p1.notify, &p2.name),
+            (Player::Two, &p2.notify, &p1.name),
+        ]
+        .into_iter()
+        .zip(&reconnections)
+        {
+            let session = new_session_token();
+            self.sessions
+                .insert(session.clone(), (player, reconnect.clone()));
+
+            let _ = notify.try_send(LobbyNotification::StartMatch {
+                player,
+                opponent: opponent.clone(),
+                session,
+                handover: handover.clone(),
+            });
+        }
+
+        let (spectators, spectate) = Spectators::new(self.spectator_delay);
+        self.running.insert(
+            table.name.clone(),
+            RunningMatch {
+                players: vec![p1.name, p2.name],
+                spectate,
+            },
+        );
+
+        let _ = self.matches.try_send(PendingMatch {
+            table: table.name,
+            player_1: p1.loadout,
+            player_2: p2.loadout,
+            transports,
+            reconnections: reconnections.map(|(_, reconnection)| reconnection),
+            reconnection_grace: self.reconnection_grace,
+            timing: self.timing,
+            spectators,
+        });
+    }
+}
+
+/// The first messages of a connection. The library of the server is sent when it's the only
+/// difference, and the client sends its handshake again
+async fn accept_handshake(transport: &mut Box<dyn PlayerTransport>) -> bool {
+    let server = Handshake::new(&*library().await);
+    let mut library_sent = false;
+    loop {
+        let response = match transport.receive().await {
+            Ok(ClientSend::Handshake(client)) => match server.check(&client) {
+                Ok(()) => HandshakeResponse::Accepted(server.clone()),
+                Err(HandshakeError::Library { .. }) if !library_sent => {
+                    library_sent = true;
+                    HandshakeResponse::Library(library().await.to_bytes())
+                }
+                Err(e) => HandshakeResponse::Rejected(e.to_string()),
+            },
+            Ok(_) => HandshakeResponse::Rejected("the handshake is needed first".into()),
+            Err(_) => return false,
+        };
+        debug!("HANDSHAKE = {response:?}");
+        let accepted = match response {
+            HandshakeResponse::Accepted(_) => Some(true),
+            HandshakeResponse::Library(_) => None,
+            HandshakeResponse::Rejected(_) => Some(false),
+        };
+        if transport
+            .send(ClientReceive::Handshake(response))
+            .await
+            .is_err()
+        {
+            return false;
+        }
+        if let Some(accepted) = accepted {
+            return accepted;
+        }
+    }
+}
+
+/// Lobby messages for a connected player, until the game starts
+pub async fn handle_connection(lobby: SharedLobby, mut transport: Box<dyn PlayerTransport>) {
+    if !accept_handshake(&mut transport).await {
+        return;
+    }
+    let (notify, notifications) = async_channel::unbounded();
+
+    // the player needs to register first, spectators can follow a game instead
+    let id = loop {
+        let request = match transport.receive().await {
+            Ok(ClientSend::Lobby(request)) => request,
+            Ok(message) => {
+                debug!("ignored message in the lobby: {message:?}");
+                continue;
+            }
+            Err(_) => return,
+        };
+        let response = match request {
+            LobbyRequest::Reconnect { session } => {
+                let reconnect = lobby.lock().unwrap().reconnect(&session);
+                match reconnect {
+                    Some((player, reconnect)) => {
+                        let reconnected = LobbyResponse::Reconnected { player };
+                        if transport
+                            .send(ClientReceive::Lobby(reconnected))
+                            .await
+                            .is_ok()
+                        {
+                            let _ = reconnect.send(transport).await;
+                        }
+                        return;
+                    }
+                    None => LobbyResponse::Error("the session is unknown or expired".into()),
+                }
+            }
+            LobbyRequest::Spectate { table } => {
+                let join = lobby.lock().unwrap().spectate(&table);
+                match join {
+                    Some(join) => {
+                        let spectating = LobbyResponse::Spectating { table };
+                        if transport
+                            .send(ClientReceive::Lobby(spectating))
+                            .await
+                            .is_ok()
+                        {
+                            let _ = join.send(transport).await;
+                        }
+                        return;
+                    }
+                    None => LobbyResponse::Error(format!("no game is played at the table {table}")),
+                }
+            }
+            LobbyRequest::Register { name, loadout } => match loadout.validate(&*library().await) {
+                Ok(()) => match lobby
+                    .lock()
+                    .unwrap()
+                    .register(name, loadout, notify.clone())
+                {
+                    Ok(id) => break id,
+                    Err(e) => LobbyResponse::Error(e),
+                },
+                Err(e) => LobbyResponse::Error(format!("invalid loadout: {e}")),
+            },
+            _ => LobbyResponse::Error("register first".into()),
+        };
+        if transport
+            .send(ClientReceive::Lobby(response))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            message = transport.receive() => match message {
+                Ok(ClientSend::Lobby(request)) => lobby.lock().unwrap().handle_request(id, request),
+                Ok(message) => debug!("ignored message in the lobby: {message:?}"),
+                Err(_) => break,
+            },
+            notification = notifications.recv() => match notification {
+                Ok(LobbyNotification::Response(response)) => {
+                    if transport.send(ClientReceive::Lobby(response)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(LobbyNotification::StartMatch { player, opponent, session, handover }) => {
+                    let starting = LobbyResponse::GameStarting { player, opponent, session };
+                    if transport.send(ClientReceive::Lobby(starting)).await.is_ok() {
+                        let _ = handover.send((player, transport)).await;
+                    }
+                    return;
+                }
+                Ok(LobbyNotification::Spectate { table, join }) => {
+                    let spectating = LobbyResponse::Spectating { table };
+                    if transport.send(ClientReceive::Lobby(spectating)).await.is_ok() {
+                        let _ = join.send(transport).await;
+                    }
+                    return;
+                }
+                Err(_) => break,
+            },
+        }
+    }
+
+    lobby.lock().unwrap().disconnect(id);
+}
+
+/// A player in the same process, the channels can be used with [`hocg_fan_sim::client::Client::new`]
+pub fn connect_local(lobby: &SharedLobby) -> (Sender<ClientSend>, Receiver<ClientReceive>) {
+    let (client_send, server_receive) = async_channel::bounded(10);
+    let (server_send, client_receive) = async_channel::bounded(10);
+    tokio::spawn(handle_connection(
+        lobby.clone(),
+        Box::new((server_send, server_receive)),
+    ));
+    (client_send, client_receive)
+}
+
+/// The first messages with the server. Its library is loaded when it's different, which only
+/// works if the library is not in use yet
+pub async fn handshake(
+    channels: &(Sender<ClientSend>, Receiver<ClientReceive>),
+) -> Result<Handshake, String> {
+    let (send, receive) = channels;
+    loop {
+        let handshake = Handshake::new(&*library().await);
+        send.send(ClientSend::Handshake(handshake))
+            .await
+            .map_err(|_| "the server closed the connection".to_string())?;
+        let response = loop {
+            match receive.recv().await {
+                Ok(ClientReceive::Handshake(response)) => break response,
+                Ok(_) => continue,
+                Err(_) => return Err("the server closed the connection".into()),
+            }
+        };
+        debug!("HANDSHAKE = {response:?}");
+        match response {
+            HandshakeResponse::Accepted(server) => return Ok(server),
+            HandshakeResponse::Library(bytes) => load_library(&bytes).await,
+            HandshakeResponse::Rejected(e) => return Err(e),
+        }
+    }
+}
+
+/// Register, then join the table or the queue. Ready when there is an opponent,
+/// and return when the game is starting, with the opponent and the session to reconnect
+pub async fn join_game(
+    channels: &(Sender<ClientSend>, Receiver<ClientReceive>),
+    name: String,
+    loadout: Loadout,
+    table: Option<String>,
+) -> Result<(Player, String, SessionToken), String> {
+    let (send, receive) = channels;
+    let request = |r| async move {
+        send.send(ClientSend::Lobby(r))
+            .await
+            .map_err(|_| "the server closed the connection".to_string())
+    };
+
+    handshake(channels).await?;
+    request(LobbyRequest::Register {
+        name: name.clone(),
+        loadout,
+    })
+    .await?;
+    loop {
+        let response = match receive.recv().await {
+            Ok(ClientReceive::Lobby(response)) => response,
+            Ok(_) => continue,
+            Err(_) => return Err("the server closed the connection".into()),
+        };
+        debug!("LOBBY = {response:?}");
+        match response {
+            LobbyResponse::Registered => match &table {
+                Some(_) => request(LobbyRequest::ListTables).await?,
+                None => request(LobbyRequest::JoinQueue).await?,
+            },
+            LobbyResponse::Tables(tables) => {
+                let table = table.clone().expect("only listed to find the table");
+                if tables.iter().any(|t| t.name == table) {
+                    request(LobbyRequest::JoinTable { table }).await?
+                } else {
+                    request(LobbyRequest::CreateTable { table }).await?
+                }
+            }
+            LobbyResponse::TableUpdated(info) => {
+                if info.players.len() == 2 && !info.ready.contains(&name) {
+                    request(LobbyRequest::Ready).await?
+                }
+            }
+            LobbyResponse::Left => return Err("the table was closed".into()),
+            LobbyResponse::Queued => {}
+            LobbyResponse::GameStarting {
+                player,
+                opponent,
+                session,
+            } => return Ok((player, opponent, session)),
+            LobbyResponse::Spectating { .. } | LobbyResponse::Reconnected { .. } => {}
+            LobbyResponse::Error(e) => return Err(e),
+        }
+    }
+}
+
+/// Follow the game at the table, without registering. Returns when the events of the game
+/// are next
+pub async fn spectate_game(
+    channels: &(Sender<ClientSend>, Receiver<ClientReceive>),
+    table: String,
+) -> Result<(), String> {
+    let (send, receive) = channels;
+    handshake(channels).await?;
+    send.send(ClientSend::Lobby(LobbyRequest::Spectate { table }))
+        .await
+        .map_err(|_| "the server closed the connection".to_string())?;
+    loop {
+        let response = match receive.recv().await {
+            Ok(ClientReceive::Lobby(response)) => response,
+            Ok(_) => continue,
+            Err(_) => return Err("the server closed the connection".into()),
+        };
+        debug!("LOBBY = {response:?}");
+        match response {
+            LobbyResponse::Spectating { .. } => return Ok(()),
+            LobbyResponse::Error(e) => return Err(e),
+            _ => {}
+        }
+    }
+}
+
+/// Continue the game after losing the connection. The client can then use
+/// [`hocg_fan_sim::client::Client::reconnect`] with the same channels
+pub async fn rejoin_game(
+    channels: &(Sender<ClientSend>, Receiver<ClientReceive>),
+    session: SessionToken,
+) -> Result<Player, String> {
+    let (send, receive) = channels;
+    handshake(channels).await?;
+    send.send(ClientSend::Lobby(LobbyRequest::Reconnect { session }))
+        .await
+        .map_err(|_| "the server closed the connection".to_string())?;
+    loop {
+        let response = match receive.recv().await {
+            Ok(ClientReceive::Lobby(response)) => response,
+            Ok(_) => continue,
+            Err(_) => return Err("the server closed the connection".into()),
+        };
+        debug!("LOBBY = {response:?}");
+        match response {
+            LobbyResponse::Reconnected { player } => return Ok(player),
+            LobbyResponse::Error(e) => return Err(e),
+            _ => {}
+        }
+    }
+}
@@ -1,16 +1,19 @@
 #![allow(dead_code)]
 
-use std::{env, iter};
+use std::env;
 
+use hocg_fan_server::{default_loadouts, server};
 use hocg_fan_sim::client::Client;
 use hocg_fan_sim::client::DefaultEventHandler;
 use hocg_fan_sim::gameplay::GameDirector;
-use hocg_fan_sim::library::{load_library, Loadout};
+use hocg_fan_sim::library::load_library;
 use hocg_fan_sim::prompters::RandomPrompter;
 use time::macros::format_description;
 use tracing::info;
 use tracing_subscriber::{fmt::time::LocalTime, EnvFilter};
 
+static DEFAULT_ADDRESS: &str = "0.0.0.0:8080";
+
 #[tokio::main]
 async fn main() {
     env::set_var("RUST_BACKTRACE", "1");
@@ -31,52 +34,31 @@ async fn main() {
         .init();
     info!("\n\n\n\n\n\n\n-- hololive OCG - Fan Simulator is running --");
 
-    let main_deck_hsd01 = Vec::from_iter(
-        None.into_iter()
-            .chain(iter::repeat("hSD01-003".into()).take(4))
-            .chain(iter::repeat("hSD01-004".into()).take(3))
-            .chain(iter::repeat("hSD01-005".into()).take(3))
-            .chain(iter::repeat("hSD01-006".into()).take(2))
-            .chain(iter::repeat("hSD01-007".into()).take(2))
-            .chain(iter::repeat("hSD01-008".into()).take(4))
-            .chain(iter::repeat("hSD01-009".into()).take(3))
-            .chain(iter::repeat("hSD01-010".into()).take(3))
-            .chain(iter::repeat("hSD01-011".into()).take(2))
-            .chain(iter::repeat("hSD01-012".into()).take(2))
-            .chain(iter::repeat("hSD01-013".into()).take(2))
-            .chain(iter::repeat("hSD01-014".into()).take(2))
-            .chain(iter::repeat("hSD01-015".into()).take(2))
-            .chain(iter::repeat("hSD01-016".into()).take(3))
-            .chain(iter::repeat("hSD01-017".into()).take(3))
-            .chain(iter::repeat("hSD01-018".into()).take(3))
-            .chain(iter::repeat("hSD01-019".into()).take(3))
-            .chain(iter::repeat("hSD01-020".into()).take(2))
-            .chain(iter::repeat("hSD01-021".into()).take(2)),
-    );
-    let cheer_deck_hsd01 = Vec::from_iter(
-        None.into_iter()
-            .chain(iter::repeat("hY01-001".into()).take(10))
-            .chain(iter::repeat("hY02-001".into()).take(10)),
-    );
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    // hocg-fan-server [--local] [address]
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--local") {
+        local_game().await;
+    } else {
+        let address = args
+            .iter()
+            .find(|a| !a.starts_with("--"))
+            .map(String::as_str)
+            .unwrap_or(DEFAULT_ADDRESS);
+        server::serve(address).await.unwrap();
+    }
+}
 
-    let player_1 = Loadout {
-        oshi: "hSD01-001".into(), // Tokino Sora
-        main_deck: main_deck_hsd01.clone(),
-        cheer_deck: cheer_deck_hsd01.clone(),
-    };
-    let player_2 = Loadout {
-        oshi: "hSD01-002".into(), // AZKi
-        main_deck: main_deck_hsd01,
-        cheer_deck: cheer_deck_hsd01,
-    };
+/// both players are played randomly, in the same process
+async fn local_game() {
+    let (player_1, player_2) = default_loadouts();
 
     let p1_channel_1 = async_channel::bounded(10);
     let p1_channel_2 = async_channel::bounded(10);
     let p2_channel_1 = async_channel::bounded(10);
     let p2_channel_2 = async_channel::bounded(10);
 
-    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
-
     let mut game = GameDirector::setup(
         &player_1,
         &player_2,
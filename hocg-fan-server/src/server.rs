@@ -0,0 +1,47 @@
+use std::{io, time::Duration};
+
+use hocg_fan_sim::client::websocket;
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+use crate::lobby::{handle_connection, Lobby, ABANDONED_TABLE_TIMEOUT};
+
+/// Accept WebSocket connections in the lobby, each match is run in its own task
+pub async fn serve(address: &str) -> io::Result<()> {
+    let listener = TcpListener::bind(address).await?;
+    info!("listening on {}", listener.local_addr()?);
+
+    let (lobby, matches) = Lobby::new();
+
+    tokio::spawn(async move {
+        while let Ok(pending) = matches.recv().await {
+            tokio::spawn(pending.run());
+        }
+    });
+
+    let cleanup_lobby = lobby.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            cleanup_lobby
+                .lock()
+                .unwrap()
+                .cleanup(ABANDONED_TABLE_TIMEOUT);
+        }
+    });
+
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let lobby = lobby.clone();
+        tokio::spawn(async move {
+            match websocket::accept(stream).await {
+                Ok(transport) => {
+                    info!("player connected: {peer}");
+                    handle_connection(lobby, Box::new(transport)).await;
+                }
+                Err(e) => error!("could not accept {peer}: {e}"),
+            }
+        });
+    }
+}
@@ -0,0 +1,192 @@
+use std::{collections::HashSet, time::Duration};
+
+use async_channel::Receiver;
+use hocg_fan_sim::{
+    events::{ClientReceive, ClientSend, EvaluateEvent, Event, IntentRequest, SyncGameState},
+    gameplay::{CardRef, GameState, Player},
+    lobby::SessionToken,
+    transport::{PlayerTransport, TransportError, TransportFuture},
+};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// the game waits that long for a disconnected player
+pub const RECONNECTION_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+pub fn new_session_token() -> SessionToken {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// The connection of a player during a game. When the connection is lost, the game waits for a
+/// new connection with the session token, until the grace period expires
+pub struct ResumableTransport {
+    player: Player,
+    transport: Box<dyn PlayerTransport>,
+    reconnections: Receiver<Box<dyn PlayerTransport>>,
+    grace_period: Duration,
+    /// what the player knows, sent again after a reconnection
+    state: GameState,
+    /// sent again after a reconnection, if it's not answered
+    pending: Option<IntentRequest>,
+    /// after a reconnection, the player only knows the cards that were visible
+    known: Option<HashSet<CardRef>>,
+    /// the new connection is used, but the player is not synchronized yet
+    resync: bool,
+    /// the end of the grace period, while the player is disconnected
+    grace_deadline: Option<Instant>,
+}
+
+impl ResumableTransport {
+    pub fn new(
+        player: Player,
+        transport: Box<dyn PlayerTransport>,
+        reconnections: Receiver<Box<dyn PlayerTransport>>,
+        grace_period: Duration,
+    ) -> Self {
+        ResumableTransport {
+            player,
+            transport,
+            reconnections,
+            grace_period,
+            state: GameState::default(),
+            pending: None,
+            known: None,
+            resync: false,
+            grace_deadline: None,
+        }
+    }
+
+    /// wait for a new connection, then resynchronize the player. the grace period is not
+    /// restarted if the wait is interrupted, e.g. by an intent timeout
+    async fn resume(&mut self, error: TransportError) -> Result<(), TransportError> {
+        let deadline = *self.grace_deadline.get_or_insert_with(|| {
+            warn!("{:?} is disconnected: {error}", self.player);
+            Instant::now() + self.grace_period
+        });
+        loop {
+            match tokio::time::timeout_at(deadline, self.reconnections.recv()).await {
+                Ok(Ok(transport)) => self.replace(transport),
+                _ => {
+                    info!("{:?} did not reconnect in time", self.player);
+                    return Err(error);
+                }
+            }
+            if self.synchronize().await.is_ok() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// use the new connection, even if the previous one is still open. the player is
+    /// synchronized before the next message
+    fn replace(&mut self, transport: Box<dyn PlayerTransport>) {
+        info!("{:?} reconnected", self.player);
+        self.transport = transport;
+        self.resync = true;
+    }
+
+    /// send the state and the pending request on the new connection. sent again from the start
+    /// if it's interrupted
+    async fn synchronize(&mut self) -> Result<(), TransportError> {
+        if !self.resync {
+            return Ok(());
+        }
+
+        let sync = SyncGameState {
+            state: Box::new(self.state.player_view(self.player)),
+        };
+        self.known = Some(
+            self.state
+                .visible_cards(Some(self.player))
+                .map(|(_, _, c)| c)
+                .collect(),
+        );
+        self.transport
+            .send(ClientReceive::Event(sync.into()))
+            .await?;
+        if let Some(req) = self.pending.clone() {
+            for message in self.with_reveals(ClientReceive::IntentRequest(req)) {
+                self.transport.send(message).await?;
+            }
+        }
+        self.resync = false;
+        self.grace_deadline = None;
+        Ok(())
+    }
+
+    /// after a reconnection, the cards are revealed when the player can see them
+    fn with_reveals(&mut self, message: ClientReceive) -> Vec<ClientReceive> {
+        match &mut self.known {
+            Some(known) => self.state.with_reveals(self.player, message, known),
+            None => vec![message],
+        }
+    }
+
+    /// finish a reconnection, even one that was interrupted
+    async fn reconnected(&mut self) -> Result<(), TransportError> {
+        if let Ok(transport) = self.reconnections.try_recv() {
+            self.replace(transport);
+        }
+        match self.synchronize().await {
+            Ok(()) => Ok(()),
+            Err(error) => self.resume(error).await,
+        }
+    }
+}
+
+impl PlayerTransport for ResumableTransport {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            self.reconnected().await?;
+
+            match &message {
+                ClientReceive::Event(event) => {
+                    event.apply_state_change(&mut self.state);
+                    if let Event::SyncGameState(_) = event {
+                        self.known = None;
+                    }
+                }
+                ClientReceive::IntentRequest(req) => self.pending = Some(req.clone()),
+                ClientReceive::InvalidIntentResponse(_)
+                | ClientReceive::StateChecksum(_)
+                | ClientReceive::Lobby(_)
+                | ClientReceive::Handshake(_) => {}
+            }
+            for message in self.with_reveals(message) {
+                if let Err(error) = self.transport.send(message).await {
+                    // the state and the pending request are sent again
+                    return self.resume(error).await;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        Box::pin(async move {
+            loop {
+                self.reconnected().await?;
+                let message = tokio::select! {
+                    message = self.transport.receive() => message,
+                    // the previous connection could still look open
+                    transport = self.reconnections.recv() => match transport {
+                        Ok(transport) => {
+                            self.replace(transport);
+                            continue;
+                        }
+                        Err(_) => self.transport.receive().await,
+                    },
+                };
+                match message {
+                    Ok(message) => {
+                        if let ClientSend::IntentResponse(_) = message {
+                            self.pending = None;
+                        }
+                        return Ok(message);
+                    }
+                    Err(error) => self.resume(error).await?,
+                }
+            }
+        })
+    }
+}
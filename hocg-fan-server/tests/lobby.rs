@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use async_channel::{Receiver, Sender};
+use hocg_fan_server::{
+    default_loadouts,
+    lobby::{connect_local, handshake, join_game, spectate_game, Lobby, SharedLobby},
+};
+use hocg_fan_sim::{
+    client::{Client, DefaultEventHandler},
+    events::{ClientReceive, ClientSend},
+    gameplay::Player,
+    handshake::{Handshake, HandshakeResponse},
+    library::{library, load_library, GlobalLibrary},
+    lobby::{LobbyRequest, LobbyResponse, TableInfo},
+    prompters::RandomPrompter,
+    tests::*,
+};
+use pretty_assertions::assert_eq;
+
+type LocalClient = (Sender<ClientSend>, Receiver<ClientReceive>);
+
+async fn send(client: &LocalClient, request: LobbyRequest) {
+    client.0.send(ClientSend::Lobby(request)).await.unwrap();
+}
+
+async fn receive(client: &LocalClient) -> LobbyResponse {
+    match client.1.recv().await.unwrap() {
+        ClientReceive::Lobby(response) => response,
+        message => panic!("unexpected message: {message:?}"),
+    }
+}
+
+async fn register(lobby: &SharedLobby, name: &str) -> LocalClient {
+    let client = connect_local(lobby);
+    handshake(&client).await.unwrap();
+    send(
+        &client,
+        LobbyRequest::Register {
+            name: name.into(),
+            loadout: default_loadouts().0,
+        },
+    )
+    .await;
+    assert_eq!(LobbyResponse::Registered, receive(&client).await);
+    client
+}
+
+fn table(name: &str, players: &[&str], ready: &[&str]) -> TableInfo {
+    TableInfo {
+        name: name.into(),
+        players: players.iter().map(|p| p.to_string()).collect(),
+        ready: ready.iter().map(|p| p.to_string()).collect(),
+        playing: false,
+    }
+}
+
+#[tokio::test]
+/// Create a table, join it, ready check and play the game
+async fn lobby_table_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, matches) = Lobby::new();
+
+    let sora = register(&lobby, "sora").await;
+    send(&sora, LobbyRequest::CreateTable { table: "t".into() }).await;
+    assert_eq!(
+        LobbyResponse::TableUpdated(table("t", &["sora"], &[])),
+        receive(&sora).await
+    );
+
+    let azki = register(&lobby, "azki").await;
+    send(&azki, LobbyRequest::ListTables).await;
+    assert_eq!(
+        LobbyResponse::Tables(vec![table("t", &["sora"], &[])]),
+        receive(&azki).await
+    );
+    send(&azki, LobbyRequest::JoinTable { table: "t".into() }).await;
+    let joined = LobbyResponse::TableUpdated(table("t", &["sora", "azki"], &[]));
+    assert_eq!(joined, receive(&sora).await);
+    assert_eq!(joined, receive(&azki).await);
+
+    // not before both players are ready
+    send(&sora, LobbyRequest::Ready).await;
+    let ready = LobbyResponse::TableUpdated(table("t", &["sora", "azki"], &["sora"]));
+    assert_eq!(ready, receive(&sora).await);
+    assert_eq!(ready, receive(&azki).await);
+    assert!(matches.is_empty());
+
+    send(&azki, LobbyRequest::Ready).await;
+    let ready = LobbyResponse::TableUpdated(table("t", &["sora", "azki"], &["sora", "azki"]));
+    assert_eq!(ready, receive(&sora).await);
+    assert_eq!(ready, receive(&azki).await);
+    let LobbyResponse::GameStarting {
+        player: Player::One,
+        opponent,
+        session: sora_session,
+    } = receive(&sora).await
+    else {
+        panic!("sora should be the first player");
+    };
+    assert_eq!("azki", opponent);
+    let LobbyResponse::GameStarting {
+        player: Player::Two,
+        opponent,
+        session: azki_session,
+    } = receive(&azki).await
+    else {
+        panic!("azki should be the second player");
+    };
+    assert_eq!("sora", opponent);
+    assert_ne!(sora_session, azki_session);
+
+    // the game is played with the same connections
+    let game = tokio::spawn(matches.recv().await.unwrap().run());
+    let sora = Client::new(sora, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let sora = tokio::spawn(sora.receive_requests());
+    let azki = Client::new(azki, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let azki = tokio::spawn(azki.receive_requests());
+
+    let outcome = game.await.unwrap().expect("the game should be over");
+    assert_eq!(outcome, sora.await.unwrap());
+    assert_eq!(outcome, azki.await.unwrap());
+}
+
+#[tokio::test]
+/// Two players in the queue are matched together
+async fn lobby_queue() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, matches) = Lobby::new();
+
+    let sora = connect_local(&lobby);
+    let azki = connect_local(&lobby);
+    let (sora, azki) = tokio::join!(
+        join_game(&sora, "sora".into(), default_loadouts().0, None),
+        join_game(&azki, "azki".into(), default_loadouts().1, None),
+    );
+    assert_ne!(sora.unwrap().0, azki.unwrap().0);
+
+    let pending = matches.recv().await.unwrap();
+    assert_eq!("queue-1", pending.table);
+}
+
+#[tokio::test]
+/// A running game can be followed by a spectator
+async fn lobby_spectate() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, matches) = Lobby::new();
+    lobby.lock().unwrap().spectator_delay = 3;
+
+    let sora = connect_local(&lobby);
+    let azki = connect_local(&lobby);
+    let (sora_joined, azki_joined) = tokio::join!(
+        join_game(&sora, "sora".into(), default_loadouts().0, None),
+        join_game(&azki, "azki".into(), default_loadouts().1, None),
+    );
+    azki_joined.unwrap();
+    let game = tokio::spawn(matches.recv().await.unwrap().run());
+
+    let (player_1, player_2) = match sora_joined.unwrap().0 {
+        Player::One => ("sora", "azki"),
+        _ => ("azki", "sora"),
+    };
+    let mut playing = table("queue-1", &[player_1, player_2], &[player_1, player_2]);
+    playing.playing = true;
+    let miko = register(&lobby, "miko").await;
+    send(&miko, LobbyRequest::ListTables).await;
+    assert_eq!(LobbyResponse::Tables(vec![playing]), receive(&miko).await);
+    send(&miko, LobbyRequest::Spectate { table: "u".into() }).await;
+    assert_eq!(
+        LobbyResponse::Error("no game is played at the table u".into()),
+        receive(&miko).await
+    );
+
+    // the spectators don't need to register
+    let noel = connect_local(&lobby);
+    assert_eq!(
+        Err("no game is played at the table u".into()),
+        spectate_game(&noel, "u".into()).await
+    );
+    let suisei = connect_local(&lobby);
+    spectate_game(&suisei, "queue-1".into()).await.unwrap();
+    let suisei = Client::new(suisei, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let suisei = tokio::spawn(suisei.receive_requests());
+
+    let sora = Client::new(sora, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let sora = tokio::spawn(sora.receive_requests());
+    let azki = Client::new(azki, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let azki = tokio::spawn(azki.receive_requests());
+
+    let outcome = game.await.unwrap().expect("the game should be over");
+    assert_eq!(outcome, sora.await.unwrap());
+    assert_eq!(outcome, azki.await.unwrap());
+    assert_eq!(outcome, suisei.await.unwrap());
+
+    // the game is not listed anymore
+    send(&miko, LobbyRequest::ListTables).await;
+    assert_eq!(LobbyResponse::Tables(vec![]), receive(&miko).await);
+}
+
+#[tokio::test]
+/// Invalid loadouts and names are rejected at registration
+async fn lobby_registration() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, _matches) = Lobby::new();
+
+    let sora = connect_local(&lobby);
+    handshake(&sora).await.unwrap();
+    send(
+        &sora,
+        LobbyRequest::Register {
+            name: "sora".into(),
+            loadout: test_loadout("hSD01-001"),
+        },
+    )
+    .await;
+    assert_eq!(
+        LobbyResponse::Error("invalid loadout: 5 copies of hSD01-003, the limit is 4".into()),
+        receive(&sora).await
+    );
+
+    // any other request needs a registration first
+    send(&sora, LobbyRequest::ListTables).await;
+    assert_eq!(
+        LobbyResponse::Error("register first".into()),
+        receive(&sora).await
+    );
+
+    let _sora = register(&lobby, "sora").await;
+    let other = connect_local(&lobby);
+    handshake(&other).await.unwrap();
+    send(
+        &other,
+        LobbyRequest::Register {
+            name: "sora".into(),
+            loadout: default_loadouts().1,
+        },
+    )
+    .await;
+    assert_eq!(
+        LobbyResponse::Error("the name sora is already used".into()),
+        receive(&other).await
+    );
+}
+
+#[tokio::test]
+/// Incompatible clients are rejected, a different library is replaced
+async fn lobby_handshake() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, _matches) = Lobby::new();
+    let server = Handshake::new(&*library().await);
+
+    async fn exchange(lobby: &SharedLobby, message: ClientSend) -> HandshakeResponse {
+        let client = connect_local(lobby);
+        client.0.send(message).await.unwrap();
+        match client.1.recv().await.unwrap() {
+            ClientReceive::Handshake(response) => response,
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+
+    let client = connect_local(&lobby);
+    assert_eq!(Ok(server.clone()), handshake(&client).await);
+
+    assert_eq!(
+        HandshakeResponse::Rejected("the handshake is needed first".into()),
+        exchange(&lobby, ClientSend::Lobby(LobbyRequest::ListTables)).await
+    );
+    let old_protocol = Handshake {
+        protocol_version: 0,
+        ..server.clone()
+    };
+    assert_eq!(
+        HandshakeResponse::Rejected(format!(
+            "incompatible protocol version 0, the server uses version {}",
+            server.protocol_version
+        )),
+        exchange(&lobby, ClientSend::Handshake(old_protocol)).await
+    );
+    let old_engine = Handshake {
+        engine_version: "0.0.0".into(),
+        ..server.clone()
+    };
+    assert_eq!(
+        HandshakeResponse::Rejected(format!(
+            "incompatible engine version 0.0.0, the server uses version {}",
+            server.engine_version
+        )),
+        exchange(&lobby, ClientSend::Handshake(old_engine)).await
+    );
+
+    // the library of the server is sent once
+    let other_library = Handshake {
+        library_hash: 0,
+        ..server.clone()
+    };
+    let client = connect_local(&lobby);
+    client
+        .0
+        .send(ClientSend::Handshake(other_library.clone()))
+        .await
+        .unwrap();
+    let Ok(ClientReceive::Handshake(HandshakeResponse::Library(bytes))) = client.1.recv().await
+    else {
+        panic!("the library should be sent");
+    };
+    let mut decoder = flate2::read::GzDecoder::new(&bytes[..]);
+    let sent: GlobalLibrary =
+        bincode::decode_from_std_read(&mut decoder, bincode::config::standard()).unwrap();
+    assert_eq!(server.library_hash, sent.library_hash());
+    // only the size of the library is logged
+    assert_eq!(
+        format!("Library({} bytes)", bytes.len()),
+        format!("{:?}", HandshakeResponse::Library(bytes))
+    );
+    client
+        .0
+        .send(ClientSend::Handshake(other_library))
+        .await
+        .unwrap();
+    assert_eq!(
+        Ok(ClientReceive::Handshake(HandshakeResponse::Rejected(
+            format!(
+                "different card library 0000000000000000, the server uses {:016x}",
+                server.library_hash
+            )
+        ))),
+        client.1.recv().await
+    );
+}
+
+#[tokio::test]
+/// Tables are removed when they are empty or abandoned
+async fn lobby_cleanup() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let (lobby, _matches) = Lobby::new();
+
+    let sora = register(&lobby, "sora").await;
+    send(&sora, LobbyRequest::CreateTable { table: "t".into() }).await;
+    receive(&sora).await;
+    send(&sora, LobbyRequest::Leave).await;
+    assert_eq!(LobbyResponse::Left, receive(&sora).await);
+    send(&sora, LobbyRequest::ListTables).await;
+    assert_eq!(LobbyResponse::Tables(vec![]), receive(&sora).await);
+
+    // the table is closed after a while, without activity
+    send(&sora, LobbyRequest::CreateTable { table: "t".into() }).await;
+    receive(&sora).await;
+    assert_eq!(0, lobby.lock().unwrap().cleanup(Duration::from_secs(60)));
+    assert_eq!(1, lobby.lock().unwrap().cleanup(Duration::ZERO));
+    assert_eq!(LobbyResponse::Left, receive(&sora).await);
+
+    // or when the last player disconnects
+    send(&sora, LobbyRequest::CreateTable { table: "t".into() }).await;
+    receive(&sora).await;
+    drop(sora);
+    let azki = register(&lobby, "azki").await;
+    // the disconnection is noticed by another task
+    for _ in 0..100 {
+        send(&azki, LobbyRequest::ListTables).await;
+        if receive(&azki).await == LobbyResponse::Tables(vec![]) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("the table should be removed");
+}
@@ -0,0 +1,213 @@
+use std::time::Duration;
+
+use hocg_fan_server::{
+    default_loadouts,
+    lobby::{connect_local, join_game, rejoin_game, Lobby, SharedLobby},
+    session::ResumableTransport,
+};
+use hocg_fan_sim::{
+    client::{Client, DefaultEventHandler},
+    events::{ClientReceive, ClientSend},
+    gameplay::{GameDirector, GameOutcome, GameOverReason, Player},
+    library::load_library,
+    lobby::SessionToken,
+    prompters::RandomPrompter,
+    timing::TimingRules,
+    transport::{DirectTransport, PlayerTransport, TransportFuture},
+};
+use pretty_assertions::assert_eq;
+use tokio::task::JoinHandle;
+
+struct TestGame {
+    lobby: SharedLobby,
+    game: JoinHandle<Option<GameOutcome>>,
+    /// plays until the end
+    player: (Player, JoinHandle<GameOutcome>),
+    /// loses its connection
+    other: (Player, Client<DefaultEventHandler, RandomPrompter>),
+    session: SessionToken,
+}
+
+/// Both players join the queue, the second one plays a few steps then loses its connection
+async fn disconnected_game(grace_period: Duration) -> TestGame {
+    let (lobby, matches) = Lobby::new();
+    lobby.lock().unwrap().reconnection_grace = grace_period;
+
+    let sora = connect_local(&lobby);
+    let azki = connect_local(&lobby);
+    let (sora_joined, azki_joined) = tokio::join!(
+        join_game(&sora, "sora".into(), default_loadouts().0, None),
+        join_game(&azki, "azki".into(), default_loadouts().1, None),
+    );
+    let (sora_player, _, _) = sora_joined.unwrap();
+    let (azki_player, _, session) = azki_joined.unwrap();
+    let game = tokio::spawn(matches.recv().await.unwrap().run());
+
+    let sora = Client::new(sora, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let sora = tokio::spawn(sora.receive_requests());
+    let mut azki = Client::new(azki, DefaultEventHandler::new(), RandomPrompter::new()).await;
+    for _ in 0..20 {
+        azki.handle_request().await.unwrap();
+    }
+    azki.send.close();
+    azki.receive.close();
+
+    TestGame {
+        lobby,
+        game,
+        player: (sora_player, sora),
+        other: (azki_player, azki),
+        session,
+    }
+}
+
+#[tokio::test]
+/// The game continues after a reconnection with the session
+async fn reconnect_during_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let TestGame {
+        lobby,
+        game,
+        player: (_, sora),
+        other: (azki_player, mut azki),
+        session,
+    } = disconnected_game(Duration::from_secs(10)).await;
+
+    assert_eq!(
+        GameOutcome {
+            winning_player: None,
+            reason: GameOverReason::Disconnect
+        },
+        azki.play().await
+    );
+    assert!(azki.disconnected);
+
+    let channels = connect_local(&lobby);
+    assert_eq!(
+        Err("the session is unknown or expired".into()),
+        rejoin_game(&channels, "wrong".into()).await
+    );
+    let channels = connect_local(&lobby);
+    assert_eq!(Ok(azki_player), rejoin_game(&channels, session).await);
+    azki.reconnect(channels);
+    let azki = tokio::spawn(async move {
+        let outcome = azki.play().await;
+        assert!(!azki.disconnected);
+        outcome
+    });
+
+    let outcome = game.await.unwrap().expect("the game should be over");
+    assert_ne!(GameOverReason::Disconnect, outcome.reason);
+    assert_eq!(outcome, sora.await.unwrap());
+    assert_eq!(outcome, azki.await.unwrap());
+}
+
+/// A new connection, the first message takes longer than the intent timeout
+struct SlowTransport {
+    transport: Box<dyn PlayerTransport>,
+    delay: Option<Duration>,
+}
+
+impl PlayerTransport for SlowTransport {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            if let Some(delay) = self.delay.take() {
+                tokio::time::sleep(delay).await;
+            }
+            self.transport.send(message).await
+        })
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        self.transport.receive()
+    }
+}
+
+#[tokio::test]
+/// The request times out while the player is synchronized after a reconnection, the player
+/// is still synchronized
+async fn reconnect_during_timed_request() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    // the first connection is still open, but it does not respond
+    let (silent_send, silent_received) = async_channel::unbounded();
+    let (_silent_responses, silent_receive) = async_channel::unbounded::<ClientSend>();
+    let (reconnect, reconnections) = async_channel::bounded(1);
+    let p1 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let p2 = ResumableTransport::new(
+        Player::Two,
+        Box::new((silent_send, silent_receive)),
+        reconnections,
+        Duration::from_secs(10),
+    );
+    let (sora, azki) = default_loadouts();
+    let mut game = GameDirector::setup(&sora, &azki, p1, p2).await;
+    game.enable_timing(TimingRules {
+        intent_timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    });
+    let game = tokio::spawn(async move {
+        if game.start_game().await.is_ok() {
+            while game.next_step().await.is_ok() {}
+        }
+        game.game.game_outcome()
+    });
+
+    // reconnect while the game waits for the response
+    while !matches!(
+        silent_received.recv().await,
+        Ok(ClientReceive::IntentRequest(_))
+    ) {}
+    let (client_send, server_receive) = async_channel::bounded(10);
+    let (server_send, client_receive) = async_channel::bounded(10);
+    reconnect
+        .send(Box::new(SlowTransport {
+            transport: Box::new((server_send, server_receive)),
+            delay: Some(Duration::from_millis(200)),
+        }))
+        .await
+        .unwrap();
+    let mut azki = Client::new(
+        (client_send, client_receive),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+
+    let outcome = azki.play().await;
+    assert!(!azki.disconnected);
+    assert_eq!(0, azki.desyncs);
+    assert_eq!(Some(outcome), game.await.unwrap());
+}
+
+#[tokio::test]
+/// The player loses when the grace period expires
+async fn reconnection_expired() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+    let TestGame {
+        lobby,
+        game,
+        player: (sora_player, sora),
+        other: (_, mut azki),
+        session,
+    } = disconnected_game(Duration::from_millis(50)).await;
+    azki.play().await;
+
+    let expected = GameOutcome {
+        winning_player: Some(sora_player),
+        reason: GameOverReason::Disconnect,
+    };
+    assert_eq!(Some(expected), game.await.unwrap());
+    assert_eq!(expected, sora.await.unwrap());
+
+    let channels = connect_local(&lobby);
+    assert_eq!(
+        Err("the session is unknown or expired".into()),
+        rejoin_game(&channels, session).await
+    );
+}
@@ -9,8 +9,8 @@ use proc_macro::TokenStream;
 use proc_macro_roids::namespace_parameters;
 use quote::{format_ident, quote};
 use syn::{
-    parse_quote, Data, DataEnum, DeriveInput, Expr, ExprLit, Fields, FieldsUnnamed, Lit, Meta,
-    Path, Variant,
+    parse_quote, Data, DataEnum, DeriveInput, Expr, ExprLit, Fields, FieldsUnnamed,
+    GenericArgument, Ident, Lit, Meta, Path, PathArguments, Type, TypePath, Variant,
 };
 
 // TODO cleanup this file
@@ -44,7 +44,7 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     let ser_variants_arms = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
 
-        let (token, infix, transparent, _default) = attributes(variant, &ns);
+        let (token, infix, transparent, _default, _precedence) = attributes(variant, &ns);
         let variant_fields = &variant.fields;
 
         let variant_capture = match variant_fields {
@@ -107,7 +107,7 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     let de_variants_tokens2 = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
 
-        let (token, infix, transparent, _default) = attributes(variant, &ns);
+        let (token, infix, transparent, _default, _precedence) = attributes(variant, &ns);
         let variant_fields = &variant.fields;
 
         if transparent || token.is_none() {
@@ -194,10 +194,11 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     let de_variants_infix2 = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
 
-        let (token, infix, transparent, _default) = attributes(variant, &ns);
+        let (token, infix, transparent, _default, _precedence) = attributes(variant, &ns);
         let variant_fields = &variant.fields;
 
-        if transparent || token.is_some() || infix.is_none() {
+        if transparent || token.is_some() || infix.is_none() || is_chained_infix(variant, enum_name)
+        {
             return quote! {};
         }
 
@@ -205,7 +206,7 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
 
         match variant_fields {
             Fields::Unit => quote! {
-                //     // - token -
+                //     // - infix -
                 //     if s == "value" {
                 //         return Ok((Value, t));
                 //     }
@@ -223,46 +224,82 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
                         let (#n, t) = t.take_param()?;
                     }
                 });
-                let first_param = take_params.next();
+                // the first param is taken before the infix
+                take_params.next();
+                let first_n = format_ident!("_0");
 
                 // // - infix -
-                // if let Ok((s, t)) = tokens[1..].take_string() {
-                //     if s == "and" {
-                //         let t = &tokens[..1];
-                //         let (_0, t) = t.take_param()?;
-                //         let t = &tokens[2..];
-                //         let (_1, t) = t.take_param()?;
-                //         return Ok((Infix(_0, _1), t)); // TODO the "t" here is too short, is that true?
+                // let t = tokens;
+                // if let Ok((_0, t)) = t.take_param() {
+                //     if let Ok((s, t)) = t.take_string() {
+                //         if s == "==" {
+                //             let (_1, t) = t.take_param()?;
+                //             return Ok((Infix(_0, _1), t));
+                //         }
                 //     }
                 // }
                 quote! {
-                    if s == #infix {
-                        let t = &tokens[..1];
-                        #first_param
-                        let t = &tokens[2..];
-                        #(#take_params)*
-                        return Ok((#enum_name::#variant_name(#(#ns,)*), t));
+                    let t = tokens;
+                    if let Ok((#first_n, t)) = t.take_param() {
+                        if let Ok((s, t)) = t.take_string() {
+                            if s == #infix {
+                                #(#take_params)*
+                                return Ok((#enum_name::#variant_name(#(#ns,)*), t));
+                            }
+                        }
                     }
                 }
             }
             a => panic!("{:?}", a),
         }
     });
-    let has_infix = variants.iter().any(|v| attributes(v, &ns).1.is_some());
-    let de_variants_infix2 = if has_infix {
+
+    // // - chained infix -
+    // loop {
+    //     let Ok((s, rest)) = t.take_string() else {
+    //         break;
+    //     };
+    //     if s == "and" && 2 >= min_precedence {
+    //         let (rhs, rest) = Self::parse_tokens_with_precedence(rest, 2 + 1)?;
+    //         value = And(value.into(), rhs.into());
+    //         t = rest;
+    //         continue;
+    //     }
+    //     break;
+    // }
+    let de_variants_chained_infix2 = variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+
+        let (token, infix, transparent, _default, precedence) = attributes(variant, &ns);
+
+        if transparent
+            || token.is_some()
+            || infix.is_none()
+            || !is_chained_infix(variant, enum_name)
+        {
+            return quote! {};
+        }
+
+        let infix = infix.unwrap();
+
         quote! {
-            if let Ok((s, t)) = tokens[1..].take_string() {
-                #(#de_variants_infix2)*
+            if s == #infix && #precedence >= min_precedence {
+                let (rhs, rest) = Self::parse_tokens_with_precedence(rest, #precedence + 1)?;
+                value = #enum_name::#variant_name(value.into(), rhs.into());
+                t = rest;
+                continue;
             }
         }
-    } else {
-        quote! {}
-    };
+    });
+    let has_chained_infix = variants.iter().any(|v| {
+        let (token, infix, transparent, _default, _precedence) = attributes(v, &ns);
+        !transparent && token.is_none() && infix.is_some() && is_chained_infix(v, enum_name)
+    });
 
     let de_variants_transparent2 = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
 
-        let (token, infix, transparent, _default) = attributes(variant, &ns);
+        let (token, infix, transparent, _default, _precedence) = attributes(variant, &ns);
         let variant_fields = &variant.fields;
 
         if !transparent || token.is_some() || infix.is_some() {
@@ -304,7 +341,7 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     let de_variants_default2 = variants.iter().map(|variant| {
         let variant_name = &variant.ident;
 
-        let (_token, _infix, _transparent, default) = attributes(variant, &ns);
+        let (_token, _infix, _transparent, default, _precedence) = attributes(variant, &ns);
         let variant_fields = &variant.fields;
 
         if !default {
@@ -386,26 +423,71 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     //     tokens.take_string()?.0.clone(),
     // ))
     let str_enum_name = format!("{enum_name}");
-    let impl_de_for_enum = quote! {
-        impl crate::card_effects::parse::ParseTokens for #enum_name {
+    let parse_tokens_body = quote! {
+        if tokens.is_empty() {
+            return Err(crate::card_effects::error::Error::ExpectedToken);
+        }
+
+        // println!("{:?} - {:?}", #str_enum_name, tokens);
 
-            #de_variants_default2
+        #(#de_variants_infix2)*
+        #de_variants_tokens2
+        #(#de_variants_transparent2)*
 
-            fn parse_tokens(tokens: &[crate::card_effects::parse::Tokens]) -> std::result::Result<(Self, &[crate::card_effects::parse::Tokens]), crate::card_effects::error::Error> {
-                if tokens.is_empty() {
-                    return Err(crate::card_effects::error::Error::ExpectedToken);
+        return Err(crate::card_effects::error::Error::UnexpectedToken(#str_enum_name.into(), tokens.take_string()?.0.clone()));
+    };
+    let impl_de_for_enum = if has_chained_infix {
+        // chained infix are parsed with precedence climbing, the rest are primary expressions
+        quote! {
+            impl crate::card_effects::parse::ParseTokens for #enum_name {
+
+                #de_variants_default2
+
+                fn parse_tokens(tokens: &[crate::card_effects::parse::Tokens]) -> std::result::Result<(Self, &[crate::card_effects::parse::Tokens]), crate::card_effects::error::Error> {
+                    Self::parse_tokens_with_precedence(tokens, 0)
                 }
+            }
+
+            impl #enum_name {
+                fn parse_tokens_with_precedence(tokens: &[crate::card_effects::parse::Tokens], min_precedence: u8) -> std::result::Result<(Self, &[crate::card_effects::parse::Tokens]), crate::card_effects::error::Error> {
+                    let (mut value, mut t) = Self::parse_primary_tokens(tokens)?;
 
-                // println!("{:?} - {:?}", #str_enum_name, tokens);
+                    loop {
+                        let Ok((s, rest)) = t.take_string() else {
+                            break;
+                        };
+                        #(#de_variants_chained_infix2)*
+                        break;
+                    }
 
-                #de_variants_infix2
-                #de_variants_tokens2
-                #(#de_variants_transparent2)*
+                    Ok((value, t))
+                }
 
-                return Err(crate::card_effects::error::Error::UnexpectedToken(#str_enum_name.into(), tokens.take_string()?.0.clone()));
+                fn parse_primary_tokens(tokens: &[crate::card_effects::parse::Tokens]) -> std::result::Result<(Self, &[crate::card_effects::parse::Tokens]), crate::card_effects::error::Error> {
+                    // sub expression in parentheses
+                    if let Some(crate::card_effects::parse::Tokens::List(_)) = tokens.first() {
+                        if let Ok((value, t)) = tokens.take_param() {
+                            return Ok((value, t));
+                        }
+                    }
+
+                    #parse_tokens_body
+                }
             }
         }
+    } else {
+        quote! {
+            impl crate::card_effects::parse::ParseTokens for #enum_name {
+
+                #de_variants_default2
 
+                fn parse_tokens(tokens: &[crate::card_effects::parse::Tokens]) -> std::result::Result<(Self, &[crate::card_effects::parse::Tokens]), crate::card_effects::error::Error> {
+                    #parse_tokens_body
+                }
+            }
+        }
+    };
+    let impl_deserialize_for_enum = quote! {
         impl<'de> serde::Deserialize<'de> for #enum_name {
             fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
             where
@@ -420,10 +502,41 @@ fn ser_de_token_for_enum_impl(ast: DeriveInput) -> proc_macro2::TokenStream {
     // put it all together
     ser_de_token_for_enum.extend(impl_ser_for_enum);
     ser_de_token_for_enum.extend(impl_de_for_enum);
+    ser_de_token_for_enum.extend(impl_deserialize_for_enum);
 
     ser_de_token_for_enum
 }
 
+/// Infix variants with both operands of the same enum can be chained, e.g. `a + b * c`.
+fn is_chained_infix(variant: &Variant, enum_name: &Ident) -> bool {
+    fn is_enum_type(ty: &Type, enum_name: &Ident) -> bool {
+        let Type::Path(TypePath { path, .. }) = ty else {
+            return false;
+        };
+        let Some(segment) = path.segments.last() else {
+            return false;
+        };
+        if segment.ident == *enum_name {
+            return true;
+        }
+        // also allow Box<Enum>
+        if segment.ident != "Box" {
+            return false;
+        }
+        let PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return false;
+        };
+        matches!(args.args.first(), Some(GenericArgument::Type(ty)) if is_enum_type(ty, enum_name))
+    }
+
+    match &variant.fields {
+        Fields::Unnamed(FieldsUnnamed { unnamed, .. }) => {
+            unnamed.len() == 2 && unnamed.iter().all(|f| is_enum_type(&f.ty, enum_name))
+        }
+        _ => false,
+    }
+}
+
 fn data_enum(ast: &DeriveInput) -> &DataEnum {
     if let Data::Enum(data_enum) = &ast.data {
         data_enum
@@ -432,12 +545,13 @@ fn data_enum(ast: &DeriveInput) -> &DataEnum {
     }
 }
 
-fn attributes(variant: &Variant, ns: &Path) -> (Option<String>, Option<String>, bool, bool) {
+fn attributes(variant: &Variant, ns: &Path) -> (Option<String>, Option<String>, bool, bool, u8) {
     let evt_meta_lists = namespace_parameters(&variant.attrs, ns);
     let mut token = None;
     let mut infix = None;
     let mut transparent = false;
     let mut default = false;
+    let mut precedence = 0;
     for meta in evt_meta_lists {
         match meta {
             Meta::NameValue(name_value) => {
@@ -459,6 +573,17 @@ fn attributes(variant: &Variant, ns: &Path) -> (Option<String>, Option<String>,
                 ) = (name_value.path.is_ident("infix"), &name_value.value)
                 {
                     infix = Some(lit_str.value());
+                } else if let (
+                    true,
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Int(lit_int),
+                        ..
+                    }),
+                ) = (name_value.path.is_ident("precedence"), &name_value.value)
+                {
+                    precedence = lit_int
+                        .base10_parse()
+                        .expect("precedence should be a small number");
                 } else {
                     panic!("Expected `hocg_fan_sim` attribute argument in the form: `#[hocg_fan_sim(token = \"some_token\")]`");
                 }
@@ -479,7 +604,7 @@ fn attributes(variant: &Variant, ns: &Path) -> (Option<String>, Option<String>,
         token.is_some() || infix.is_some() || transparent,
         "Expected to have at least one of (token, infix, transparent)"
     );
-    (token, infix, transparent, default)
+    (token, infix, transparent, default, precedence)
 }
 
 #[cfg(test)]
@@ -609,13 +734,13 @@ mod tests {
                     if tokens.is_empty() {
                         return Err(crate::card_effects::error::Error::ExpectedToken);
                     }
-                    if let Ok((s, t)) = tokens[1..].take_string() {
-                        if s == "+" {
-                            let t = &tokens[..1];
-                            let (_0, t) = t.take_param()?;
-                            let t = &tokens[2..];
-                            let (_1, t) = t.take_param()?;
-                            return Ok((MyEnum::TupleInfix(_0, _1,), t));
+                    let t = tokens;
+                    if let Ok((_0, t)) = t.take_param() {
+                        if let Ok((s, t)) = t.take_string() {
+                            if s == "+" {
+                                let (_1, t) = t.take_param()?;
+                                return Ok((MyEnum::TupleInfix(_0, _1,), t));
+                            }
                         }
                     }
                     if let Ok((s, t)) = tokens.take_string() {
@@ -2,6 +2,8 @@ pub mod effects;
 pub mod error;
 pub mod evaluate;
 pub mod parse;
+pub mod text;
+pub mod trace;
 
 pub use effects::*;
 pub use error::*;
@@ -38,10 +38,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     events::{EnterStep, Event, ExitStep, TriggeredEvent},
-    gameplay::Step,
+    gameplay::{CardRef, Game, Step},
 };
 
-use super::*;
+use super::{evaluate::EvaluateEffect, *};
 
 #[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub struct Var(pub String);
@@ -97,6 +97,33 @@ impl ParseTokens for NumberLiteral {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct StringLiteral(pub String);
+
+impl From<StringLiteral> for Tokens {
+    fn from(value: StringLiteral) -> Self {
+        format!("\"{}\"", value.0).as_str().into()
+    }
+}
+
+impl ParseTokens for StringLiteral {
+    fn default_effect() -> Option<Self> {
+        None
+    }
+
+    fn parse_tokens(tokens: &[Tokens]) -> Result<(Self, &[Tokens])> {
+        if let Ok((s, t)) = tokens.take_string() {
+            if s.len() >= 2 && s.starts_with('"') && s.ends_with('"') {
+                return Ok((StringLiteral(s[1..s.len() - 1].into()), t));
+            }
+        }
+        Err(Error::UnexpectedToken(
+            "StringLiteral".into(),
+            tokens.take_string()?.0.clone(),
+        ))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 // let <$var> = <value> | <player> | <target>
 pub struct Let<T>(pub Var, pub T);
@@ -142,6 +169,9 @@ impl<T: ParseTokens + Debug> ParseTokens for Let<T> {
 
 #[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub enum Action {
+    // add_counter <[card_ref]> <name> <value> <life_time> -> <action>
+    #[hocg_fan_sim(token = "add_counter")]
+    AddCounter(CardReferences, StringLiteral, Number, LifeTime),
     // add_global_mod <mod> <life_time> -> action
     #[hocg_fan_sim(token = "add_global_mod")]
     AddGlobalModifier(Player, Modifier, LifeTime),
@@ -151,18 +181,36 @@ pub enum Action {
     // add_zone_mod <zone> <mod> <life_time> -> action
     #[hocg_fan_sim(token = "add_zone_mod")]
     AddZoneModifier(Zone, Modifier, LifeTime),
+    // archive_attached_cheers <[card_ref]> -> <action>
+    #[hocg_fan_sim(token = "archive_attached_cheers")]
+    ArchiveAttachedCheers(CardReferences),
     // attach_cards <[card_ref]> <card_ref> -> <action>
     #[hocg_fan_sim(token = "attach_cards")]
     AttachCards(CardReferences, CardReference),
+    // bloom <card_ref> <card_ref> -> <action>
+    #[hocg_fan_sim(token = "bloom")]
+    Bloom(CardReference, CardReference),
+    // choose_one <[[action]]> -> <action>
+    #[hocg_fan_sim(token = "choose_one")]
+    ChooseOne(Vec<Vec<Action>>),
     // deal_damage <[card_ref]> <value> -> <action>
     #[hocg_fan_sim(token = "deal_damage")]
     DealDamage(CardReferences, Number),
     // deal_special_damage <[card_ref]> <value> -> <action>
     #[hocg_fan_sim(token = "deal_special_damage")]
     DealSpecialDamage(CardReferences, Number),
+    // discard <[card_ref]> -> <action>
+    #[hocg_fan_sim(token = "discard")]
+    Discard(CardReferences),
     // draw <value> -> <action>
     #[hocg_fan_sim(token = "draw")]
     Draw(Number),
+    // for_each <$var> in <[card_ref]> <[action]> -> <action>
+    #[hocg_fan_sim(token = "for_each", infix = "in")]
+    ForEach(Var, CardReferences, Vec<Action>),
+    // heal <[card_ref]> <value> -> <action>
+    #[hocg_fan_sim(token = "heal")]
+    Heal(CardReferences, Number),
     // if <condition> <[action]> -> <action>
     #[hocg_fan_sim(token = "if")]
     If(Condition, Vec<Action>),
@@ -184,9 +232,21 @@ pub enum Action {
     // no_action -> <action>
     #[hocg_fan_sim(default, token = "no_action")]
     Noop,
+    // remove_counter <[card_ref]> <name> <value> -> <action>
+    #[hocg_fan_sim(token = "remove_counter")]
+    RemoveCounter(CardReferences, StringLiteral, Number),
+    // repeat <value> <[action]> -> <action>
+    #[hocg_fan_sim(token = "repeat")]
+    Repeat(Number, Vec<Action>),
+    // return_to_hand <[card_ref]> -> <action>
+    #[hocg_fan_sim(token = "return_to_hand")]
+    ReturnToHand(CardReferences),
     // reveal <[card_ref]> -> <action>
     #[hocg_fan_sim(token = "reveal")]
     Reveal(CardReferences),
+    // search <value> <condition> -> <action>
+    #[hocg_fan_sim(token = "search")]
+    Search(Number, Condition),
     // send_to <zone> <[card_ref]> -> <action>
     #[hocg_fan_sim(token = "send_to")]
     SendTo(Zone, CardReferences),
@@ -199,6 +259,9 @@ pub enum Action {
     // shuffle <zone> -> <action>
     #[hocg_fan_sim(token = "shuffle")]
     Shuffle(Zone),
+    // swap_center <card_ref> -> <action>
+    #[hocg_fan_sim(token = "swap_center")]
+    SwapCenter(CardReference),
 }
 
 #[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
@@ -270,7 +333,7 @@ pub enum Condition {
     #[hocg_fan_sim(token = "all")]
     All(CardReferences, Box<Condition>),
     // <condition> and <condition> -> <condition>
-    #[hocg_fan_sim(infix = "and")]
+    #[hocg_fan_sim(infix = "and", precedence = 2)]
     And(Box<Condition>, Box<Condition>),
     // any <[card_ref]> <condition> -> <condition>
     #[hocg_fan_sim(token = "any")]
@@ -287,13 +350,22 @@ pub enum Condition {
     // false -> <condition>
     #[hocg_fan_sim(token = "false")]
     False,
+    // <value> > <value> -> <condition>
+    #[hocg_fan_sim(infix = ">")]
+    GreaterThan(Number, Number),
     // <value> >= <value> -> <condition>
     #[hocg_fan_sim(infix = ">=")]
     GreaterThanEquals(Number, Number),
+    // [active_card] has_attribute <attribute> -> <condition>
+    #[hocg_fan_sim(token = "has_attribute")]
+    HasAttribute(Attribute),
     // [active_card] has_cheers -> <condition>
     #[hocg_fan_sim(token = "has_cheers")]
     HasCheers,
-    // [active_card] is_attribute_buzz -> <condition>
+    // [active_card] has_tag <tag> -> <condition>
+    #[hocg_fan_sim(token = "has_tag")]
+    HasTag(HashTag),
+    // [active_card] is_attribute_buzz -> <condition> (alias of has_attribute buzz)
     #[hocg_fan_sim(token = "is_attribute_buzz")]
     IsAttributeBuzz,
     // [active_card] is_color <color> -> <condition>
@@ -308,25 +380,31 @@ pub enum Condition {
     // [active_card] is_in_zone <zone> -> <condition>
     #[hocg_fan_sim(token = "is_in_zone")]
     IsInZone(Zone),
-    // [active_card] is_level_first -> <condition>
+    // [active_card] is_level <level> -> <condition>
+    #[hocg_fan_sim(token = "is_level")]
+    IsLevel(Level),
+    // [active_card] is_level_first -> <condition> (alias of is_level first)
     #[hocg_fan_sim(token = "is_level_first")]
     IsLevelFirst,
-    // [active_card] is_level_second -> <condition>
+    // [active_card] is_level_second -> <condition> (alias of is_level second)
     #[hocg_fan_sim(token = "is_level_second")]
     IsLevelSecond,
     // [active_card] is_member -> <condition>
     #[hocg_fan_sim(token = "is_member")]
     IsMember,
-    // [active_card] is_named_azki -> <condition>
+    // [active_card] is_named <"name"> -> <condition>
+    #[hocg_fan_sim(token = "is_named")]
+    IsNamed(StringLiteral),
+    // [active_card] is_named_azki -> <condition> (alias of is_named "AZKi")
     #[hocg_fan_sim(token = "is_named_azki")]
     IsNamedAzki,
-    // [active_card] is_named_omaru_polka -> <condition>
+    // [active_card] is_named_omaru_polka -> <condition> (alias of is_named "Omaru Polka")
     #[hocg_fan_sim(token = "is_named_omaru_polka")]
     IsNamedOmaruPolka,
-    // [active_card] is_named_tokino_sora -> <condition>
+    // [active_card] is_named_tokino_sora -> <condition> (alias of is_named "Tokino Sora")
     #[hocg_fan_sim(token = "is_named_tokino_sora")]
     IsNamedTokinoSora,
-    // [active_card] is_named_usada_pekora -> <condition>
+    // [active_card] is_named_usada_pekora -> <condition> (alias of is_named "Usada Pekora")
     #[hocg_fan_sim(token = "is_named_usada_pekora")]
     IsNamedUsadaPekora,
     // [active_card] is_card <card_ref> -> <condition>
@@ -338,17 +416,29 @@ pub enum Condition {
     // is_odd <value> -> <condition>
     #[hocg_fan_sim(token = "is_odd")]
     IsOdd(Number),
+    // [active_card] is_oshi -> <condition>
+    #[hocg_fan_sim(token = "is_oshi")]
+    IsOshi,
+    // [active_card] is_support_kind <support_kind> -> <condition>
+    #[hocg_fan_sim(token = "is_support_kind")]
+    IsSupportKind(SupportKind),
     // [active_card] is_support_limited -> <condition>
     #[hocg_fan_sim(token = "is_support_limited")]
     IsSupportLimited,
+    // <value> < <value> -> <condition>
+    #[hocg_fan_sim(infix = "<")]
+    LessThan(Number, Number),
     // <value> <= <value> -> <condition>
     #[hocg_fan_sim(infix = "<=")]
     LessThanEquals(Number, Number),
     // not <condition> -> <condition>
     #[hocg_fan_sim(token = "not")]
     Not(Box<Condition>),
+    // <value> != <value> -> <condition>
+    #[hocg_fan_sim(infix = "!=")]
+    NotEquals(Number, Number),
     // <condition> or <condition> -> <condition>
-    #[hocg_fan_sim(infix = "or")]
+    #[hocg_fan_sim(infix = "or", precedence = 1)]
     Or(Box<Condition>, Box<Condition>),
     // true -> <condition>
     #[hocg_fan_sim(default, token = "true")]
@@ -419,12 +509,30 @@ pub enum Modifier {
     // as_cheer <number> <color> -> <mod>
     #[hocg_fan_sim(token = "as_cheer")]
     AsCheer(Number, Color),
+    // baton_pass_cost <value> -> <mod>
+    #[hocg_fan_sim(token = "baton_pass_cost")]
+    BatonPassCost(Number),
+    // collab_while_resting -> <mod>
+    #[hocg_fan_sim(token = "collab_while_resting")]
+    CollabWhileResting,
     // deal_less_dmg <value> -> <mod>
     #[hocg_fan_sim(token = "deal_less_dmg")]
     DealLessDamage(Number),
     // deal_more_dmg <value> -> <mod>
     #[hocg_fan_sim(token = "deal_more_dmg")]
     DealMoreDamage(Number),
+    // instead_of <replaced_event> <[action]> -> <mod>
+    #[hocg_fan_sim(token = "instead_of")]
+    InsteadOf(ReplacedEvent, Vec<Action>),
+    // less_art_cost <number> <color> -> <mod>
+    #[hocg_fan_sim(token = "less_art_cost")]
+    LessArtCost(Number, Color),
+    // more_hp <value> -> <mod>
+    #[hocg_fan_sim(token = "more_hp")]
+    MoreHp(Number),
+    // prevent <replaced_event> -> <mod>
+    #[hocg_fan_sim(token = "prevent")]
+    Prevent(ReplacedEvent),
     // recv_less_dmg <value> -> <mod>
     #[hocg_fan_sim(token = "recv_less_dmg")]
     ReceiveLessDamage(Number),
@@ -437,11 +545,27 @@ pub enum Modifier {
     // no_life_loss -> <mod>
     #[hocg_fan_sim(token = "no_life_loss")]
     NoLifeLoss,
+    // untargetable_by_arts -> <mod>
+    #[hocg_fan_sim(token = "untargetable_by_arts")]
+    UntargetableByArts,
     // when <condition> <mod>  -> <mod>
     #[hocg_fan_sim(token = "when")]
     When(Condition, Box<Modifier>),
 }
 
+#[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum ReplacedEvent {
+    // knocked_out -> <replaced_event>
+    #[hocg_fan_sim(token = "knocked_out")]
+    KnockedOut,
+    // receive_dmg -> <replaced_event>
+    #[hocg_fan_sim(token = "receive_dmg")]
+    ReceiveDamage,
+    // send_to <zone> -> <replaced_event>
+    #[hocg_fan_sim(token = "send_to")]
+    SendToZone(Zone),
+}
+
 #[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub enum Player {
     // you -> <player>
@@ -457,22 +581,40 @@ pub enum Number {
     // count <[card_ref]> -> <value>
     #[hocg_fan_sim(token = "count")]
     Count(CardReferences),
+    // <value> / <value> -> <value> (rounded down)
+    #[hocg_fan_sim(infix = "/", precedence = 2)]
+    Divide(Box<Number>, Box<Number>),
+    // div_round_up <value> <value> -> <value>
+    #[hocg_fan_sim(token = "div_round_up")]
+    DivideRoundUp(Box<Number>, Box<Number>),
     // 123 -> <value>
     #[hocg_fan_sim(transparent)]
     Literal(NumberLiteral),
+    // max <value> <value> -> <value>
+    #[hocg_fan_sim(token = "max")]
+    Max(Box<Number>, Box<Number>),
+    // min <value> <value> -> <value>
+    #[hocg_fan_sim(token = "min")]
+    Min(Box<Number>, Box<Number>),
     // <value> - <value> -> <value>
-    #[hocg_fan_sim(infix = "-")]
+    #[hocg_fan_sim(infix = "-", precedence = 1)]
     Minus(Box<Number>, Box<Number>),
     // <value> * <value> -> <value>
-    #[hocg_fan_sim(infix = "*")]
+    #[hocg_fan_sim(infix = "*", precedence = 2)]
     Multiply(Box<Number>, Box<Number>),
     // <value> + <value> -> <value>
-    #[hocg_fan_sim(infix = "+")]
+    #[hocg_fan_sim(infix = "+", precedence = 1)]
     Plus(Box<Number>, Box<Number>),
+    // sum <[card_ref]> <value> -> <value>
+    #[hocg_fan_sim(token = "sum")]
+    Sum(CardReferences, Box<Number>),
     // <$var> -> <value>
     #[hocg_fan_sim(transparent)]
     Var(Var),
 
+    // [active_card] counter <name> -> <value>
+    #[hocg_fan_sim(token = "counter")]
+    Counter(StringLiteral),
     // [active_card] dmg_amount -> <value>
     #[hocg_fan_sim(token = "dmg_amount")]
     DamageAmount,
@@ -483,6 +625,9 @@ pub enum Number {
 
 #[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub enum Zone {
+    // activate_support -> <zone>
+    #[hocg_fan_sim(token = "activate_support")]
+    ActivateSupport,
     // archive -> <zone>
     #[hocg_fan_sim(token = "archive")]
     Archive,
@@ -495,27 +640,39 @@ pub enum Zone {
     // cheer_deck -> <zone>
     #[hocg_fan_sim(token = "cheer_deck")]
     CheerDeck,
+    // collab -> <zone>
+    #[hocg_fan_sim(token = "collab")]
+    Collab,
     // hand -> <zone>
     #[hocg_fan_sim(token = "hand")]
     Hand,
     // holo_power -> <zone>
     #[hocg_fan_sim(token = "holo_power")]
     HoloPower,
+    // life -> <zone>
+    #[hocg_fan_sim(token = "life")]
+    Life,
     // main_deck -> <zone>
     #[hocg_fan_sim(token = "main_deck")]
     MainDeck,
     // main_stage -> <zone>
     #[hocg_fan_sim(token = "main_stage")]
     MainStage,
-    // opponent_back_stage -> <zone>
+    // opponent_back_stage -> <zone> (alias of opponent back_stage)
     #[hocg_fan_sim(token = "opponent_back_stage")]
     OpponentBackStage,
-    // opponent_center_stage -> <zone>
+    // opponent_center_stage -> <zone> (alias of opponent center_stage)
     #[hocg_fan_sim(token = "opponent_center_stage")]
     OpponentCenterStage,
+    // oshi -> <zone>
+    #[hocg_fan_sim(token = "oshi")]
+    Oshi,
     // stage -> <zone>
     #[hocg_fan_sim(token = "stage")]
     Stage,
+    // <player> <zone> -> <zone>
+    #[hocg_fan_sim(transparent)]
+    Of(Player, Box<Zone>),
 }
 
 #[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
@@ -543,9 +700,115 @@ pub enum Color {
     Colorless,
 }
 
+#[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum Attribute {
+    // buzz -> <attribute>
+    #[hocg_fan_sim(token = "buzz")]
+    Buzz,
+    // unlimited -> <attribute>
+    #[hocg_fan_sim(token = "unlimited")]
+    Unlimited,
+}
+
+#[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum HashTag {
+    // jp -> <tag>
+    #[hocg_fan_sim(token = "jp")]
+    JP,
+    // id -> <tag>
+    #[hocg_fan_sim(token = "id")]
+    ID,
+    // en -> <tag>
+    #[hocg_fan_sim(token = "en")]
+    EN,
+    // gen_0 -> <tag>
+    #[hocg_fan_sim(token = "gen_0")]
+    Gen0,
+    // gen_3 -> <tag>
+    #[hocg_fan_sim(token = "gen_3")]
+    Gen3,
+    // gen_4 -> <tag>
+    #[hocg_fan_sim(token = "gen_4")]
+    Gen4,
+    // gen_5 -> <tag>
+    #[hocg_fan_sim(token = "gen_5")]
+    Gen5,
+    // secret_society_holox -> <tag>
+    #[hocg_fan_sim(token = "secret_society_holox")]
+    SecretSocietyholoX,
+    // id_gen_1 -> <tag>
+    #[hocg_fan_sim(token = "id_gen_1")]
+    IDGen1,
+    // id_gen_3 -> <tag>
+    #[hocg_fan_sim(token = "id_gen_3")]
+    IDGen3,
+    // myth -> <tag>
+    #[hocg_fan_sim(token = "myth")]
+    Myth,
+    // promise -> <tag>
+    #[hocg_fan_sim(token = "promise")]
+    Promise,
+    // alcohol -> <tag>
+    #[hocg_fan_sim(token = "alcohol")]
+    Alcohol,
+    // animal_ears -> <tag>
+    #[hocg_fan_sim(token = "animal_ears")]
+    AnimalEars,
+    // art -> <tag>
+    #[hocg_fan_sim(token = "art")]
+    Art,
+    // bird -> <tag>
+    #[hocg_fan_sim(token = "bird")]
+    Bird,
+    // drawing -> <tag>
+    #[hocg_fan_sim(token = "drawing")]
+    Drawing,
+    // song -> <tag>
+    #[hocg_fan_sim(token = "song")]
+    Song,
+}
+
+#[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum Level {
+    // spot -> <level>
+    #[hocg_fan_sim(token = "spot")]
+    Spot,
+    // debut -> <level>
+    #[hocg_fan_sim(token = "debut")]
+    Debut,
+    // first -> <level>
+    #[hocg_fan_sim(token = "first")]
+    First,
+    // second -> <level>
+    #[hocg_fan_sim(token = "second")]
+    Second,
+}
+
+#[derive(HocgFanSimCardEffect, Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum SupportKind {
+    // staff -> <support_kind>
+    #[hocg_fan_sim(token = "staff")]
+    Staff,
+    // item -> <support_kind>
+    #[hocg_fan_sim(token = "item")]
+    Item,
+    // event -> <support_kind>
+    #[hocg_fan_sim(token = "event")]
+    Event,
+    // tool -> <support_kind>
+    #[hocg_fan_sim(token = "tool")]
+    Tool,
+    // mascot -> <support_kind>
+    #[hocg_fan_sim(token = "mascot")]
+    Mascot,
+    // fan -> <support_kind>
+    #[hocg_fan_sim(token = "fan")]
+    Fan,
+}
+
 //////////////////////////////////////
 
-#[derive(Encode, Decode, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, GetSize)]
+#[derive(Encode, Decode, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, GetSize)]
 #[serde(rename_all = "snake_case")]
 pub enum Trigger {
     Never,
@@ -560,10 +823,39 @@ pub enum Trigger {
     OnAfterPerformArt,
     OnBeforeRollDice,
     OnAfterRollDice,
+    OnBeforeBloom,
+    OnAfterBloom,
+    OnBeforeCollab,
+    OnAfterCollab,
+    OnBeforeBatonPass,
+    OnAfterBatonPass,
+    OnBeforeHoloMemberKnockedOut,
+    OnAfterHoloMemberKnockedOut,
+    OnBeforeAttachToCard,
+    OnAfterAttachToCard,
+    OnBeforeDealDamage,
+    OnAfterDealDamage,
+    OnBeforeLoseLives,
+    OnAfterLoseLives,
+    OnBeforeDraw,
+    OnAfterDraw,
+    OnBeforeSendToZone,
+    OnAfterSendToZone,
+    /// only activate when the event concerns the scope
+    Scoped(Box<Trigger>, TriggerScope),
 }
 
 impl Trigger {
-    pub fn should_activate(&self, triggered_event: &TriggeredEvent) -> bool {
+    pub fn scoped(self, scope: TriggerScope) -> Self {
+        Trigger::Scoped(Box::new(self), scope)
+    }
+
+    pub fn should_activate(
+        &self,
+        card: CardRef,
+        triggered_event: &TriggeredEvent,
+        game: &Game,
+    ) -> bool {
         match self {
             Trigger::Never => false,
             Trigger::ActivateInMainStep => false,
@@ -608,6 +900,114 @@ impl Trigger {
             Trigger::OnAfterRollDice => {
                 matches!(triggered_event, TriggeredEvent::After(Event::RollDice(_)))
             }
+            Trigger::OnBeforeBloom => {
+                matches!(triggered_event, TriggeredEvent::Before(Event::Bloom(_)))
+            }
+            Trigger::OnAfterBloom => {
+                matches!(triggered_event, TriggeredEvent::After(Event::Bloom(_)))
+            }
+            Trigger::OnBeforeCollab => {
+                matches!(triggered_event, TriggeredEvent::Before(Event::Collab(_)))
+            }
+            Trigger::OnAfterCollab => {
+                matches!(triggered_event, TriggeredEvent::After(Event::Collab(_)))
+            }
+            Trigger::OnBeforeBatonPass => {
+                matches!(triggered_event, TriggeredEvent::Before(Event::BatonPass(_)))
+            }
+            Trigger::OnAfterBatonPass => {
+                matches!(triggered_event, TriggeredEvent::After(Event::BatonPass(_)))
+            }
+            Trigger::OnBeforeHoloMemberKnockedOut => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::Before(Event::HoloMemberKnockedOut(_))
+                )
+            }
+            Trigger::OnAfterHoloMemberKnockedOut => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::After(Event::HoloMemberKnockedOut(_))
+                )
+            }
+            Trigger::OnBeforeAttachToCard => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::Before(Event::AttachToCard(_))
+                )
+            }
+            Trigger::OnAfterAttachToCard => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::After(Event::AttachToCard(_))
+                )
+            }
+            Trigger::OnBeforeDealDamage => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::Before(Event::DealDamage(_))
+                )
+            }
+            Trigger::OnAfterDealDamage => {
+                matches!(triggered_event, TriggeredEvent::After(Event::DealDamage(_)))
+            }
+            Trigger::OnBeforeLoseLives => {
+                matches!(triggered_event, TriggeredEvent::Before(Event::LoseLives(_)))
+            }
+            Trigger::OnAfterLoseLives => {
+                matches!(triggered_event, TriggeredEvent::After(Event::LoseLives(_)))
+            }
+            Trigger::OnBeforeDraw => {
+                matches!(triggered_event, TriggeredEvent::Before(Event::Draw(_)))
+            }
+            Trigger::OnAfterDraw => {
+                matches!(triggered_event, TriggeredEvent::After(Event::Draw(_)))
+            }
+            Trigger::OnBeforeSendToZone => {
+                matches!(
+                    triggered_event,
+                    TriggeredEvent::Before(Event::SendToZone(_))
+                )
+            }
+            Trigger::OnAfterSendToZone => {
+                matches!(triggered_event, TriggeredEvent::After(Event::SendToZone(_)))
+            }
+            Trigger::Scoped(trigger, scope) => {
+                trigger.should_activate(card, triggered_event, game)
+                    && scope.is_in_scope(card, triggered_event.event(), game)
+            }
+        }
+    }
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Debug, Clone, PartialEq, Eq, GetSize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerScope {
+    /// the event concerns this card
+    ThisCard,
+    /// the event concerns one of your cards, or you
+    Yours,
+    /// the event concerns one of your opponent's cards, or your opponent
+    Opponent,
+    /// the condition is true for one of the cards concerned by the event
+    Condition(Condition),
+}
+
+impl TriggerScope {
+    pub fn is_in_scope(&self, card: CardRef, event: &Event, game: &Game) -> bool {
+        let subjects = event.subject_cards(game);
+        let player = game.player_for_card(card);
+        match self {
+            TriggerScope::ThisCard => subjects.contains(&card),
+            TriggerScope::Yours => event.subject_player(game) == Some(player),
+            TriggerScope::Opponent => event.subject_player(game) == Some(player.opponent()),
+            TriggerScope::Condition(condition) => subjects.into_iter().any(|subject| {
+                condition
+                    .ctx()
+                    .with_card(card, game)
+                    .for_card(subject)
+                    .evaluate(game)
+            }),
         }
     }
 }
@@ -22,6 +22,7 @@ pub enum Error {
     ExpectedToken,
     MissingBracket,
     UnbalancedBrackets,
+    UnclosedString,
     NoTokens,
     RemainingTokens,
     UnexpectedToken(String, String),
@@ -35,6 +36,7 @@ impl Display for Error {
             Error::ExpectedToken => formatter.write_str("Expected token"),
             Error::MissingBracket => formatter.write_str("Missing bracket"),
             Error::UnbalancedBrackets => formatter.write_str("Unbalanced brackets"),
+            Error::UnclosedString => formatter.write_str("Unclosed string"),
             Error::NoTokens => formatter.write_str("No tokens"),
             Error::RemainingTokens => formatter.write_str("Remaining tokens"),
             Error::UnexpectedToken(expected, unexpected) => {
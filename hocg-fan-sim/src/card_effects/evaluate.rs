@@ -4,13 +4,18 @@ use std::ops::Deref;
 use iter_tools::Itertools;
 
 use super::effects::*;
+use super::parse::SerializeEffect;
+use super::text::EffectText;
+use super::trace::{EvaluationTracer, TraceKind, TraceNode};
 use crate::cards::Color;
+use crate::cards::SupportKind;
 use crate::cards::*;
 use crate::gameplay::Player;
 use crate::gameplay::Zone;
 use crate::modifiers::DamageMarkers;
 use crate::modifiers::LifeTime;
 use crate::modifiers::ModifierKind;
+use crate::modifiers::ReplacedEvent;
 use crate::{
     gameplay::{self, *},
     modifiers::{self},
@@ -36,6 +41,8 @@ pub struct EvaluateContext {
     pub active_player: Option<gameplay::Player>,
     pub variables: HashMap<String, LetValue>,
     pub is_triggered: bool,
+    /// opt-in, records the evaluated actions and conditions
+    pub tracer: Option<EvaluationTracer>,
 }
 
 impl EvaluateContext {
@@ -69,6 +76,22 @@ impl<T> EvaluateBuilder<'_, T> {
         let player = game.player_for_card(card);
         self.context.active_player = Some(player);
 
+        // trace the effect if the game is traced
+        if self.context.tracer.is_none() {
+            self.context.tracer = game.tracer.clone();
+        }
+
+        self
+    }
+
+    pub fn with_tracer(mut self, tracer: EvaluationTracer) -> Self {
+        self.context.tracer = Some(tracer);
+        self
+    }
+
+    pub fn for_card(mut self, card: CardRef) -> Self {
+        // temporary target for filters
+        self.context = self.context.for_card(card);
         self
     }
 
@@ -113,11 +136,29 @@ where
             game.game.event_span.open_card_span(card);
         }
 
+        // the events emitted by the effect are traced with it
+        let tracer = context.tracer.clone();
+        let install_tracer = tracer.is_some() && game.game.tracer.is_none();
+        if install_tracer {
+            game.game.tracer = tracer.clone();
+        }
+        if let Some(tracer) = &tracer {
+            let node = TraceNode::new(TraceKind::Effect, "effect", card, &game.game.event_span);
+            tracer.open(node);
+        }
+
         let value = self
             .effect
             .evaluate_with_context_mut(&mut context, game)
             .await;
 
+        if let Some(tracer) = &tracer {
+            tracer.close(value.as_ref().err().map(|o| format!("game over: {o:?}")));
+        }
+        if install_tracer {
+            game.game.tracer = None;
+        }
+
         if let Some(card) = card {
             game.game.event_span.close_card_span(card);
         }
@@ -236,6 +277,24 @@ where
 
 ///////////////////////////////////////
 
+/// remove the outer parentheses, like in the card definitions
+fn trace_label(effect: String) -> String {
+    match effect.strip_prefix('(').and_then(|e| e.strip_suffix(')')) {
+        Some(effect) => effect.into(),
+        None => effect,
+    }
+}
+
+impl LetValue {
+    fn trace(&self) -> String {
+        match self {
+            LetValue::CardReferences(cards) => format!("{cards:?}"),
+            LetValue::Condition(condition) => condition.clone().serialize_effect(),
+            LetValue::Number(number) => number.to_string(),
+        }
+    }
+}
+
 impl EvaluateEffectMut for Action {
     type Value = ();
 
@@ -244,7 +303,52 @@ impl EvaluateEffectMut for Action {
         ctx: &mut EvaluateContext,
         game: &mut GameDirector,
     ) -> EvaluateResult<Self::Value> {
+        let Some(tracer) = ctx.tracer.clone() else {
+            return self.evaluate_action(ctx, game).await;
+        };
+
+        let label = trace_label(self.clone().serialize_effect());
+        tracer.open(TraceNode::new(
+            TraceKind::Action,
+            label,
+            ctx.active_card,
+            &game.game.event_span,
+        ));
+        let before = ctx.variables.clone();
+
+        let value = self.evaluate_action(ctx, game).await;
+
+        // the bound variables, and the selected cards
+        let mut details = ctx
+            .variables
+            .iter()
+            .filter(|(name, value)| before.get(*name) != Some(value))
+            .map(|(name, value)| format!("{name} = {}", value.trace()))
+            .sorted()
+            .collect_vec();
+        if let Err(outcome) = &value {
+            details.push(format!("game over: {outcome:?}"));
+        }
+        tracer.close(details);
+
+        value
+    }
+}
+
+impl Action {
+    async fn evaluate_action(
+        &self,
+        ctx: &mut EvaluateContext,
+        game: &mut GameDirector,
+    ) -> EvaluateResult<()> {
         match self {
+            Action::AddCounter(cards, name, amount, life_time) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                let amount = amount.evaluate_with_context(ctx, &game.game);
+                let life_time = life_time.evaluate_with_context(ctx, &game.game);
+                game.add_counter(cards, name.0.clone(), amount, life_time)
+                    .await?;
+            }
             Action::AddGlobalModifier(player, modifier, life_time) => {
                 game.add_zone_modifier(
                     player.evaluate_with_context(ctx, &game.game),
@@ -274,6 +378,14 @@ impl EvaluateEffectMut for Action {
                 )
                 .await?;
             }
+            Action::ArchiveAttachedCheers(cards) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                let cheers = cards
+                    .into_iter()
+                    .flat_map(|c| game.game.attached_cheers(c).collect_vec())
+                    .collect_vec();
+                game.send_to_archive(cheers).await?;
+            }
             Action::AttachCards(attachments, target) => {
                 let attachments = attachments.evaluate_with_context(ctx, &game.game);
                 let target = target.evaluate_with_context(ctx, &game.game);
@@ -282,6 +394,17 @@ impl EvaluateEffectMut for Action {
                 }
                 game.attach_cards_to_card(attachments, target).await?;
             }
+            Action::Bloom(bloom, target) => {
+                let bloom = bloom.evaluate_with_context(ctx, &game.game);
+                let target = target.evaluate_with_context(ctx, &game.game);
+                game.bloom_holo_member(bloom, target).await?;
+            }
+            Action::ChooseOne(choices) => {
+                let player = ctx.active_player.expect("there should be an active player");
+                let options = choices.iter().map(|c| c.to_text()).collect_vec();
+                let choice = game.prompt_for_option(player, options).await?;
+                Box::pin(choices[choice].evaluate_with_context_mut(ctx, game)).await?;
+            }
             Action::DealDamage(targets, amount) => {
                 let card = ctx.active_card.expect("there should be an active card");
                 let targets = targets.evaluate_with_context(ctx, &game.game);
@@ -296,6 +419,15 @@ impl EvaluateEffectMut for Action {
                 game.deal_damage(card, targets, DamageMarkers::from_hp(amount as u16), true)
                     .await?;
             }
+            Action::Discard(cards) => {
+                let player = ctx.active_player.expect("there should be an active player");
+                let cards = cards
+                    .evaluate_with_context(ctx, &game.game)
+                    .into_iter()
+                    .filter(|c| game.board(player).get_zone(Zone::Hand).is_in_zone(*c))
+                    .collect_vec();
+                game.send_to_archive(cards).await?;
+            }
             Action::Draw(amount) => {
                 game.draw_from_main_deck(
                     ctx.active_player
@@ -304,6 +436,23 @@ impl EvaluateEffectMut for Action {
                 )
                 .await?;
             }
+            Action::ForEach(var, cards, actions) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                for card in cards {
+                    ctx.variables
+                        .insert(var.0.clone(), LetValue::CardReferences([card].into()));
+                    Box::pin(actions.evaluate_with_context_mut(ctx, game)).await?;
+                }
+            }
+            Action::Heal(cards, amount) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                let amount = amount.evaluate_with_context(ctx, &game.game);
+                game.remove_damage_markers_from_many_cards(
+                    cards,
+                    DamageMarkers::from_hp(amount as u16),
+                )
+                .await?;
+            }
             Action::If(condition, actions) => {
                 if condition.evaluate_with_context(ctx, &game.game) {
                     Box::pin(actions.evaluate_with_context_mut(ctx, game)).await?;
@@ -335,6 +484,21 @@ impl EvaluateEffectMut for Action {
                 ctx.variables.insert(let_value.0 .0.clone(), value);
             }
             Action::Noop => {}
+            Action::RemoveCounter(cards, name, amount) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                let amount = amount.evaluate_with_context(ctx, &game.game);
+                game.remove_counter(cards, name.0.clone(), amount).await?;
+            }
+            Action::Repeat(amount, actions) => {
+                let amount = amount.evaluate_with_context(ctx, &game.game);
+                for _ in 0..amount {
+                    Box::pin(actions.evaluate_with_context_mut(ctx, game)).await?;
+                }
+            }
+            Action::ReturnToHand(cards) => {
+                let cards = cards.evaluate_with_context(ctx, &game.game);
+                game.return_to_hand(cards).await?;
+            }
             Action::Reveal(cards) => {
                 let cards = cards.evaluate_with_context(ctx, &game.game);
                 let map: HashMap<(Player, Zone), Vec<CardRef>> =
@@ -343,6 +507,17 @@ impl EvaluateEffectMut for Action {
                     game.reveal_cards(player, zone, &cards).await?;
                 }
             }
+            Action::Search(amount, condition) => {
+                let player = ctx.active_player.expect("there should be an active player");
+                let amount = amount.evaluate_with_context(ctx, &game.game);
+                let cards = game.board(player).get_zone(Zone::MainDeck).all_cards();
+                let choice = game
+                    .prompt_for_select(player, cards, condition.clone(), ctx, 0, amount)
+                    .await?;
+                game.reveal_cards(player, Zone::MainDeck, &choice).await?;
+                game.send_to_zone(choice, Zone::Hand).await?;
+                game.shuffle_main_deck(player).await?;
+            }
             Action::SendTo(to_zone, cards) => {
                 let (_, to_zone) = to_zone.evaluate_with_context(ctx, &game.game);
                 let cards = cards.evaluate_with_context(ctx, &game.game);
@@ -364,6 +539,10 @@ impl EvaluateEffectMut for Action {
                 let (player, zone) = zone.evaluate_with_context(ctx, &game.game);
                 game.shuffle_decks(vec![(player, zone)]).await?;
             }
+            Action::SwapCenter(card) => {
+                let card = card.evaluate_with_context(ctx, &game.game);
+                game.swap_center_stage(card).await?;
+            }
         }
         Ok(())
     }
@@ -467,6 +646,26 @@ impl EvaluateEffect for Condition {
     type Value = bool;
 
     fn evaluate_with_context(&self, ctx: &EvaluateContext, game: &Game) -> Self::Value {
+        let Some(tracer) = &ctx.tracer else {
+            return self.evaluate_condition(ctx, game);
+        };
+
+        let label = trace_label(self.clone().serialize_effect());
+        tracer.open(TraceNode::new(
+            TraceKind::Condition,
+            label,
+            ctx.active_card,
+            &game.event_span,
+        ));
+        let value = self.evaluate_condition(ctx, game);
+        tracer.close([format!("{value}")]);
+
+        value
+    }
+}
+
+impl Condition {
+    fn evaluate_condition(&self, ctx: &EvaluateContext, game: &Game) -> bool {
         match self {
             Condition::All(cards, condition) => {
                 let cards = cards.evaluate_with_context(ctx, game);
@@ -496,15 +695,30 @@ impl EvaluateEffect for Condition {
                 !cards.is_empty()
             }
             Condition::False => false,
+            Condition::GreaterThan(value_1, value_2) => {
+                let value_1 = value_1.evaluate_with_context(ctx, game);
+                let value_2 = value_2.evaluate_with_context(ctx, game);
+                value_1 > value_2
+            }
             Condition::GreaterThanEquals(value_1, value_2) => {
                 let value_1 = value_1.evaluate_with_context(ctx, game);
                 let value_2 = value_2.evaluate_with_context(ctx, game);
                 value_1 >= value_2
             }
+            Condition::HasAttribute(attribute) => {
+                let attribute = attribute.evaluate_with_context(ctx, game);
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).is_attribute(attribute)
+            }
             Condition::HasCheers => {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.attached_cheers(card).next().is_some()
             }
+            Condition::HasTag(tag) => {
+                let tag = tag.evaluate_with_context(ctx, game);
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).has_hash_tag(tag)
+            }
             Condition::IsAttributeBuzz => {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.lookup_card(card)
@@ -526,7 +740,14 @@ impl EvaluateEffect for Condition {
             Condition::IsInZone(zone) => {
                 let (player, zone) = zone.evaluate_with_context(ctx, game);
                 let card = ctx.active_card.expect("there should be an active card");
-                game.board(player).find_card_zone(card) == Some(zone)
+                game.board(player)
+                    .find_card_zone(card)
+                    .is_some_and(|z| zone.includes(z))
+            }
+            Condition::IsLevel(level) => {
+                let level = level.evaluate_with_context(ctx, game);
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).is_level(level)
             }
             Condition::IsLevelFirst => {
                 let card = ctx.active_card.expect("there should be an active card");
@@ -540,6 +761,10 @@ impl EvaluateEffect for Condition {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.lookup_card(card).is_member()
             }
+            Condition::IsNamed(name) => {
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).is_named(&name.0)
+            }
             Condition::IsNamedAzki => {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.lookup_card(card).is_named("AZKi")
@@ -570,10 +795,24 @@ impl EvaluateEffect for Condition {
                 let value = value.evaluate_with_context(ctx, game);
                 value % 2 == 1
             }
+            Condition::IsOshi => {
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).is_oshi()
+            }
+            Condition::IsSupportKind(kind) => {
+                let kind = kind.evaluate_with_context(ctx, game);
+                let card = ctx.active_card.expect("there should be an active card");
+                game.lookup_card(card).is_support_kind(kind)
+            }
             Condition::IsSupportLimited => {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.lookup_card(card).is_support_limited()
             }
+            Condition::LessThan(value_1, value_2) => {
+                let value_1 = value_1.evaluate_with_context(ctx, game);
+                let value_2 = value_2.evaluate_with_context(ctx, game);
+                value_1 < value_2
+            }
             Condition::LessThanEquals(value_1, value_2) => {
                 let value_1 = value_1.evaluate_with_context(ctx, game);
                 let value_2 = value_2.evaluate_with_context(ctx, game);
@@ -583,6 +822,11 @@ impl EvaluateEffect for Condition {
                 let condition = condition.evaluate_with_context(ctx, game);
                 !condition
             }
+            Condition::NotEquals(value_1, value_2) => {
+                let value_1 = value_1.evaluate_with_context(ctx, game);
+                let value_2 = value_2.evaluate_with_context(ctx, game);
+                value_1 != value_2
+            }
             Condition::Or(condition_1, condition_2) => {
                 let condition_1 = condition_1.evaluate_with_context(ctx, game);
                 let condition_2 = condition_2.evaluate_with_context(ctx, game);
@@ -618,7 +862,7 @@ impl EvaluateEffectMut for super::LetValue {
             super::LetValue::OptionalActivate => {
                 let player = ctx.active_player.expect("there should be an active player");
                 Ok(LetValue::Condition(
-                    game.prompt_for_optional_activate(player).await.into(),
+                    game.prompt_for_optional_activate(player).await?.into(),
                 ))
             }
             super::LetValue::RollDice => {
@@ -638,7 +882,7 @@ impl EvaluateEffectMut for super::LetValue {
                         0,
                         usize::MAX,
                     )
-                    .await;
+                    .await?;
                 let leftovers = cards
                     .into_iter()
                     .filter(|c| !choice.contains(c))
@@ -652,7 +896,7 @@ impl EvaluateEffectMut for super::LetValue {
                 let cards = cards.evaluate_with_context(ctx, &game.game);
                 let choice = game
                     .prompt_for_select(player, cards.clone(), condition.as_ref().clone(), ctx, 1, 1)
-                    .await;
+                    .await?;
                 let leftovers = cards
                     .into_iter()
                     .filter(|c| !choice.contains(c))
@@ -666,7 +910,7 @@ impl EvaluateEffectMut for super::LetValue {
                 let min = min.evaluate_with_context(ctx, &game.game);
                 let max = max.evaluate_with_context(ctx, &game.game);
                 Ok(LetValue::Number(
-                    game.prompt_for_number(player, min, max).await,
+                    game.prompt_for_number(player, min, max).await?,
                 ))
             }
             super::LetValue::SelectUpTo(amount, cards, condition) => {
@@ -682,7 +926,7 @@ impl EvaluateEffectMut for super::LetValue {
                         0,
                         amount,
                     )
-                    .await;
+                    .await?;
                 let leftovers = cards
                     .into_iter()
                     .filter(|c| !choice.contains(c))
@@ -733,6 +977,11 @@ impl EvaluateEffect for Modifier {
                 let color = color.evaluate_with_context(ctx, game);
                 ModifierKind::AsCheer(color, amount)
             }
+            Modifier::BatonPassCost(amount) => {
+                let amount = amount.evaluate_with_context(ctx, game);
+                ModifierKind::BatonPassCost(amount)
+            }
+            Modifier::CollabWhileResting => ModifierKind::CollabWhileResting,
             Modifier::DealLessDamage(amount) => {
                 let amount = amount.evaluate_with_context(ctx, game);
                 ModifierKind::DealLessDamage(amount)
@@ -741,6 +990,23 @@ impl EvaluateEffect for Modifier {
                 let amount = amount.evaluate_with_context(ctx, game);
                 ModifierKind::DealMoreDamage(amount)
             }
+            Modifier::InsteadOf(event, actions) => {
+                let event = event.evaluate_with_context(ctx, game);
+                ModifierKind::Instead(event, actions.clone())
+            }
+            Modifier::LessArtCost(amount, color) => {
+                let amount = amount.evaluate_with_context(ctx, game);
+                let color = color.evaluate_with_context(ctx, game);
+                ModifierKind::LessArtCost(color, amount)
+            }
+            Modifier::MoreHp(amount) => {
+                let amount = amount.evaluate_with_context(ctx, game);
+                ModifierKind::MoreHp(amount)
+            }
+            Modifier::Prevent(event) => {
+                let event = event.evaluate_with_context(ctx, game);
+                ModifierKind::Prevent(event)
+            }
             Modifier::ReceiveLessDamage(amount) => {
                 let amount = amount.evaluate_with_context(ctx, game);
                 ModifierKind::ReceiveLessDamage(amount)
@@ -754,6 +1020,7 @@ impl EvaluateEffect for Modifier {
                 ModifierKind::NextDiceRoll(number)
             }
             Modifier::NoLifeLoss => ModifierKind::NoLifeLoss,
+            Modifier::UntargetableByArts => ModifierKind::UntargetableByArts,
             Modifier::When(condition, modifier) => {
                 let modifier = modifier.evaluate_with_context(ctx, game);
                 ModifierKind::Conditional(Box::new(condition.clone()), Box::new(modifier))
@@ -762,6 +1029,22 @@ impl EvaluateEffect for Modifier {
     }
 }
 
+impl EvaluateEffect for super::ReplacedEvent {
+    type Value = ReplacedEvent;
+
+    fn evaluate_with_context(&self, ctx: &EvaluateContext, game: &Game) -> Self::Value {
+        match self {
+            super::ReplacedEvent::KnockedOut => ReplacedEvent::KnockedOut,
+            super::ReplacedEvent::ReceiveDamage => ReplacedEvent::ReceiveDamage,
+            super::ReplacedEvent::SendToZone(zone) => {
+                // the replacement is on the card, only the zone matters
+                let (_, zone) = zone.evaluate_with_context(ctx, game);
+                ReplacedEvent::SendToZone(zone)
+            }
+        }
+    }
+}
+
 impl EvaluateEffect for super::Player {
     type Value = Player;
 
@@ -788,7 +1071,33 @@ impl EvaluateEffect for Number {
                 let cards = cards.evaluate_with_context(ctx, game);
                 cards.len()
             }
+            Number::Divide(a, b) => {
+                let a = a.evaluate_with_context(ctx, game);
+                let b = b.evaluate_with_context(ctx, game);
+                // dividing by zero gives nothing
+                a.checked_div(b).unwrap_or(0)
+            }
+            Number::DivideRoundUp(a, b) => {
+                let a = a.evaluate_with_context(ctx, game);
+                let b = b.evaluate_with_context(ctx, game);
+                // dividing by zero gives nothing
+                if b == 0 {
+                    0
+                } else {
+                    a.div_ceil(b)
+                }
+            }
             Number::Literal(number) => number.0,
+            Number::Max(a, b) => {
+                let a = a.evaluate_with_context(ctx, game);
+                let b = b.evaluate_with_context(ctx, game);
+                a.max(b)
+            }
+            Number::Min(a, b) => {
+                let a = a.evaluate_with_context(ctx, game);
+                let b = b.evaluate_with_context(ctx, game);
+                a.min(b)
+            }
             Number::Minus(a, b) => {
                 let a = a.evaluate_with_context(ctx, game);
                 let b = b.evaluate_with_context(ctx, game);
@@ -804,6 +1113,13 @@ impl EvaluateEffect for Number {
                 let b = b.evaluate_with_context(ctx, game);
                 a + b
             }
+            Number::Sum(cards, value) => {
+                let cards = cards.evaluate_with_context(ctx, game);
+                cards
+                    .into_iter()
+                    .map(|c| value.evaluate_with_context(&ctx.for_card(c), game))
+                    .sum()
+            }
             Number::Var(var) => {
                 match ctx.variables.get(&var.0).unwrap_or_else(|| {
                     panic!("the variable should exist: {:?} - ctx: {:?}", var, ctx)
@@ -812,6 +1128,10 @@ impl EvaluateEffect for Number {
                     _ => panic!("wrong value: {:?} - ctx: {:?}", var, ctx),
                 }
             }
+            Number::Counter(name) => {
+                let card = ctx.active_card.expect("there should be an active card");
+                game.get_counter(card, &name.0)
+            }
             Number::DamageAmount => {
                 let card = ctx.active_card.expect("there should be an active card");
                 game.get_damage(card).to_hp() as usize
@@ -827,7 +1147,7 @@ impl EvaluateEffect for Number {
 impl EvaluateEffect for super::Zone {
     type Value = (Player, Zone);
 
-    fn evaluate_with_context(&self, ctx: &EvaluateContext, _game: &Game) -> Self::Value {
+    fn evaluate_with_context(&self, ctx: &EvaluateContext, game: &Game) -> Self::Value {
         let player = ctx.active_player.expect("there should be an active player");
         let opponent = match player {
             Player::One => Player::Two,
@@ -835,17 +1155,27 @@ impl EvaluateEffect for super::Zone {
             Player::Both => unreachable!("cannot be bot"),
         };
         match self {
+            super::Zone::ActivateSupport => (player, Zone::ActivateSupport),
             super::Zone::Archive => (player, Zone::Archive),
             super::Zone::BackStage => (player, Zone::BackStage),
             super::Zone::CenterStage => (player, Zone::CenterStage),
             super::Zone::CheerDeck => (player, Zone::CheerDeck),
+            super::Zone::Collab => (player, Zone::Collab),
             super::Zone::Hand => (player, Zone::Hand),
             super::Zone::HoloPower => (player, Zone::HoloPower),
+            super::Zone::Life => (player, Zone::Life),
             super::Zone::MainDeck => (player, Zone::MainDeck),
             super::Zone::MainStage => (player, Zone::MainStage),
             super::Zone::OpponentBackStage => (opponent, Zone::BackStage),
             super::Zone::OpponentCenterStage => (opponent, Zone::CenterStage),
+            super::Zone::Oshi => (player, Zone::Oshi),
             super::Zone::Stage => (player, Zone::Stage),
+            super::Zone::Of(player, zone) => {
+                // the zone is relative to the given player
+                let mut ctx = ctx.clone();
+                ctx.active_player = Some(player.evaluate_with_context(&ctx, game));
+                zone.evaluate_with_context(&ctx, game)
+            }
         }
     }
 }
@@ -865,3 +1195,69 @@ impl EvaluateEffect for super::Color {
         }
     }
 }
+
+impl EvaluateEffect for Attribute {
+    type Value = HoloMemberExtraAttribute;
+
+    fn evaluate_with_context(&self, _ctx: &EvaluateContext, _game: &Game) -> Self::Value {
+        match self {
+            Attribute::Buzz => HoloMemberExtraAttribute::Buzz,
+            Attribute::Unlimited => HoloMemberExtraAttribute::Unlimited,
+        }
+    }
+}
+
+impl EvaluateEffect for HashTag {
+    type Value = HoloMemberHashTag;
+
+    fn evaluate_with_context(&self, _ctx: &EvaluateContext, _game: &Game) -> Self::Value {
+        match self {
+            HashTag::JP => HoloMemberHashTag::JP,
+            HashTag::ID => HoloMemberHashTag::ID,
+            HashTag::EN => HoloMemberHashTag::EN,
+            HashTag::Gen0 => HoloMemberHashTag::Gen0,
+            HashTag::Gen3 => HoloMemberHashTag::Gen3,
+            HashTag::Gen4 => HoloMemberHashTag::Gen4,
+            HashTag::Gen5 => HoloMemberHashTag::Gen5,
+            HashTag::SecretSocietyholoX => HoloMemberHashTag::SecretSocietyholoX,
+            HashTag::IDGen1 => HoloMemberHashTag::IDGen1,
+            HashTag::IDGen3 => HoloMemberHashTag::IDGen3,
+            HashTag::Myth => HoloMemberHashTag::Myth,
+            HashTag::Promise => HoloMemberHashTag::Promise,
+            HashTag::Alcohol => HoloMemberHashTag::Alcohol,
+            HashTag::AnimalEars => HoloMemberHashTag::AnimalEars,
+            HashTag::Art => HoloMemberHashTag::Art,
+            HashTag::Bird => HoloMemberHashTag::Bird,
+            HashTag::Drawing => HoloMemberHashTag::Drawing,
+            HashTag::Song => HoloMemberHashTag::Song,
+        }
+    }
+}
+
+impl EvaluateEffect for Level {
+    type Value = HoloMemberLevel;
+
+    fn evaluate_with_context(&self, _ctx: &EvaluateContext, _game: &Game) -> Self::Value {
+        match self {
+            Level::Spot => HoloMemberLevel::Spot,
+            Level::Debut => HoloMemberLevel::Debut,
+            Level::First => HoloMemberLevel::First,
+            Level::Second => HoloMemberLevel::Second,
+        }
+    }
+}
+
+impl EvaluateEffect for super::SupportKind {
+    type Value = SupportKind;
+
+    fn evaluate_with_context(&self, _ctx: &EvaluateContext, _game: &Game) -> Self::Value {
+        match self {
+            super::SupportKind::Staff => SupportKind::Staff,
+            super::SupportKind::Item => SupportKind::Item,
+            super::SupportKind::Event => SupportKind::Event,
+            super::SupportKind::Tool => SupportKind::Tool,
+            super::SupportKind::Mascot => SupportKind::Mascot,
+            super::SupportKind::Fan => SupportKind::Fan,
+        }
+    }
+}
@@ -230,17 +230,37 @@ impl FromStr for Tokens {
             Ok(())
         }
 
-        // group tokens by line
-        let by_line = s.replace('(', "((").replace(')', "))").replace('\n', ")(");
-        let by_line = format!("({by_line})");
+        // group tokens by line, string literals are kept as is
+        let mut by_line = String::from("(");
+        let mut in_string = false;
+        for c in s.chars() {
+            match c {
+                '"' => {
+                    in_string = !in_string;
+                    by_line.push(c);
+                }
+                c if in_string => by_line.push(c),
+                '(' => by_line.push_str("(("),
+                ')' => by_line.push_str("))"),
+                '\n' => by_line.push_str(")("),
+                c => by_line.push(c),
+            }
+        }
+        by_line.push(')');
 
         let mut stack = Vec::new();
         let mut token = String::new();
         let mut list = Vec::new();
         let mut bracket_level = 0;
+        let mut in_string = false;
 
         for c in by_line.chars() {
             match c {
+                '"' => {
+                    in_string = !in_string;
+                    token.push(c);
+                }
+                c if in_string => token.push(c),
                 '(' => {
                     bracket_level += 1;
                     add_token(&mut list, token)?;
@@ -273,6 +293,11 @@ impl FromStr for Tokens {
         }
         add_token(&mut list, token)?;
 
+        // check closed string literal
+        if in_string {
+            return Err(Error::UnclosedString);
+        }
+
         // check balanced bracket
         if bracket_level != 0 {
             return Err(Error::UnbalancedBrackets);
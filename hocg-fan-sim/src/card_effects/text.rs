@@ -0,0 +1,813 @@
+use std::collections::HashMap;
+
+use iter_tools::Itertools;
+
+use super::effects::*;
+
+/// how a variable, or any other noun, should be written in a sentence
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Noun {
+    text: String,
+    plural: bool,
+}
+
+impl Noun {
+    fn singular(text: impl Into<String>) -> Self {
+        Noun {
+            text: text.into(),
+            plural: false,
+        }
+    }
+    fn plural(text: impl Into<String>) -> Self {
+        Noun {
+            text: text.into(),
+            plural: true,
+        }
+    }
+
+    /// conjugate a verb with this noun as the subject
+    fn verb(&self, singular: &str, plural: &str) -> String {
+        if self.plural {
+            format!("{} {plural}", self.text)
+        } else {
+            format!("{} {singular}", self.text)
+        }
+    }
+}
+
+/// the subject of a condition, none when used as a filter ("cards that are green")
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Subject(Option<String>, bool);
+
+impl Subject {
+    fn this_card() -> Self {
+        Subject(Some("this card".into()), false)
+    }
+    fn filter(plural: bool) -> Self {
+        Subject(None, plural)
+    }
+
+    fn verb(&self, singular: &str, plural: &str) -> String {
+        let verb = if self.1 { plural } else { singular };
+        match &self.0 {
+            Some(subject) => format!("{subject} {verb}"),
+            None => verb.into(),
+        }
+    }
+    fn is(&self) -> String {
+        self.verb("is", "are")
+    }
+    fn has(&self) -> String {
+        self.verb("has", "have")
+    }
+    /// "is a holomem" or "are holomem"
+    fn is_a(&self, noun: &str) -> String {
+        if self.1 {
+            // holomem and oshi are the same in plural
+            let plural = if noun.ends_with("holomem") || noun.ends_with("oshi") {
+                noun.to_string()
+            } else {
+                format!("{noun}s")
+            };
+            format!("{} {plural}", self.is())
+        } else {
+            let article = if noun.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                "an"
+            } else {
+                "a"
+            };
+            format!("{} {article} {noun}", self.is())
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextContext {
+    variables: HashMap<String, Noun>,
+    // conditions are written depending on their subject, where they are used
+    conditions: HashMap<String, Condition>,
+}
+
+impl TextContext {
+    fn var(&self, var: &Var) -> Noun {
+        self.variables.get(&var.0).cloned().unwrap_or_else(|| {
+            // unknown variable, use its name
+            Noun::singular(format!(
+                "the {}",
+                var.0.trim_start_matches('$').replace('_', " ")
+            ))
+        })
+    }
+    fn set_var(&mut self, var: &Var, noun: Noun) {
+        self.variables.insert(var.0.clone(), noun);
+    }
+}
+
+/// Renders effects as plain English, to compare with the official card text
+pub trait EffectText {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String;
+
+    fn to_text(&self) -> String {
+        self.text_with_context(&mut TextContext::default())
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn uncapitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn sentence(s: String) -> String {
+    format!("{}.", capitalize(&s))
+}
+
+fn cards_word(amount: &Number) -> &'static str {
+    if *amount == Number::Literal(NumberLiteral(1)) {
+        "card"
+    } else {
+        "cards"
+    }
+}
+
+impl EffectText for [Action] {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        self.iter()
+            .map(|a| a.text_with_context(ctx))
+            .filter(|s| !s.is_empty())
+            .join(" ")
+    }
+}
+
+impl EffectText for Vec<Action> {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        self.as_slice().text_with_context(ctx)
+    }
+}
+
+impl EffectText for [Condition] {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        let conditions = self
+            .iter()
+            .filter(|c| **c != Condition::True)
+            .map(|c| c.text_with_context(ctx))
+            .collect_vec();
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            sentence(format!("only if {}", conditions.join(" and ")))
+        }
+    }
+}
+
+impl EffectText for Vec<Condition> {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        self.as_slice().text_with_context(ctx)
+    }
+}
+
+impl EffectText for Action {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        match self {
+            Action::AddCounter(cards, name, amount, life_time) => sentence(format!(
+                "put {} \"{}\" counters on {}{}",
+                amount.text_with_context(ctx),
+                name.0,
+                cards.noun(ctx).text,
+                life_time.text_with_context(ctx)
+            )),
+            Action::AddGlobalModifier(player, modifier, life_time) => {
+                let player = match player {
+                    Player::You => Noun::plural("you"),
+                    Player::Opponent => Noun::singular("your opponent"),
+                };
+                sentence(format!(
+                    "{} {}{}",
+                    player.verb("gains", "gain"),
+                    modifier.text_with_context(ctx),
+                    life_time.text_with_context(ctx)
+                ))
+            }
+            Action::AddModifier(cards, modifier, life_time) => sentence(format!(
+                "{} {}{}",
+                cards.noun(ctx).verb("gains", "gain"),
+                modifier.text_with_context(ctx),
+                life_time.text_with_context(ctx)
+            )),
+            Action::AddZoneModifier(zone, modifier, life_time) => sentence(format!(
+                "holomem in {} gain {}{}",
+                zone.text(),
+                modifier.text_with_context(ctx),
+                life_time.text_with_context(ctx)
+            )),
+            Action::ArchiveAttachedCheers(cards) => sentence(format!(
+                "archive the cheers attached to {}",
+                cards.noun(ctx).text
+            )),
+            Action::AttachCards(attachments, target) => sentence(format!(
+                "attach {} to {}",
+                attachments.noun(ctx).text,
+                target.noun(ctx).text
+            )),
+            Action::Bloom(bloom, target) => sentence(format!(
+                "bloom {} with {}",
+                target.noun(ctx).text,
+                bloom.noun(ctx).text
+            )),
+            Action::ChooseOne(choices) => {
+                let choices = choices
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| format!("({}) {}", i + 1, c.text_with_context(&mut ctx.clone())))
+                    .join(" ");
+                format!("Choose one: {choices}")
+            }
+            Action::DealDamage(cards, amount) => sentence(format!(
+                "deal {} damage to {}",
+                amount.text_with_context(ctx),
+                cards.noun(ctx).text
+            )),
+            Action::DealSpecialDamage(cards, amount) => sentence(format!(
+                "deal {} special damage to {}",
+                amount.text_with_context(ctx),
+                cards.noun(ctx).text
+            )),
+            Action::Discard(cards) => {
+                sentence(format!("archive {} from your hand", cards.noun(ctx).text))
+            }
+            Action::Draw(amount) => sentence(format!(
+                "draw {} {}",
+                amount.text_with_context(ctx),
+                cards_word(amount)
+            )),
+            Action::ForEach(var, cards, actions) => {
+                let cards = cards.noun(ctx).text;
+                let mut ctx = ctx.clone();
+                ctx.set_var(var, Noun::singular("that card"));
+                format!(
+                    "For each of {cards}: {}",
+                    actions.text_with_context(&mut ctx)
+                )
+            }
+            Action::Heal(cards, amount) => sentence(format!(
+                "restore {} HP to {}",
+                amount.text_with_context(ctx),
+                cards.noun(ctx).text
+            )),
+            Action::If(condition, actions) => {
+                let condition = condition.text_with_context(ctx);
+                let actions = actions.text_with_context(&mut ctx.clone());
+                format!("If {condition}, {}", uncapitalize(&actions))
+            }
+            Action::KnockOut(cards) => sentence(format!("knock out {}", cards.noun(ctx).text)),
+            Action::LetCardReferences(Let(var, cards)) => {
+                let noun = cards.noun(ctx);
+                ctx.set_var(var, noun);
+                String::new()
+            }
+            Action::LetCondition(Let(var, condition)) => {
+                ctx.conditions.insert(var.0.clone(), condition.clone());
+                String::new()
+            }
+            Action::LetSelect(Let(var, value)) => {
+                let (text, mut noun) = value.text(ctx);
+                // multiple selections in the same effect, use the variable name to tell them apart
+                if ctx.variables.values().any(|n| n.text == noun.text) {
+                    noun.text = format!(
+                        "{} ({})",
+                        noun.text,
+                        var.0.trim_start_matches('$').replace('_', " ")
+                    );
+                }
+                ctx.set_var(var, noun);
+                text
+            }
+            Action::LetNumber(Let(var, number)) => {
+                let noun = Noun::singular(number.text_with_context(ctx));
+                ctx.set_var(var, noun);
+                String::new()
+            }
+            Action::Noop => String::new(),
+            Action::RemoveCounter(cards, name, amount) => sentence(format!(
+                "remove {} \"{}\" counters from {}",
+                amount.text_with_context(ctx),
+                name.0,
+                cards.noun(ctx).text
+            )),
+            Action::Repeat(amount, actions) => format!(
+                "Repeat {} times: {}",
+                amount.text_with_context(ctx),
+                actions.text_with_context(&mut ctx.clone())
+            ),
+            Action::ReturnToHand(cards) => {
+                sentence(format!("return {} to the hand", cards.noun(ctx).text))
+            }
+            Action::Reveal(cards) => sentence(format!("reveal {}", cards.noun(ctx).text)),
+            Action::Search(amount, condition) => {
+                let filter = match condition {
+                    Condition::Anything => String::new(),
+                    _ => format!(
+                        " that {}",
+                        condition.text_with_subject(&Subject::filter(false), ctx)
+                    ),
+                };
+                let search = sentence(format!(
+                    "search your deck for up to {} {}{filter}, reveal them and put them into your hand",
+                    amount.text_with_context(ctx),
+                    cards_word(amount)
+                ));
+                format!("{search} Then shuffle your deck.")
+            }
+            Action::SendTo(zone, cards) => {
+                let cards = cards.noun(ctx).text;
+                match zone {
+                    Zone::Archive => sentence(format!("archive {cards}")),
+                    _ => sentence(format!("put {cards} into {}", zone.text())),
+                }
+            }
+            Action::SendToBottom(zone, cards) => sentence(format!(
+                "put {} on the bottom of {}",
+                cards.noun(ctx).text,
+                zone.text()
+            )),
+            Action::SendToTop(zone, cards) => sentence(format!(
+                "put {} on top of {}",
+                cards.noun(ctx).text,
+                zone.text()
+            )),
+            Action::Shuffle(zone) => sentence(format!("shuffle {}", zone.text())),
+            Action::SwapCenter(card) => sentence(format!(
+                "swap {} with your center holomem",
+                card.noun(ctx).text
+            )),
+        }
+    }
+}
+
+impl LetValue {
+    /// the sentence for the prompt, and how to refer to the chosen value afterward
+    fn text(&self, ctx: &mut TextContext) -> (String, Noun) {
+        match self {
+            LetValue::OptionalActivate => {
+                ("You may use this effect.".into(), Noun::singular("you do"))
+            }
+            LetValue::RollDice => ("Roll a die.".into(), Noun::singular("the result")),
+            LetValue::SelectAny(cards, condition) => (
+                sentence(format!(
+                    "choose any number of {}",
+                    cards.filtered(condition, ctx).text
+                )),
+                Noun::plural("the chosen cards"),
+            ),
+            LetValue::SelectOne(cards, condition) => (
+                sentence(format!(
+                    "choose 1 of {}",
+                    cards.filtered(condition, ctx).text
+                )),
+                Noun::singular("the chosen card"),
+            ),
+            LetValue::SelectNumberBetween(min, max) => (
+                sentence(format!(
+                    "choose a number between {} and {}",
+                    min.text_with_context(ctx),
+                    max.text_with_context(ctx)
+                )),
+                Noun::singular("the chosen number"),
+            ),
+            LetValue::SelectUpTo(amount, cards, condition) => (
+                sentence(format!(
+                    "choose up to {} of {}",
+                    amount.text_with_context(ctx),
+                    cards.filtered(condition, ctx).text
+                )),
+                Noun::plural("the chosen cards"),
+            ),
+        }
+    }
+}
+
+impl CardReference {
+    fn noun(&self, ctx: &TextContext) -> Noun {
+        match self {
+            CardReference::ArtTarget => Noun::singular("the art target"),
+            CardReference::AttachTarget => Noun::singular("the attach target"),
+            CardReference::EventOrigin => Noun::singular("the card that caused the event"),
+            CardReference::ThisCard => Noun::singular("this card"),
+            CardReference::Var(var) => ctx.var(var),
+        }
+    }
+}
+
+impl CardReferences {
+    fn noun(&self, ctx: &TextContext) -> Noun {
+        match self {
+            CardReferences::ArtTarget => Noun::singular("the art target"),
+            CardReferences::AttachedTo(card) => {
+                Noun::plural(format!("the cards attached to {}", card.noun(ctx).text))
+            }
+            CardReferences::AttachTarget => Noun::singular("the attach target"),
+            CardReferences::EventOrigin => Noun::singular("the card that caused the event"),
+            CardReferences::From(zone) => Noun::plural(format!("the cards in {}", zone.text())),
+            CardReferences::FromTop(amount, zone) => {
+                if cards_word(amount) == "card" {
+                    Noun::singular(format!("the top card of {}", zone.text()))
+                } else {
+                    let amount = amount.text_with_context(&mut ctx.clone());
+                    Noun::plural(format!("the top {amount} cards of {}", zone.text()))
+                }
+            }
+            CardReferences::Leftovers => Noun::plural("the cards that were not chosen"),
+            CardReferences::ThisCard => Noun::singular("this card"),
+            CardReferences::Var(var) => ctx.var(var),
+            CardReferences::Filter(cards, condition) => cards.filtered(condition, ctx),
+        }
+    }
+
+    fn filtered(&self, condition: &Condition, ctx: &TextContext) -> Noun {
+        let noun = self.noun(ctx);
+        match condition {
+            Condition::Anything | Condition::True => noun,
+            _ => Noun {
+                text: format!(
+                    "{} that {}",
+                    noun.text,
+                    condition.text_with_subject(&Subject::filter(noun.plural), &mut ctx.clone())
+                ),
+                plural: noun.plural,
+            },
+        }
+    }
+}
+
+impl EffectText for Condition {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        self.text_with_subject(&Subject::this_card(), ctx)
+    }
+}
+
+impl Condition {
+    fn text_with_subject(&self, subject: &Subject, ctx: &mut TextContext) -> String {
+        match self {
+            Condition::All(cards, condition) => {
+                let cards = cards.noun(ctx);
+                let all = if cards.plural {
+                    Subject(Some(format!("all of {}", cards.text)), true)
+                } else {
+                    Subject(Some(cards.text), false)
+                };
+                condition.text_with_subject(&all, ctx)
+            }
+            Condition::And(a, b) => format!(
+                "{} and {}",
+                a.text_with_subject(subject, ctx),
+                b.text_with_subject(subject, ctx)
+            ),
+            Condition::Any(cards, condition) => {
+                let cards = cards.noun(ctx);
+                let any = if cards.plural {
+                    Subject(Some(format!("at least one of {}", cards.text)), false)
+                } else {
+                    Subject(Some(cards.text), false)
+                };
+                condition.text_with_subject(&any, ctx)
+            }
+            Condition::Anything => subject.is_a("card"),
+            Condition::Equals(a, b) => format!(
+                "{} is {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::Exists(cards) => {
+                format!("there is at least one card among {}", cards.noun(ctx).text)
+            }
+            Condition::False => "never".into(),
+            Condition::GreaterThan(a, b) => format!(
+                "{} is greater than {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::GreaterThanEquals(a, b) => format!(
+                "{} is {} or greater",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::HasAttribute(attribute) => {
+                format!("{} the {} attribute", subject.has(), attribute.text())
+            }
+            Condition::HasCheers => format!("{} cheers attached", subject.has()),
+            Condition::HasTag(tag) => format!("{} the #{} tag", subject.has(), tag.text()),
+            Condition::IsAttributeBuzz => subject.is_a("Buzz holomem"),
+            Condition::IsColor(color) => format!("{} {}", subject.is(), color.text()),
+            Condition::IsCheer => subject.is_a("cheer"),
+            Condition::IsEven(number) => format!("{} is even", number.text_with_context(ctx)),
+            Condition::IsInZone(zone) => format!("{} in {}", subject.is(), zone.text()),
+            Condition::IsLevel(level) => subject.is_a(&format!("{} holomem", level.text())),
+            Condition::IsLevelFirst => subject.is_a("1st holomem"),
+            Condition::IsLevelSecond => subject.is_a("2nd holomem"),
+            Condition::IsMember => subject.is_a("holomem"),
+            Condition::IsNamed(name) => format!("{} named \"{}\"", subject.is(), name.0),
+            Condition::IsNamedAzki => format!("{} named \"AZKi\"", subject.is()),
+            Condition::IsNamedOmaruPolka => format!("{} named \"Omaru Polka\"", subject.is()),
+            Condition::IsNamedTokinoSora => format!("{} named \"Tokino Sora\"", subject.is()),
+            Condition::IsNamedUsadaPekora => format!("{} named \"Usada Pekora\"", subject.is()),
+            Condition::IsCard(card) => format!("{} {}", subject.is(), card.noun(ctx).text),
+            Condition::IsNotCard(card) => format!("{} not {}", subject.is(), card.noun(ctx).text),
+            Condition::IsOdd(number) => format!("{} is odd", number.text_with_context(ctx)),
+            Condition::IsOshi => subject.is_a("oshi"),
+            Condition::IsSupportKind(kind) => {
+                subject.is_a(&format!("{} support card", kind.text()))
+            }
+            Condition::IsSupportLimited => subject.is_a("limited support card"),
+            Condition::LessThan(a, b) => format!(
+                "{} is less than {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::LessThanEquals(a, b) => format!(
+                "{} is {} or less",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::Not(condition) => {
+                // the negation is easier to read with an explicit subject
+                let subject = match subject {
+                    Subject(None, false) => Subject(Some("it".into()), false),
+                    Subject(None, true) => Subject(Some("they".into()), true),
+                    subject => subject.clone(),
+                };
+                format!(
+                    "it is not true that {}",
+                    condition.text_with_subject(&subject, ctx)
+                )
+            }
+            Condition::NotEquals(a, b) => format!(
+                "{} is not {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Condition::Or(a, b) => format!(
+                "{} or {}",
+                a.text_with_subject(subject, ctx),
+                b.text_with_subject(subject, ctx)
+            ),
+            Condition::True => "always".into(),
+            Condition::Var(var) => match ctx.conditions.get(&var.0).cloned() {
+                Some(condition) => condition.text_with_subject(subject, ctx),
+                None => ctx.var(var).text,
+            },
+            Condition::Yours => format!("{} yours", subject.is()),
+        }
+    }
+}
+
+impl EffectText for Number {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        match self {
+            Number::Count(cards) => format!("the number of {}", cards.noun(ctx).text),
+            Number::Divide(a, b) => format!(
+                "{} divided by {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::DivideRoundUp(a, b) => format!(
+                "{} divided by {}, rounded up",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Literal(n) => n.0.to_string(),
+            Number::Max(a, b) => format!(
+                "the greater of {} and {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Min(a, b) => format!(
+                "the lesser of {} and {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Minus(a, b) => format!(
+                "{} minus {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Multiply(a, b) => format!(
+                "{} times {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Plus(a, b) => format!(
+                "{} plus {}",
+                a.text_with_context(ctx),
+                b.text_with_context(ctx)
+            ),
+            Number::Sum(cards, number) => format!(
+                "the total of {} for {}",
+                number.text_with_context(ctx),
+                cards.noun(ctx).text
+            ),
+            Number::Var(var) => ctx.var(var).text,
+            Number::Counter(name) => format!("the number of \"{}\" counters", name.0),
+            Number::DamageAmount => "its damage".into(),
+            Number::HealthPointAmount => "its remaining HP".into(),
+        }
+    }
+}
+
+impl EffectText for Modifier {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        match self {
+            Modifier::AsArtCost(amount, color) => format!(
+                "\"can be treated as {} {} cheer for art costs\"",
+                amount.text_with_context(ctx),
+                color.text()
+            ),
+            Modifier::AsCheer(amount, color) => format!(
+                "\"can be treated as {} {} cheer\"",
+                amount.text_with_context(ctx),
+                color.text()
+            ),
+            Modifier::BatonPassCost(amount) => {
+                format!("a baton pass cost of {}", amount.text_with_context(ctx))
+            }
+            Modifier::CollabWhileResting => "\"can collab while resting\"".into(),
+            Modifier::DealLessDamage(amount) => {
+                format!("-{} damage", amount.text_with_context(ctx))
+            }
+            Modifier::DealMoreDamage(amount) => {
+                format!("+{} damage", amount.text_with_context(ctx))
+            }
+            Modifier::InsteadOf(event, actions) => format!(
+                "\"instead of {}: {}\"",
+                event.text(),
+                actions.text_with_context(&mut ctx.clone())
+            ),
+            Modifier::LessArtCost(amount, color) => format!(
+                "-{} {} art cost",
+                amount.text_with_context(ctx),
+                color.text()
+            ),
+            Modifier::MoreHp(amount) => format!("+{} HP", amount.text_with_context(ctx)),
+            Modifier::Prevent(event) => format!("\"prevent {}\"", event.text()),
+            Modifier::ReceiveLessDamage(amount) => {
+                format!("-{} damage received", amount.text_with_context(ctx))
+            }
+            Modifier::ReceiveMoreDamage(amount) => {
+                format!("+{} damage received", amount.text_with_context(ctx))
+            }
+            Modifier::NextDiceRoll(amount) => {
+                format!("\"the next die roll is {}\"", amount.text_with_context(ctx))
+            }
+            Modifier::NoLifeLoss => "\"no life loss\"".into(),
+            Modifier::UntargetableByArts => "\"cannot be targeted by arts\"".into(),
+            Modifier::When(condition, modifier) => format!(
+                "{} when {}",
+                modifier.text_with_context(ctx),
+                condition.text_with_subject(&Subject(Some("it".into()), false), ctx)
+            ),
+        }
+    }
+}
+
+impl EffectText for LifeTime {
+    fn text_with_context(&self, ctx: &mut TextContext) -> String {
+        match self {
+            LifeTime::ThisGame => " for the rest of the game".into(),
+            LifeTime::ThisTurn => " this turn".into(),
+            LifeTime::NextTurn(Player::You) => " until the end of your next turn".into(),
+            LifeTime::NextTurn(Player::Opponent) => {
+                " until the end of your opponent's next turn".into()
+            }
+            LifeTime::ThisStep => " this step".into(),
+            LifeTime::ThisArt => " for this art".into(),
+            LifeTime::ThisEffect => " for this effect".into(),
+            LifeTime::UntilRemoved => String::new(),
+            LifeTime::WhileAttached(card) => {
+                format!(" while attached to {}", card.noun(ctx).text)
+            }
+        }
+    }
+}
+
+impl ReplacedEvent {
+    fn text(&self) -> String {
+        match self {
+            ReplacedEvent::KnockedOut => "being knocked out".into(),
+            ReplacedEvent::ReceiveDamage => "receiving damage".into(),
+            ReplacedEvent::SendToZone(zone) => format!("being sent to {}", zone.text()),
+        }
+    }
+}
+
+impl Zone {
+    fn text(&self) -> String {
+        self.text_for(&Player::You)
+    }
+
+    fn text_for(&self, player: &Player) -> String {
+        let owner = match player {
+            Player::You => "your",
+            Player::Opponent => "your opponent's",
+        };
+        let zone = match self {
+            Zone::ActivateSupport => "support zone",
+            Zone::Archive => "archive",
+            Zone::BackStage => "back position",
+            Zone::CenterStage => "center position",
+            Zone::CheerDeck => "cheer deck",
+            Zone::Collab => "collab position",
+            Zone::Hand => "hand",
+            Zone::HoloPower => "holo power",
+            Zone::Life => "life",
+            Zone::MainDeck => "deck",
+            Zone::MainStage => "center and collab positions",
+            Zone::OpponentBackStage => return Zone::BackStage.text_for(&Player::Opponent),
+            Zone::OpponentCenterStage => return Zone::CenterStage.text_for(&Player::Opponent),
+            Zone::Oshi => "oshi",
+            Zone::Stage => "stage",
+            Zone::Of(player, zone) => return zone.text_for(player),
+        };
+        format!("{owner} {zone}")
+    }
+}
+
+impl Color {
+    fn text(&self) -> &'static str {
+        match self {
+            Color::White => "white",
+            Color::Green => "green",
+            Color::Red => "red",
+            Color::Blue => "blue",
+            Color::Purple => "purple",
+            Color::Yellow => "yellow",
+            Color::Colorless => "colorless",
+        }
+    }
+}
+
+impl Attribute {
+    fn text(&self) -> &'static str {
+        match self {
+            Attribute::Buzz => "Buzz",
+            Attribute::Unlimited => "Unlimited",
+        }
+    }
+}
+
+impl HashTag {
+    fn text(&self) -> &'static str {
+        match self {
+            HashTag::JP => "JP",
+            HashTag::ID => "ID",
+            HashTag::EN => "EN",
+            HashTag::Gen0 => "Gen0",
+            HashTag::Gen3 => "Gen3",
+            HashTag::Gen4 => "Gen4",
+            HashTag::Gen5 => "Gen5",
+            HashTag::SecretSocietyholoX => "SecretSocietyholoX",
+            HashTag::IDGen1 => "IDGen1",
+            HashTag::IDGen3 => "IDGen3",
+            HashTag::Myth => "Myth",
+            HashTag::Promise => "Promise",
+            HashTag::Alcohol => "Alcohol",
+            HashTag::AnimalEars => "AnimalEars",
+            HashTag::Art => "Art",
+            HashTag::Bird => "Bird",
+            HashTag::Drawing => "Drawing",
+            HashTag::Song => "Song",
+        }
+    }
+}
+
+impl Level {
+    fn text(&self) -> &'static str {
+        match self {
+            Level::Spot => "Spot",
+            Level::Debut => "Debut",
+            Level::First => "1st",
+            Level::Second => "2nd",
+        }
+    }
+}
+
+impl SupportKind {
+    fn text(&self) -> &'static str {
+        match self {
+            SupportKind::Staff => "staff",
+            SupportKind::Item => "item",
+            SupportKind::Event => "event",
+            SupportKind::Tool => "tool",
+            SupportKind::Mascot => "mascot",
+            SupportKind::Fan => "fan",
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fmt::{Display, Write},
+    sync::{Arc, Mutex},
+};
+
+use serde::Serialize;
+
+use crate::{events::EventSpan, gameplay::CardRef};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraceKind {
+    Effect,
+    Action,
+    Condition,
+    Event,
+}
+
+/// a single evaluation, with the evaluations that happened during it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraceNode {
+    pub kind: TraceKind,
+    /// the evaluated effect, or the emitted event
+    pub label: String,
+    /// the card evaluating the effect
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card: Option<String>,
+    /// from the event span, the card at the origin of the current event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    /// bound variables, condition result, ...
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    pub fn new(
+        kind: TraceKind,
+        label: impl Into<String>,
+        card: Option<CardRef>,
+        span: &EventSpan,
+    ) -> Self {
+        TraceNode {
+            kind,
+            label: label.into(),
+            card: card.map(|c| c.to_string()),
+            origin: span.current_card().map(|c| c.to_string()),
+            details: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn write_text(&self, f: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        let kind = match self.kind {
+            TraceKind::Effect => "effect",
+            TraceKind::Action => "action",
+            TraceKind::Condition => "condition",
+            TraceKind::Event => "event",
+        };
+        write!(f, "{indent}{kind}: {}", self.label.replace('\n', " ")).unwrap();
+        if let Some(card) = &self.card {
+            write!(f, " [card: {card}]").unwrap();
+        }
+        if let Some(origin) = &self.origin {
+            write!(f, " [origin: {origin}]").unwrap();
+        }
+        writeln!(f).unwrap();
+        for detail in &self.details {
+            writeln!(f, "{indent}  -> {detail}").unwrap();
+        }
+        for child in &self.children {
+            child.write_text(f, depth + 1);
+        }
+    }
+}
+
+/// the completed evaluations, retrieved from the tracer
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+#[serde(transparent)]
+pub struct Trace(pub Vec<TraceNode>);
+
+impl Trace {
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for node in &self.0 {
+            node.write_text(&mut text, 0);
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("trace should serialize")
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+
+#[derive(Debug, Default)]
+struct TraceTree {
+    completed: Vec<TraceNode>,
+    open: Vec<TraceNode>,
+}
+
+/// Opt-in recording of effect evaluations, shared by the contexts of the same game
+#[derive(Debug, Clone, Default)]
+pub struct EvaluationTracer(Arc<Mutex<TraceTree>>);
+
+impl PartialEq for EvaluationTracer {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl Eq for EvaluationTracer {}
+
+impl EvaluationTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&self, node: TraceNode) {
+        self.0.lock().unwrap().open.push(node);
+    }
+
+    pub fn close(&self, details: impl IntoIterator<Item = String>) {
+        let mut tree = self.0.lock().unwrap();
+        let Some(mut node) = tree.open.pop() else {
+            return;
+        };
+        node.details.extend(details);
+        if let Some(parent) = tree.open.last_mut() {
+            parent.children.push(node);
+        } else {
+            tree.completed.push(node);
+        }
+    }
+
+    /// a node without children
+    pub fn record(&self, node: TraceNode, details: impl IntoIterator<Item = String>) {
+        self.open(node);
+        self.close(details);
+    }
+
+    /// retrieve the completed evaluations, the tracer keeps recording afterward
+    pub fn take_trace(&self) -> Trace {
+        Trace(std::mem::take(&mut self.0.lock().unwrap().completed))
+    }
+}
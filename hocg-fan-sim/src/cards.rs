@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::events::{Bloom, Collab, Event, TriggeredEvent};
 use crate::gameplay::Zone;
 use crate::gameplay::{CardRef, GameDirector};
+use crate::modifiers::LifeTime;
 use crate::modifiers::ModifierKind::{self, *};
 use crate::{
     card_effects::{
@@ -120,6 +121,15 @@ impl Card {
         }
     }
 
+    pub fn name(&self) -> &str {
+        match self {
+            Card::OshiHoloMember(c) => &c.name,
+            Card::HoloMember(c) => &c.name,
+            Card::Support(c) => &c.name,
+            Card::Cheer(c) => &c.name,
+        }
+    }
+
     pub fn is_color(&self, color: Color, card: CardRef, game: &Game) -> bool {
         match self {
             Card::OshiHoloMember(o) => o.color == color,
@@ -142,6 +152,13 @@ impl Card {
             })
     }
 
+    pub fn has_hash_tag(&self, tag: HoloMemberHashTag) -> bool {
+        match self {
+            Card::HoloMember(m) => m.hash_tags.contains(&tag),
+            _ => false,
+        }
+    }
+
     pub fn is_level(&self, level: HoloMemberLevel) -> bool {
         match self {
             Card::HoloMember(m) => m.level == level,
@@ -155,11 +172,23 @@ impl Card {
 
     pub fn is_named(&self, name: &str) -> bool {
         match self {
+            Card::OshiHoloMember(o) => o.name == name,
             Card::HoloMember(m) => m.names().contains(&name.to_string()),
             _ => false,
         }
     }
 
+    pub fn is_oshi(&self) -> bool {
+        matches!(self, Card::OshiHoloMember(_))
+    }
+
+    pub fn is_support_kind(&self, kind: SupportKind) -> bool {
+        match self {
+            Card::Support(s) => s.kind == kind,
+            _ => false,
+        }
+    }
+
     pub fn is_support_limited(&self) -> bool {
         match self {
             Card::Support(s) => s.limited,
@@ -271,11 +300,17 @@ impl OshiHoloMemberCard {
             return false;
         }
 
-        //  cannot use the same skill twice in a turn
+        //  could prevent skill by effect
         if game.has_modifier(card, PreventOshiSkill(skill_idx)) {
             return false;
         }
 
+        //  once per turn / once per game
+        let usage = format!("oshi_skill {skill_idx}");
+        if game.has_reached_usage_limit(card, &usage, self.skills[skill_idx].limit) {
+            return false;
+        }
+
         self.skills[skill_idx]
             .condition
             .ctx()
@@ -292,6 +327,10 @@ pub struct OshiSkill {
     pub name: String,
     pub cost: OshiSkillCost,
     pub text: String,
+    /// when unlimited, the limit of the kind is used. see [`OshiSkillKind::default_limit`]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "UsageLimit::is_unlimited")]
+    pub limit: UsageLimit,
     pub triggers: CardEffectTrigger,
     #[serde(serialize_with = "serialize_conditions")]
     #[serde(deserialize_with = "deserialize_conditions")]
@@ -310,6 +349,52 @@ pub enum OshiSkillKind {
     Special,
 }
 
+impl OshiSkillKind {
+    /// the rules limit the oshi skills, without it being written on the cards
+    pub fn default_limit(&self) -> UsageLimit {
+        match self {
+            OshiSkillKind::Normal => UsageLimit::OncePerTurn,
+            OshiSkillKind::Special => UsageLimit::OncePerGame,
+        }
+    }
+}
+
+#[derive(
+    Encode, Decode, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, GetSize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageLimit {
+    #[default]
+    Unlimited,
+    OncePerTurn,
+    OncePerGame,
+    /// shared by every card with the same name
+    OncePerTurnPerName,
+    /// shared by every card with the same name
+    OncePerGamePerName,
+}
+
+impl UsageLimit {
+    pub fn is_unlimited(&self) -> bool {
+        *self == UsageLimit::Unlimited
+    }
+
+    pub fn is_per_name(&self) -> bool {
+        matches!(
+            self,
+            UsageLimit::OncePerTurnPerName | UsageLimit::OncePerGamePerName
+        )
+    }
+
+    pub fn life_time(&self) -> Option<LifeTime> {
+        match self {
+            UsageLimit::Unlimited => None,
+            UsageLimit::OncePerTurn | UsageLimit::OncePerTurnPerName => Some(LifeTime::ThisTurn),
+            UsageLimit::OncePerGame | UsageLimit::OncePerGamePerName => Some(LifeTime::ThisGame),
+        }
+    }
+}
+
 #[derive(Encode, Decode, Serialize, Deserialize, Debug, Clone, GetSize)]
 #[serde(rename_all = "snake_case")]
 pub struct HoloMemberCard {
@@ -362,7 +447,7 @@ impl HoloMemberCard {
 
         // can only baton pass if there is enough cheers attached
         let cost = std::iter::repeat(Color::Colorless)
-            .take(self.baton_pass_cost as usize)
+            .take(game.baton_pass_cost(card, self.baton_pass_cost) as usize)
             .collect_vec();
         if !game.required_attached_cheers(card, &cost, false) {
             return false;
@@ -398,7 +483,9 @@ impl HoloMemberCard {
         }
 
         //  cannot bloom if the damage is more the bloom hp, it would be knocked out instantly
-        if game.get_damage(target.0).to_hp() >= self.hp {
+        // the hp modifiers of the target are kept by the bloom card
+        let more_hp = game.max_hp(target.0).saturating_sub(target.1.hp);
+        if game.get_damage(target.0).to_hp() >= self.hp.saturating_add(more_hp) {
             return false;
         }
 
@@ -435,6 +522,12 @@ impl HoloMemberCard {
             return false;
         }
 
+        //  once per turn / once per game
+        let usage = format!("ability {ability_idx}");
+        if game.has_reached_usage_limit(card, &usage, self.abilities[ability_idx].limit) {
+            return false;
+        }
+
         self.abilities[ability_idx]
             .condition
             .ctx()
@@ -461,8 +554,14 @@ impl HoloMemberCard {
             return false;
         }
 
+        // some members cannot be targeted
+        if game.has_modifier(target_card, UntargetableByArts) {
+            return false;
+        }
+
         // need required attached cheers to attack
-        if !game.required_attached_cheers(card, &self.arts[art_idx].cost, true) {
+        let cost = game.art_cost(card, &self.arts[art_idx].cost);
+        if !game.required_attached_cheers(card, &cost, true) {
             return false;
         }
 
@@ -541,6 +640,9 @@ pub struct HoloMemberAbility {
     pub kind: MemberAbilityKind,
     pub name: String,
     pub text: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "UsageLimit::is_unlimited")]
+    pub limit: UsageLimit,
     #[serde(serialize_with = "serialize_conditions")]
     #[serde(deserialize_with = "deserialize_conditions")]
     #[serde(skip_serializing_if = "skip_default_conditions")]
@@ -552,8 +654,13 @@ pub struct HoloMemberAbility {
 }
 
 impl HoloMemberAbility {
-    pub fn should_activate(&self, card: CardRef, triggered_event: &TriggeredEvent) -> bool {
-        match self.kind {
+    pub fn should_activate(
+        &self,
+        card: CardRef,
+        triggered_event: &TriggeredEvent,
+        game: &Game,
+    ) -> bool {
+        match &self.kind {
             MemberAbilityKind::CollabEffect => {
                 if let TriggeredEvent::After(Event::Collab(Collab {
                     card: collab_card, ..
@@ -575,7 +682,9 @@ impl HoloMemberAbility {
                     false
                 }
             }
-            MemberAbilityKind::Gift(_) => unimplemented!(), // TODO not sure what gift does
+            MemberAbilityKind::Gift(triggers) => triggers
+                .iter()
+                .any(|t| t.should_activate(card, triggered_event, game)),
         }
     }
 }
@@ -669,6 +778,11 @@ impl SupportCard {
             return false;
         }
 
+        //  once per turn / once per game
+        if self.has_reached_usage_limit(card, effect_idx, game) {
+            return false;
+        }
+
         self.effects[effect_idx]
             .condition
             .ctx()
@@ -676,6 +790,16 @@ impl SupportCard {
             .evaluate(&game.game)
     }
 
+    fn has_reached_usage_limit(
+        &self,
+        card: CardRef,
+        effect_idx: usize,
+        game: &GameDirector,
+    ) -> bool {
+        let usage = format!("support_effect {effect_idx}");
+        game.has_reached_usage_limit(card, &usage, self.effects[effect_idx].limit)
+    }
+
     pub fn can_attach_target(
         &self,
         card: CardRef,
@@ -687,6 +811,11 @@ impl SupportCard {
             return false;
         }
 
+        //  once per turn / once per game
+        if self.has_reached_usage_limit(card, effect_idx, game) {
+            return false;
+        }
+
         self.effects[effect_idx]
             .condition
             .ctx()
@@ -707,6 +836,11 @@ impl SupportCard {
             return false;
         }
 
+        //  once per turn / once per game
+        if self.has_reached_usage_limit(card, effect_idx, game) {
+            return false;
+        }
+
         self.effects[effect_idx]
             .condition
             .ctx()
@@ -730,6 +864,9 @@ pub enum SupportKind {
 #[derive(Encode, Decode, Serialize, Deserialize, Debug, Clone, GetSize)]
 #[serde(rename_all = "snake_case")]
 pub struct SupportEffect {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "UsageLimit::is_unlimited")]
+    pub limit: UsageLimit,
     pub triggers: CardEffectTrigger,
     #[serde(serialize_with = "serialize_conditions")]
     #[serde(deserialize_with = "deserialize_conditions")]
@@ -1,17 +1,35 @@
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+use std::collections::VecDeque;
+
 use async_channel::{Receiver, Sender};
-use tracing::debug;
+use iter_tools::Itertools;
+use tracing::{debug, error, warn};
 
 use crate::{
     events::*,
-    gameplay::{Game, GameContinue, GameOutcome, GameResult},
+    gameplay::{Game, GameContinue, GameOutcome, GameOverReason, GameResult},
 };
 
+/// the last events in a desync report
+pub const DESYNC_REPORT_EVENTS: usize = 20;
+
 pub struct Client<E, I> {
     pub game: Game,
     pub send: Sender<ClientSend>,
     pub receive: Receiver<ClientReceive>,
     pub event_handler: E,
     pub intent_handler: I,
+    /// the connection was lost before the end of the game, see [`Client::reconnect`]
+    pub disconnected: bool,
+    /// the state was different from the server, see [`GameState::checksum`]
+    ///
+    /// [`GameState::checksum`]: crate::gameplay::GameState::checksum
+    pub desyncs: usize,
+    recent_events: VecDeque<Event>,
+    /// waiting for the state after a desync
+    sync_requested: bool,
 }
 impl<E, I> Client<E, I>
 where
@@ -29,21 +47,48 @@ where
             receive: channels.1,
             event_handler,
             intent_handler,
+            disconnected: false,
+            desyncs: 0,
+            recent_events: VecDeque::new(),
+            sync_requested: false,
         }
     }
 
+    /// continue the game with a new connection, the server resends the state of the game
+    pub fn reconnect(&mut self, channels: (Sender<ClientSend>, Receiver<ClientReceive>)) {
+        self.send = channels.0;
+        self.receive = channels.1;
+        self.disconnected = false;
+    }
+
+    /// the outcome of the game, or a disconnection without a winner
+    fn outcome(&mut self) -> GameOutcome {
+        self.game.game_outcome().unwrap_or_else(|| {
+            self.disconnected = true;
+            GameOutcome {
+                winning_player: None,
+                reason: GameOverReason::Disconnect,
+            }
+        })
+    }
+
     pub async fn handle_request(&mut self) -> GameResult {
-        let req = self
-            .receive
-            .recv()
-            .await
-            .map_err(|_| self.game.game_outcome().expect("game should be over"))?;
+        let Ok(req) = self.receive.recv().await else {
+            return Err(self.outcome());
+        };
         match req {
             ClientReceive::Event(event) => {
                 debug!("RECEIVED EVENT = {:?}", event);
 
                 // sync state with server
                 event.apply_state_change(&mut self.game.state);
+                if self.recent_events.len() == DESYNC_REPORT_EVENTS {
+                    self.recent_events.pop_front();
+                }
+                self.recent_events.push_back(event.clone());
+                if let Event::SyncGameState(_) = event {
+                    self.sync_requested = false;
+                }
 
                 if let Some(outcome) = self.game.game_outcome() {
                     return Err(outcome);
@@ -59,10 +104,47 @@ where
                     .handle_intent_request(&self.game, req)
                     .await;
                 debug!("SENT INTENT = {:?}", resp);
-                self.send
+                if self
+                    .send
                     .send(ClientSend::IntentResponse(resp))
                     .await
-                    .unwrap();
+                    .is_err()
+                {
+                    return Err(self.outcome());
+                }
+            }
+            ClientReceive::InvalidIntentResponse(error) => {
+                // the request is sent again
+                warn!("REJECTED INTENT = {error}");
+            }
+            ClientReceive::StateChecksum(expected) => {
+                let checksum = self.game.state.checksum(expected.player);
+                if checksum != expected.checksum && !self.sync_requested {
+                    self.desyncs += 1;
+                    error!(
+                        "DESYNC = expected {:016x}, found {checksum:016x}, last events:\n{}",
+                        expected.checksum,
+                        self.recent_events
+                            .iter()
+                            .map(|event| format!("{event:?}"))
+                            .join("\n")
+                    );
+                    if self
+                        .send
+                        .send(ClientSend::RequestSyncGameState)
+                        .await
+                        .is_err()
+                    {
+                        return Err(self.outcome());
+                    }
+                    self.sync_requested = true;
+                }
+            }
+            ClientReceive::Lobby(resp) => {
+                debug!("IGNORED LOBBY = {:?}", resp);
+            }
+            ClientReceive::Handshake(resp) => {
+                debug!("IGNORED HANDSHAKE = {:?}", resp);
             }
         }
 
@@ -70,11 +152,18 @@ where
     }
 
     pub async fn receive_requests(mut self) -> GameOutcome {
-        // loop until the end of the game
-        while self.handle_request().await.is_ok() {}
+        self.play().await
+    }
 
-        debug!("GAME OUTCOME = {:?}", self.game.game_outcome());
-        self.game.game_outcome().expect("game should be over")
+    /// loop until the end of the game, or until the client is disconnected
+    pub async fn play(&mut self) -> GameOutcome {
+        let outcome = loop {
+            if let Err(outcome) = self.handle_request().await {
+                break outcome;
+            }
+        };
+        debug!("GAME OUTCOME = {outcome:?}");
+        outcome
     }
 }
 
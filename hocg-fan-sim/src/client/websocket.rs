@@ -0,0 +1,151 @@
+use async_channel::{Receiver, Sender};
+use bincode::{config, Decode, Encode};
+use futures_util::{SinkExt, StreamExt};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use tokio_tungstenite::{
+    tungstenite::{protocol::WebSocketConfig, Error, Message},
+    WebSocketStream,
+};
+use tracing::{debug, error};
+
+use crate::{
+    events::{ClientReceive, ClientSend},
+    transport::{PlayerTransport, TransportError, TransportFuture},
+};
+
+pub fn encode_message<T: Encode>(message: T) -> Vec<u8> {
+    bincode::encode_to_vec(message, config::standard()).expect("message should encode")
+}
+
+/// the messages of the clients are small, at most a deck in the lobby
+pub const CLIENT_MESSAGE_LIMIT: usize = 64 * 1024;
+/// the messages of the server can have the game state, or the library during the handshake
+pub const SERVER_MESSAGE_LIMIT: usize = 16 * 1024 * 1024;
+
+/// the limit applies to the decoded size, a message cannot claim a larger allocation
+pub fn decode_message<T: Decode, const LIMIT: usize>(bytes: &[u8]) -> Option<T> {
+    bincode::decode_from_slice(bytes, config::standard().with_limit::<LIMIT>())
+        .map(|(message, _)| message)
+        .ok()
+}
+
+/// the socket does not buffer larger messages than the ones that can be decoded
+fn socket_config(limit: usize) -> WebSocketConfig {
+    WebSocketConfig {
+        max_message_size: Some(limit),
+        max_frame_size: Some(limit),
+        ..Default::default()
+    }
+}
+
+/// Connect to a game server, the channels can be used with [`crate::client::Client::new`]
+pub async fn connect(url: &str) -> Result<(Sender<ClientSend>, Receiver<ClientReceive>), Error> {
+    let config = socket_config(SERVER_MESSAGE_LIMIT);
+    let (socket, _) = tokio_tungstenite::connect_async_with_config(url, Some(config), true).await?;
+    Ok(bridge(socket))
+}
+
+/// Accept a player connection, the transport can be used with [`crate::gameplay::GameDirector::setup`]
+pub async fn accept(stream: TcpStream) -> Result<WebSocketTransport<TcpStream>, Error> {
+    // the messages are small, and sent one at a time
+    stream.set_nodelay(true)?;
+    let config = socket_config(CLIENT_MESSAGE_LIMIT);
+    let socket = tokio_tungstenite::accept_async_with_config(stream, Some(config)).await?;
+    Ok(WebSocketTransport { socket })
+}
+
+/// Network, the messages are written directly to the socket
+pub struct WebSocketTransport<S> {
+    pub socket: WebSocketStream<S>,
+}
+
+impl<S> PlayerTransport for WebSocketTransport<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+{
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            let message = Message::Binary(encode_message(message));
+            Ok(self.socket.send(message).await?)
+        })
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        Box::pin(async move {
+            loop {
+                match self.socket.next().await.transpose()? {
+                    Some(Message::Binary(bytes)) => {
+                        return decode_message::<_, CLIENT_MESSAGE_LIMIT>(&bytes).ok_or(
+                            TransportError::InvalidMessage("could not decode message".into()),
+                        )
+                    }
+                    Some(Message::Close(_)) | None => return Err(TransportError::Disconnected),
+                    Some(_) => {}
+                }
+            }
+        })
+    }
+}
+
+impl From<Error> for TransportError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::ConnectionClosed | Error::AlreadyClosed | Error::Io(_) => {
+                TransportError::Disconnected
+            }
+            error => TransportError::InvalidMessage(error.to_string()),
+        }
+    }
+}
+
+/// Forward the messages between the channels and the socket, until either side is closed
+pub fn bridge<S, O, I>(socket: WebSocketStream<S>) -> (Sender<O>, Receiver<I>)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    O: Encode + Send + 'static,
+    I: Decode + Send + 'static,
+{
+    let (mut socket_send, mut socket_receive) = socket.split();
+    let (send, outgoing) = async_channel::bounded::<O>(10);
+    let (incoming, receive) = async_channel::bounded::<I>(10);
+
+    tokio::spawn(async move {
+        while let Ok(message) = outgoing.recv().await {
+            let message = Message::Binary(encode_message(message));
+            if let Err(e) = socket_send.send(message).await {
+                error!("could not send message: {e}");
+                break;
+            }
+        }
+        // the channel is closed, so is the connection
+        let _ = socket_send.close().await;
+    });
+
+    tokio::spawn(async move {
+        while let Some(message) = socket_receive.next().await {
+            match message {
+                Ok(Message::Binary(bytes)) => {
+                    // only used to connect to a server
+                    let Some(message) = decode_message::<_, SERVER_MESSAGE_LIMIT>(&bytes) else {
+                        error!("could not decode message");
+                        break;
+                    };
+                    if incoming.send(message).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(Message::Close(_)) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    debug!("connection lost: {e}");
+                    break;
+                }
+            }
+        }
+        // dropping the sender closes the channel
+    });
+
+    (send, receive)
+}
@@ -1,16 +1,21 @@
 use crate::{
     card_effects::{
         evaluate::{EvaluateContext, EvaluateEffectMut},
+        trace::{TraceKind, TraceNode},
         Trigger,
     },
     cards::*,
     gameplay::{
-        CardRef, GameContinue, GameDirector, GameOutcome, GameOverReason, GameResult, GameState,
-        MainStepAction, PerformanceStepAction, Player, Rps, Step, Zone, ZoneAddLocation,
-        MAX_MEMBERS_ON_STAGE,
+        CardRef, Game, GameContinue, GameDirector, GameOutcome, GameOverReason, GameResult,
+        GameState, MainStepAction, PendingTrigger, PerformanceStepAction, Player, Rps, Step, Zone,
+        ZoneAddLocation, MAX_MEMBERS_ON_STAGE,
     },
+    handshake::{Handshake, HandshakeResponse},
+    lobby::{LobbyRequest, LobbyResponse},
     modifiers::{DamageMarkers, LifeTime, Modifier, ModifierKind, ModifierRef},
 };
+use std::time::Duration;
+
 use bincode::{Decode, Encode};
 use enum_dispatch::enum_dispatch;
 use get_size::GetSize;
@@ -21,13 +26,31 @@ use ModifierKind::*;
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum ClientReceive {
+    /// stays the first variant, to be read by any version
+    Handshake(HandshakeResponse),
     Event(Event),
     IntentRequest(IntentRequest),
+    /// the response to the last intent request was rejected, the request is sent again
+    InvalidIntentResponse(String),
+    StateChecksum(StateChecksum),
+    Lobby(LobbyResponse),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum ClientSend {
+    /// stays the first variant, to be read by any version
+    Handshake(Handshake),
     IntentResponse(IntentResponse),
+    /// after a desync, the state is sent again
+    RequestSyncGameState,
+    Lobby(LobbyRequest),
+}
+
+/// Sent to the players after each step, compared with [`GameState::checksum`] of their own state
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct StateChecksum {
+    pub player: Player,
+    pub checksum: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -78,6 +101,11 @@ pub enum Event {
     AddDamageMarkers,
     RemoveDamageMarkers,
     ClearDamageMarkers,
+    AddCardCounters,
+    RemoveCardCounters,
+    ClearCardCounters,
+    AddCardUsage,
+    ClearCardUsages,
 
     LookAndSelect,
     SendToZone,
@@ -101,6 +129,8 @@ pub enum Event {
     ActivateHoloMemberArtEffect,
 
     PerformArt,
+    /// marker event before resolving simultaneous triggered effects, in order
+    ResolveTriggers,
     WaitingForPlayerIntent,
 
     // Card effect events
@@ -113,6 +143,46 @@ pub enum Event {
     RollDice,
 }
 
+impl Event {
+    /// the cards concerned by the event, used to scope triggers
+    pub fn subject_cards(&self, game: &Game) -> Vec<CardRef> {
+        let cards = match self {
+            Event::SendToZone(e) => e.cards.clone(),
+            Event::AttachToCard(e) => Some(e.card)
+                .into_iter()
+                .chain(e.attachments.iter().copied())
+                .collect(),
+            Event::Collab(e) => vec![e.card],
+            Event::Bloom(e) => vec![e.from_card],
+            Event::BatonPass(e) => vec![e.from_card, e.to_card],
+            Event::PerformArt(e) => vec![e.card],
+            Event::HoloMemberKnockedOut(e) => e.cards.clone(),
+            Event::DealDamage(e) => e.targets.clone(),
+            _ => vec![],
+        };
+
+        if cards.is_empty() {
+            // the event is about the card that caused it
+            game.event_span.current_card().into_iter().collect()
+        } else {
+            cards
+        }
+    }
+
+    /// the player concerned by the event, used to scope triggers
+    pub fn subject_player(&self, game: &Game) -> Option<Player> {
+        match self {
+            Event::Draw(e) => Some(e.player),
+            Event::LoseLives(e) => Some(e.player),
+            Event::RollDice(e) => Some(e.player),
+            _ => self
+                .subject_cards(game)
+                .first()
+                .map(|c| game.player_for_card(*c)),
+        }
+    }
+}
+
 // Basic events
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
@@ -180,6 +250,315 @@ pub enum IntentRequest {
         player: Player,
         select_numbers: Vec<usize>,
     },
+    /// used by modal effects (choose one)
+    SelectOption {
+        player: Player,
+        select_options: Vec<String>,
+    },
+    /// used when many effects trigger at the same time
+    OrderTriggers {
+        player: Player,
+        select_triggers: Vec<PendingTrigger>,
+    },
+}
+
+impl IntentRequest {
+    /// the cards that the player can look at to respond, they could be hidden otherwise
+    pub fn shown_cards(&self) -> Vec<CardRef> {
+        match self {
+            IntentRequest::LookSelectZoneToZone {
+                look_cards,
+                select_cards,
+                ..
+            } => look_cards.iter().chain(select_cards).copied().collect(),
+            IntentRequest::SelectToAttach { select_cards, .. } => select_cards.clone(),
+            IntentRequest::SelectAttachments {
+                card,
+                select_attachments,
+                ..
+            } => [card.1]
+                .into_iter()
+                .chain(select_attachments.iter().copied())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// a safe response, used when the player does not respond in time
+    pub fn default_response(&self) -> IntentResponse {
+        match self {
+            IntentRequest::Rps { player, select_rps } => IntentResponse::Rps {
+                player: *player,
+                select_rps: select_rps[0],
+            },
+            IntentRequest::Mulligan {
+                player,
+                select_yes_no,
+            } => IntentResponse::Mulligan {
+                player: *player,
+                select_yes_no: !select_yes_no.contains(&false),
+            },
+            IntentRequest::ActivateEffect {
+                player,
+                select_yes_no,
+            } => IntentResponse::ActivateEffect {
+                player: *player,
+                select_yes_no: !select_yes_no.contains(&false),
+            },
+            IntentRequest::LookSelectZoneToZone {
+                player,
+                select_cards,
+                min_amount,
+                ..
+            } => IntentResponse::LookSelectZoneToZone {
+                player: *player,
+                select_cards: select_cards.iter().copied().take(*min_amount).collect(),
+            },
+            IntentRequest::SelectToAttach {
+                player,
+                select_cards,
+                ..
+            } => IntentResponse::SelectToAttach {
+                player: *player,
+                select_card: select_cards[0],
+            },
+            IntentRequest::MainStepAction {
+                player,
+                select_actions,
+            } => IntentResponse::MainStepAction {
+                player: *player,
+                select_action: select_actions
+                    .iter()
+                    .copied()
+                    .find(|a| *a == MainStepAction::Done)
+                    .unwrap_or(select_actions[0]),
+            },
+            IntentRequest::SelectAttachments {
+                player,
+                select_attachments,
+                min_amount,
+                ..
+            } => IntentResponse::SelectAttachments {
+                player: *player,
+                select_attachments: select_attachments
+                    .iter()
+                    .copied()
+                    .take(*min_amount)
+                    .collect(),
+            },
+            IntentRequest::PerformanceStepAction {
+                player,
+                select_actions,
+            } => IntentResponse::PerformanceStepAction {
+                player: *player,
+                select_action: select_actions
+                    .iter()
+                    .copied()
+                    .find(|a| *a == PerformanceStepAction::Done)
+                    .unwrap_or(select_actions[0]),
+            },
+            IntentRequest::SelectNumber {
+                player,
+                select_numbers,
+            } => IntentResponse::SelectNumber {
+                player: *player,
+                select_number: select_numbers[0],
+            },
+            IntentRequest::SelectOption { player, .. } => IntentResponse::SelectOption {
+                player: *player,
+                select_option: 0,
+            },
+            IntentRequest::OrderTriggers {
+                player,
+                select_triggers,
+            } => IntentResponse::OrderTriggers {
+                player: *player,
+                select_order: select_triggers.clone(),
+            },
+        }
+    }
+
+    /// the response is one of the choices of the request, from the player it was sent to
+    pub fn validate(&self, resp: &IntentResponse) -> Result<(), String> {
+        match (self, resp) {
+            (
+                IntentRequest::Rps { player, select_rps },
+                IntentResponse::Rps {
+                    player: resp_player,
+                    select_rps: choice,
+                },
+            ) => validate_player(*player, *resp_player).and(validate_choice(select_rps, choice)),
+            (
+                IntentRequest::Mulligan {
+                    player,
+                    select_yes_no,
+                },
+                IntentResponse::Mulligan {
+                    player: resp_player,
+                    select_yes_no: choice,
+                },
+            )
+            | (
+                IntentRequest::ActivateEffect {
+                    player,
+                    select_yes_no,
+                },
+                IntentResponse::ActivateEffect {
+                    player: resp_player,
+                    select_yes_no: choice,
+                },
+            ) => validate_player(*player, *resp_player).and(validate_choice(select_yes_no, choice)),
+            (
+                IntentRequest::LookSelectZoneToZone {
+                    player,
+                    select_cards,
+                    min_amount,
+                    max_amount,
+                    ..
+                },
+                IntentResponse::LookSelectZoneToZone {
+                    player: resp_player,
+                    select_cards: choices,
+                },
+            ) => validate_player(*player, *resp_player).and(validate_choices(
+                select_cards,
+                choices,
+                *min_amount,
+                *max_amount,
+            )),
+            (
+                IntentRequest::SelectToAttach {
+                    player,
+                    select_cards,
+                    ..
+                },
+                IntentResponse::SelectToAttach {
+                    player: resp_player,
+                    select_card,
+                },
+            ) => validate_player(*player, *resp_player)
+                .and(validate_choice(select_cards, select_card)),
+            (
+                IntentRequest::MainStepAction {
+                    player,
+                    select_actions,
+                },
+                IntentResponse::MainStepAction {
+                    player: resp_player,
+                    select_action,
+                },
+            ) => validate_player(*player, *resp_player)
+                .and(validate_choice(select_actions, select_action)),
+            (
+                IntentRequest::SelectAttachments {
+                    player,
+                    select_attachments,
+                    min_amount,
+                    max_amount,
+                    ..
+                },
+                IntentResponse::SelectAttachments {
+                    player: resp_player,
+                    select_attachments: choices,
+                },
+            ) => validate_player(*player, *resp_player).and(validate_choices(
+                select_attachments,
+                choices,
+                *min_amount,
+                *max_amount,
+            )),
+            (
+                IntentRequest::PerformanceStepAction {
+                    player,
+                    select_actions,
+                },
+                IntentResponse::PerformanceStepAction {
+                    player: resp_player,
+                    select_action,
+                },
+            ) => validate_player(*player, *resp_player)
+                .and(validate_choice(select_actions, select_action)),
+            (
+                IntentRequest::SelectNumber {
+                    player,
+                    select_numbers,
+                },
+                IntentResponse::SelectNumber {
+                    player: resp_player,
+                    select_number,
+                },
+            ) => validate_player(*player, *resp_player)
+                .and(validate_choice(select_numbers, select_number)),
+            (
+                IntentRequest::SelectOption {
+                    player,
+                    select_options,
+                },
+                IntentResponse::SelectOption {
+                    player: resp_player,
+                    select_option,
+                },
+            ) => validate_player(*player, *resp_player).and(
+                (*select_option < select_options.len())
+                    .then_some(())
+                    .ok_or_else(|| "the choice is not one of the options".to_string()),
+            ),
+            (
+                IntentRequest::OrderTriggers {
+                    player,
+                    select_triggers,
+                },
+                IntentResponse::OrderTriggers {
+                    player: resp_player,
+                    select_order,
+                },
+            ) => validate_player(*player, *resp_player).and(validate_choices(
+                select_triggers,
+                select_order,
+                select_triggers.len(),
+                select_triggers.len(),
+            )),
+            _ => Err("the response does not match the request".into()),
+        }
+    }
+}
+
+fn validate_player(player: Player, resp_player: Player) -> Result<(), String> {
+    if player == resp_player {
+        Ok(())
+    } else {
+        Err(format!("the request was sent to {player:?}"))
+    }
+}
+
+fn validate_choice<T: PartialEq>(choices: &[T], choice: &T) -> Result<(), String> {
+    if choices.contains(choice) {
+        Ok(())
+    } else {
+        Err("the choice is not one of the options".into())
+    }
+}
+
+/// each choice at most once
+fn validate_choices<T: PartialEq>(
+    choices: &[T],
+    selected: &[T],
+    min_amount: usize,
+    max_amount: usize,
+) -> Result<(), String> {
+    if selected.len() < min_amount || selected.len() > max_amount {
+        return Err(format!(
+            "select between {min_amount} and {max_amount} choices, not {}",
+            selected.len()
+        ));
+    }
+    for (i, choice) in selected.iter().enumerate() {
+        validate_choice(choices, choice)?;
+        if selected[..i].contains(choice) {
+            return Err("the same choice is selected more than once".into());
+        }
+    }
+    Ok(())
 }
 
 // #[derive(Debug, Clone, PartialEq, Eq)]
@@ -260,12 +639,29 @@ pub enum IntentResponse {
         player: Player,
         select_number: usize,
     },
+    /// used by modal effects (choose one), index of the option
+    SelectOption {
+        player: Player,
+        select_option: usize,
+    },
+    /// used when many effects trigger at the same time, in resolution order
+    OrderTriggers {
+        player: Player,
+        select_order: Vec<PendingTrigger>,
+    },
+
+    /// accepted at any prompt, the player loses the game
+    Concede {
+        player: Player,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, GetSize, Encode, Decode)]
 pub struct EventSpan {
     pub origin_stack: Vec<Option<CardRef>>,
     pub event_stack: Vec<Event>,
+    /// replacements being applied, a replacement cannot apply to its own event
+    pub replacement_stack: Vec<(ModifierRef, CardRef)>,
 }
 
 impl EventSpan {
@@ -273,6 +669,7 @@ impl EventSpan {
         Self {
             origin_stack: Vec::new(),
             event_stack: Vec::new(),
+            replacement_stack: Vec::new(),
         }
     }
 
@@ -309,6 +706,23 @@ impl EventSpan {
         assert_eq!(self.current_card(), None);
         self.origin_stack.pop();
     }
+    pub fn open_trigger_span(&mut self) {
+        // keep the origin of the event, for the triggered effects
+        self.origin_stack.push(self.current_card());
+    }
+    pub fn close_trigger_span(&mut self) {
+        self.origin_stack.pop();
+    }
+    pub fn open_replacement_span(&mut self, modifier: ModifierRef, card: CardRef) {
+        self.replacement_stack.push((modifier, card));
+    }
+    pub fn close_replacement_span(&mut self, modifier: ModifierRef, card: CardRef) {
+        assert_eq!(self.replacement_stack.last(), Some(&(modifier, card)));
+        self.replacement_stack.pop();
+    }
+    pub fn is_replacing(&self, modifier: ModifierRef, card: CardRef) -> bool {
+        self.replacement_stack.contains(&(modifier, card))
+    }
 
     pub fn current_card(&self) -> Option<CardRef> {
         self.origin_stack.last().copied().flatten()
@@ -339,7 +753,30 @@ impl EventSpan {
 }
 
 impl GameDirector {
-    pub async fn send_event(&mut self, mut event: Event) -> Result<Event, GameOutcome> {
+    pub async fn send_event(&mut self, event: Event) -> Result<Event, GameOutcome> {
+        let Some(tracer) = self.game.tracer.clone() else {
+            return self.perform_event(event).await;
+        };
+
+        let sent = event.clone();
+        tracer.open(TraceNode::new(
+            TraceKind::Event,
+            format!("{sent:?}"),
+            None,
+            &self.game.event_span,
+        ));
+        let event = self.perform_event(event).await;
+        // the event could have been modified or replaced
+        tracer.close(match &event {
+            Ok(event) if *event != sent => Some(format!("{event:?}")),
+            Ok(_) => None,
+            Err(outcome) => Some(format!("game over: {outcome:?}")),
+        });
+
+        event
+    }
+
+    async fn perform_event(&mut self, mut event: Event) -> Result<Event, GameOutcome> {
         // keep track of current event
         self.game.event_span.open_event_span(event.clone());
 
@@ -347,6 +784,14 @@ impl GameDirector {
         let before = TriggeredEvent::Before(&event);
         Box::pin(self.evaluate_triggers(before)).await?;
 
+        // prevent or replace the event, with replacement modifiers
+        if let AdjustEventOutcome::PreventEvent = Box::pin(self.replace_event(&mut event)).await? {
+            // done with the current event
+            // unchecked should be fine, can't check because the event changed in replace
+            self.game.event_span.close_event_span_unchecked();
+            return Ok(event);
+        }
+
         // change the event before it happens, with modifiers from triggers
         if let AdjustEventOutcome::PreventEvent = Box::pin(event.adjust_event(self)).await? {
             // done with the current event
@@ -361,18 +806,21 @@ impl GameDirector {
         );
         // send the event before evaluating. it will prepare the client to receive the related events
         // TODO sanitize the event before sending it to each player
-        self.client(self.game.active_player())
-            .0
-            .send(ClientReceive::Event(event.clone()))
-            .await
-            .unwrap();
-        self.client(self.game.active_player().opponent())
-            .0
-            .send(ClientReceive::Event(event.clone()))
-            .await
-            .unwrap();
+        let active_player = self.game.active_player();
+        self.send_to(active_player, ClientReceive::Event(event.clone()))
+            .await?;
+        self.send_to(
+            active_player.opponent(),
+            ClientReceive::Event(event.clone()),
+        )
+        .await?;
+        #[cfg(feature = "spectators")]
+        self.spectate_event(&event);
         // perform the modification to the game state
-        Box::pin(event.evaluate_event(self)).await?;
+        let evaluated = Box::pin(event.evaluate_event(self)).await;
+        #[cfg(feature = "spectators")]
+        self.spectate_reveals();
+        evaluated?;
 
         // trigger after effects
         let after = TriggeredEvent::After(&event);
@@ -394,86 +842,142 @@ impl GameDirector {
             self.game.event_span.current_card()
         );
 
-        let current_player = self.game.active_player();
-        let current_player_cards_on_stage = self
-            .board(current_player)
-            .oshi()
-            .into_iter()
-            .chain(self.board(current_player).stage())
-            .map(|c| (current_player, c));
-        let opponent = self.game.active_player().opponent();
-        let opponent_cards_on_stage = self
-            .board(opponent)
-            .oshi()
-            .into_iter()
-            .chain(self.board(opponent).stage())
-            .map(|c| (opponent, c));
-
         // current player activate first
-        let all_cards_on_stage = current_player_cards_on_stage
-            .chain(opponent_cards_on_stage)
-            .flat_map(|(p, c)| Some(c).into_iter().chain(self.board(p).attachments(c)))
-            .collect_vec();
-
-        for card in all_cards_on_stage {
-            let mut oshi_skill = None;
-            let mut member_ability = None;
-            let mut support_ability = None;
-            match self.lookup_card(card) {
-                Card::OshiHoloMember(o) => {
-                    for (idx, skill) in o.skills.iter().enumerate() {
-                        // FIXME need to use the usual check, but with event?
-                        if skill.triggers.iter().any(|t| t.should_activate(&trigger))
-                            && o.can_use_skill(card, idx, self, false)
-                        {
-                            debug!("ACTIVATE SKILL? = {skill:?}");
-                            oshi_skill = Some(idx);
+        let current_player = self.game.active_player();
+        for player in [current_player, current_player.opponent()] {
+            let cards_on_stage = self
+                .board(player)
+                .oshi()
+                .into_iter()
+                .chain(self.board(player).stage())
+                .flat_map(|c| Some(c).into_iter().chain(self.board(player).attachments(c)))
+                .collect_vec();
+
+            // gather everything that triggers
+            let mut pending = Vec::new();
+            for card in cards_on_stage {
+                match self.lookup_card(card) {
+                    Card::OshiHoloMember(o) => {
+                        for (idx, skill) in o.skills.iter().enumerate() {
+                            // FIXME need to use the usual check, but with event?
+                            if skill
+                                .triggers
+                                .iter()
+                                .any(|t| t.should_activate(card, &trigger, &self.game))
+                                && o.can_use_skill(card, idx, self, false)
+                            {
+                                debug!("ACTIVATE SKILL? = {skill:?}");
+                                pending.push(PendingTrigger::OshiSkill(card, idx));
+                            }
                         }
                     }
-                }
-                Card::HoloMember(m) => {
-                    for (idx, ability) in m.abilities.iter().enumerate() {
-                        // FIXME need to use the usual check, but with event?
-                        if ability.should_activate(card, &trigger)
-                            && m.can_use_ability(card, idx, self, false)
-                        {
-                            debug!("ACTIVATE ABILITY = {ability:?}");
-                            member_ability = Some(idx);
+                    Card::HoloMember(m) => {
+                        for (idx, ability) in m.abilities.iter().enumerate() {
+                            // FIXME need to use the usual check, but with event?
+                            if ability.should_activate(card, &trigger, &self.game)
+                                && m.can_use_ability(card, idx, self, false)
+                            {
+                                debug!("ACTIVATE ABILITY = {ability:?}");
+                                pending.push(PendingTrigger::HoloMemberAbility(card, idx));
+                            }
                         }
                     }
-                }
-                Card::Support(s) => {
-                    for (idx, effect) in s.effects.iter().enumerate() {
-                        // FIXME need to use the usual check, but with event?
-                        if effect.triggers.iter().any(|t| t.should_activate(&trigger))
-                            && s.can_use_effect(card, idx, self, false)
-                        {
-                            debug!("ACTIVATE SUPPORT? = {effect:?}");
-                            support_ability = Some(idx);
+                    Card::Support(s) => {
+                        for (idx, effect) in s.effects.iter().enumerate() {
+                            // FIXME need to use the usual check, but with event?
+                            if effect
+                                .triggers
+                                .iter()
+                                .any(|t| t.should_activate(card, &trigger, &self.game))
+                                && s.can_use_effect(card, idx, self, false)
+                            {
+                                debug!("ACTIVATE SUPPORT? = {effect:?}");
+                                pending.push(PendingTrigger::SupportEffect(card, idx));
+                            }
                         }
                     }
+                    Card::Cheer(_) => {} // cheers do not have triggers yet
                 }
-                Card::Cheer(_) => {} // cheers do not have triggers yet
+            }
+
+            if pending.is_empty() {
+                continue;
+            }
+
+            // the player chooses the order of simultaneous effects
+            if pending.len() > 1 {
+                pending = self.prompt_for_trigger_order(player, pending).await?;
+                self.send_event(
+                    ResolveTriggers {
+                        player,
+                        triggers: pending.clone(),
+                    }
+                    .into(),
+                )
+                .await?;
             }
 
             // activate skill or ability
-            self.game.event_span.open_untracked_span();
-            if let Some(idx) = oshi_skill {
-                // prompt for yes / no, optional activation
-                let activate = self
-                    .prompt_for_optional_activate(self.player_for_card(card))
-                    .await;
-                if activate {
-                    self.activate_oshi_skill(card, idx, true).await?;
-                }
+            self.game.event_span.open_trigger_span();
+            for pending_trigger in pending {
+                self.resolve_pending_trigger(pending_trigger).await?;
+            }
+            self.game.event_span.close_trigger_span();
+        }
+
+        Ok(GameContinue)
+    }
+
+    async fn resolve_pending_trigger(&mut self, pending_trigger: PendingTrigger) -> GameResult {
+        // a previous effect could have prevented this one
+        let can_activate = match pending_trigger {
+            PendingTrigger::OshiSkill(card, idx) => self
+                .lookup_oshi(card)
+                .is_some_and(|o| o.can_use_skill(card, idx, self, true)),
+            PendingTrigger::HoloMemberAbility(card, idx) => self
+                .lookup_holo_member(card)
+                .is_some_and(|m| m.can_use_ability(card, idx, self, true)),
+            PendingTrigger::SupportEffect(card, idx) => self
+                .lookup_support(card)
+                .is_some_and(|s| s.can_use_effect(card, idx, self, true)),
+        };
+        if !can_activate {
+            debug!("CANNOT ACTIVATE ANYMORE = {pending_trigger:?}");
+            return Ok(GameContinue);
+        }
+
+        // prompt for yes / no, optional activation
+        if pending_trigger.is_optional() {
+            let player = self.player_for_card(pending_trigger.card());
+            if !self.prompt_for_optional_activate(player).await? {
+                return Ok(GameContinue);
             }
-            if let Some(idx) = member_ability {
+        }
+
+        match pending_trigger {
+            PendingTrigger::OshiSkill(card, idx) => {
+                self.activate_oshi_skill(card, idx, true).await?;
+            }
+            PendingTrigger::HoloMemberAbility(card, idx) => {
                 self.activate_holo_member_ability(card, idx, true).await?;
             }
-            if let Some(idx) = support_ability {
+            PendingTrigger::SupportEffect(card, idx) => {
                 self.activate_support_effect(card, idx, true).await?;
             }
-            self.game.event_span.close_untracked_span();
+        }
+
+        Ok(GameContinue)
+    }
+
+    /// the players detect a desync with their own state
+    pub async fn send_checksums(&mut self) -> GameResult {
+        for player in [Player::One, Player::Two] {
+            let checksum = StateChecksum {
+                player,
+                checksum: self.game.state.checksum(player),
+            };
+            self.send_to(player, ClientReceive::StateChecksum(checksum))
+                .await?;
         }
 
         Ok(GameContinue)
@@ -742,6 +1246,7 @@ impl GameDirector {
     }
 
     pub async fn send_to_collab(&mut self, card: CardRef) -> GameResult {
+        self.game.event_span.open_card_span(card);
         self.send_event(
             Collab {
                 card,
@@ -750,13 +1255,30 @@ impl GameDirector {
             .into(),
         )
         .await?;
+        self.game.event_span.close_card_span(card);
 
         Ok(GameContinue)
     }
 
     pub async fn baton_pass(&mut self, from_card: CardRef, to_card: CardRef) -> GameResult {
+        self.game.event_span.open_card_span(from_card);
         self.send_event(BatonPass { from_card, to_card }.into())
             .await?;
+        self.game.event_span.close_card_span(from_card);
+
+        Ok(GameContinue)
+    }
+
+    pub async fn swap_center_stage(&mut self, back_card: CardRef) -> GameResult {
+        let player = self.player_for_card(back_card);
+        if self.board(player).find_card_zone(back_card) != Some(Zone::BackStage) {
+            return Ok(GameContinue);
+        }
+
+        // the center member goes to the back, before the back member can take its place
+        let center = self.board(player).center_stage().collect_vec();
+        self.send_to_back_stage(center).await?;
+        self.send_to_center_stage(back_card).await?;
 
         Ok(GameContinue)
     }
@@ -779,6 +1301,20 @@ impl GameDirector {
         Ok(GameContinue)
     }
 
+    pub async fn return_to_hand(&mut self, cards: Vec<CardRef>) -> GameResult {
+        // the bloom stack goes back with the member, the other attachments are archived
+        let stack = cards
+            .iter()
+            .copied()
+            .flat_map(|c| self.game.attachments(c).collect_vec())
+            .filter(|a| self.game.is_holo_member(*a))
+            .collect_vec();
+        let cards = cards.into_iter().chain(stack).collect_vec();
+        self.send_to_zone(cards, Zone::Hand).await?;
+
+        Ok(GameContinue)
+    }
+
     pub async fn send_to_zone(&mut self, cards: Vec<CardRef>, zone: Zone) -> GameResult {
         self.send_to_zone_with_location(cards, zone, zone.default_add_location())
             .await?;
@@ -825,7 +1361,7 @@ impl GameDirector {
             // TODO package with prompt
             // info!("lost a life: {}", CardDisplay::new(cheer, self));
 
-            if let Some(mem) = self.prompt_for_cheer(player).await {
+            if let Some(mem) = self.prompt_for_cheer(player).await? {
                 self.send_event(
                     AttachToCard {
                         attachments: vec![cheer],
@@ -1037,6 +1573,117 @@ impl GameDirector {
         Ok(GameContinue)
     }
 
+    pub async fn add_counters_to_many_cards(
+        &mut self,
+        cards: Vec<CardRef>,
+        name: String,
+        amount: usize,
+        life_time: LifeTime,
+    ) -> GameResult {
+        if cards.is_empty() || amount < 1 {
+            return Ok(GameContinue);
+        }
+
+        self.send_event(
+            AddCardCounters {
+                cards,
+                name,
+                amount,
+                life_time,
+            }
+            .into(),
+        )
+        .await?;
+
+        Ok(GameContinue)
+    }
+
+    pub async fn remove_counters_from_many_cards(
+        &mut self,
+        cards: Vec<CardRef>,
+        name: String,
+        amount: usize,
+    ) -> GameResult {
+        let cards = cards
+            .into_iter()
+            .filter(|c| self.has_counter(*c, &name))
+            .collect_vec();
+
+        if cards.is_empty() || amount < 1 {
+            return Ok(GameContinue);
+        }
+
+        self.send_event(
+            RemoveCardCounters {
+                cards,
+                name,
+                amount,
+            }
+            .into(),
+        )
+        .await?;
+
+        Ok(GameContinue)
+    }
+
+    /// clears the named counter, or all of them
+    pub async fn clear_counters_from_many_cards(
+        &mut self,
+        cards: Vec<CardRef>,
+        name: Option<String>,
+    ) -> GameResult {
+        let cards = cards
+            .into_iter()
+            .filter(|c| match &name {
+                Some(name) => self
+                    .game
+                    .state
+                    .card_counters
+                    .get(c)
+                    .is_some_and(|cs| cs.contains_key(name)),
+                None => self.game.state.card_counters.contains_key(c),
+            })
+            .collect_vec();
+
+        if cards.is_empty() {
+            return Ok(GameContinue);
+        }
+
+        self.send_event(ClearCardCounters { cards, name }.into())
+            .await?;
+
+        Ok(GameContinue)
+    }
+
+    /// clears the named usage, or all of them
+    pub async fn clear_usages_from_many_cards(
+        &mut self,
+        cards: Vec<CardRef>,
+        usage: Option<String>,
+    ) -> GameResult {
+        let cards = cards
+            .into_iter()
+            .filter(|c| match &usage {
+                Some(usage) => self
+                    .game
+                    .state
+                    .card_usages
+                    .get(c)
+                    .is_some_and(|us| us.contains_key(usage)),
+                None => self.game.state.card_usages.contains_key(c),
+            })
+            .collect_vec();
+
+        if cards.is_empty() {
+            return Ok(GameContinue);
+        }
+
+        self.send_event(ClearCardUsages { cards, usage }.into())
+            .await?;
+
+        Ok(GameContinue)
+    }
+
     pub async fn draw_from_main_deck(&mut self, player: Player, amount: usize) -> GameResult {
         if amount < 1 {
             return Ok(GameContinue);
@@ -1058,6 +1705,7 @@ impl GameDirector {
     }
 
     pub async fn bloom_holo_member(&mut self, bloom: CardRef, target: CardRef) -> GameResult {
+        self.game.event_span.open_card_span(bloom);
         self.send_event(
             Bloom {
                 from_card: bloom,
@@ -1066,6 +1714,7 @@ impl GameDirector {
             .into(),
         )
         .await?;
+        self.game.event_span.close_card_span(bloom);
 
         Ok(GameContinue)
     }
@@ -1244,8 +1893,8 @@ impl EvaluateEvent for Setup {
         let first_player;
         loop {
             info!("prompt rps");
-            let rps_1 = game.prompt_for_rps(Player::One).await;
-            let rps_2 = game.prompt_for_rps(Player::Two).await;
+            let rps_1 = game.prompt_for_rps(Player::One).await?;
+            let rps_2 = game.prompt_for_rps(Player::Two).await?;
             use super::gameplay::RpsOutcome;
             match rps_1.vs(rps_2) {
                 RpsOutcome::Win => {
@@ -1285,26 +1934,26 @@ impl EvaluateEvent for Setup {
         // TODO member hide
         // TODO request (intent)
         info!("prompt debut 1");
-        let debut_1 = game.prompt_for_first_debut(first_player).await;
+        let debut_1 = game.prompt_for_first_debut(first_player).await?;
         game.send_to_center_stage(debut_1).await?;
 
         // TODO member hide
         // TODO request (intent)
         info!("prompt debut 2");
-        let debut_2 = game.prompt_for_first_debut(second_player).await;
+        let debut_2 = game.prompt_for_first_debut(second_player).await?;
         game.send_to_center_stage(debut_2).await?;
 
         // - place other debut / spot members back stage
         // TODO member hide
         // TODO request (intent)
         info!("prompt other debut 1");
-        let other_debut_1: Vec<_> = game.prompt_for_first_back_stage(first_player).await;
+        let other_debut_1: Vec<_> = game.prompt_for_first_back_stage(first_player).await?;
         game.send_to_back_stage(other_debut_1).await?;
 
         // TODO member hide
         // TODO request (intent)
         info!("prompt other debut 2");
-        let other_debut_2: Vec<_> = game.prompt_for_first_back_stage(second_player).await;
+        let other_debut_2: Vec<_> = game.prompt_for_first_back_stage(second_player).await?;
         game.send_to_back_stage(other_debut_2).await?;
 
         // - reveal face down oshi and members
@@ -1457,8 +2106,13 @@ pub struct Reveal {
     pub cards: Vec<(CardRef, CardNumber)>,
 }
 impl EvaluateEvent for Reveal {
-    fn apply_state_change(&self, _state: &mut GameState) {
-        // no state change
+    fn apply_state_change(&self, state: &mut GameState) {
+        // the cards could be hidden from this view of the game
+        for (card, card_number) in &self.cards {
+            state
+                .card_map
+                .insert(*card, (self.player, card_number.clone()));
+        }
     }
 
     async fn evaluate_event(&self, _game: &mut GameDirector) -> GameResult {
@@ -1518,6 +2172,7 @@ impl EvaluateEvent for StartTurn {
         state.turn_number = self.turn_number;
 
         state.start_turn_modifiers(self.active_player);
+        state.start_turn_counters(self.active_player);
     }
 
     async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
@@ -1855,6 +2510,156 @@ impl EvaluateEvent for LookAndSelect {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct AddCardCounters {
+    pub cards: Vec<CardRef>,
+    pub name: String,
+    pub amount: usize,
+    pub life_time: LifeTime,
+}
+impl EvaluateEvent for AddCardCounters {
+    fn apply_state_change(&self, state: &mut GameState) {
+        for card in self.cards.iter().copied() {
+            *state
+                .card_counters
+                .entry(card)
+                .or_default()
+                .entry(self.name.clone())
+                .or_default() += self.amount;
+            // the latest life time is kept
+            state
+                .card_counter_life_times
+                .entry(card)
+                .or_default()
+                .insert(self.name.clone(), self.life_time);
+        }
+    }
+
+    async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
+        if self.cards.is_empty() || self.amount < 1 {
+            return Ok(GameContinue);
+        }
+
+        self.apply_state_change(&mut game.game.state);
+
+        Ok(GameContinue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct RemoveCardCounters {
+    pub cards: Vec<CardRef>,
+    pub name: String,
+    pub amount: usize,
+}
+impl EvaluateEvent for RemoveCardCounters {
+    fn apply_state_change(&self, state: &mut GameState) {
+        for card in self.cards.iter() {
+            let Some(counters) = state.card_counters.get_mut(card) else {
+                continue;
+            };
+            let Some(counter) = counters.get_mut(&self.name) else {
+                continue;
+            };
+            *counter = counter.saturating_sub(self.amount);
+
+            // an empty counter is removed
+            if *counter == 0 {
+                state.remove_counter(*card, &self.name);
+            }
+        }
+    }
+
+    async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
+        if self.cards.is_empty() || self.amount < 1 {
+            return Ok(GameContinue);
+        }
+
+        self.apply_state_change(&mut game.game.state);
+
+        Ok(GameContinue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct ClearCardCounters {
+    pub cards: Vec<CardRef>,
+    /// all the counters, when there is no name
+    pub name: Option<String>,
+}
+impl EvaluateEvent for ClearCardCounters {
+    fn apply_state_change(&self, state: &mut GameState) {
+        for card in self.cards.iter() {
+            if let Some(name) = &self.name {
+                state.remove_counter(*card, name);
+            } else {
+                state.card_counters.remove_entry(card);
+                state.card_counter_life_times.remove_entry(card);
+            }
+        }
+    }
+
+    async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
+        if self.cards.is_empty() {
+            return Ok(GameContinue);
+        }
+
+        self.apply_state_change(&mut game.game.state);
+
+        Ok(GameContinue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct AddCardUsage {
+    pub card: CardRef,
+    pub usage: String,
+    pub life_time: LifeTime,
+}
+impl EvaluateEvent for AddCardUsage {
+    fn apply_state_change(&self, state: &mut GameState) {
+        state
+            .card_usages
+            .entry(self.card)
+            .or_default()
+            .insert(self.usage.clone(), self.life_time);
+    }
+
+    async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
+        self.apply_state_change(&mut game.game.state);
+
+        Ok(GameContinue)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct ClearCardUsages {
+    pub cards: Vec<CardRef>,
+    /// all the usages, when there is no usage
+    pub usage: Option<String>,
+}
+impl EvaluateEvent for ClearCardUsages {
+    fn apply_state_change(&self, state: &mut GameState) {
+        for card in self.cards.iter() {
+            if let Some(usage) = &self.usage {
+                state.remove_usage(*card, usage);
+            } else {
+                state.card_usages.remove_entry(card);
+            }
+        }
+    }
+
+    async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
+        if self.cards.is_empty() {
+            return Ok(GameContinue);
+        }
+
+        self.apply_state_change(&mut game.game.state);
+
+        Ok(GameContinue)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub struct SendToZone {
     pub cards: Vec<CardRef>,
@@ -1912,6 +2717,12 @@ impl EvaluateEvent for SendToZone {
             game.clear_all_damage_markers_from_many_cards(self.cards.clone())
                 .await?;
 
+            game.clear_counters_from_many_cards(self.cards.clone(), None)
+                .await?;
+
+            game.clear_usages_from_many_cards(self.cards.clone(), None)
+                .await?;
+
             let attachments = self
                 .cards
                 .iter()
@@ -2029,7 +2840,9 @@ impl EvaluateEvent for Collab {
         if game.board(player).get_zone(Zone::Collab).count() > 0 {
             panic!("collab is already occupied");
         }
-        if game.has_modifier(self.card, Resting) {
+        if game.has_modifier(self.card, Resting)
+            && !game.has_modifier(self.card, CollabWhileResting)
+        {
             panic!("cannot collab a resting member");
         }
         if game.has_modifier(self.card, PreventCollab) {
@@ -2101,6 +2914,7 @@ impl EvaluateEvent for Bloom {
             .promote_attachment(self.from_card, self.to_card);
         state.promote_modifiers(self.from_card, self.to_card);
         state.promote_damage_markers(self.from_card, self.to_card);
+        state.promote_counters(self.from_card, self.to_card);
     }
 
     async fn evaluate_event(&self, game: &mut GameDirector) -> GameResult {
@@ -2157,7 +2971,7 @@ impl EvaluateEvent for BatonPass {
         // TODO request (intent) select attached cheers
         let cheers = game
             .prompt_for_baton_pass(player, self.from_card, mem.baton_pass_cost)
-            .await;
+            .await?;
         game.send_to_archive(cheers).await?;
 
         // send the center member to the back
@@ -2196,6 +3010,7 @@ impl EvaluateEvent for ActivateSupportCard {
 
         let limited_use = sup.limited;
         let effect = sup.effects[self.effect_idx].effect.clone();
+        let limit = sup.effects[self.effect_idx].limit;
 
         if !sup.can_use_support(self.card, self.effect_idx, game) {
             unreachable!("support should not be an option, if it's not allowed")
@@ -2206,6 +3021,10 @@ impl EvaluateEvent for ActivateSupportCard {
         game.send_to_zone(vec![self.card], Zone::ActivateSupport)
             .await?;
 
+        // once per turn / once per game
+        let usage = format!("support_effect {}", self.effect_idx);
+        game.record_usage(self.card, &usage, limit).await?;
+
         // activate the support card
         effect
             .ctx()
@@ -2251,6 +3070,13 @@ impl EvaluateEvent for ActivateSupportEffect {
         }
 
         let effect = support.effects[self.effect_idx].effect.clone();
+        let limit = support.effects[self.effect_idx].limit;
+
+        // once per turn / once per game
+        game.game.event_span.open_untracked_span();
+        let usage = format!("support_effect {}", self.effect_idx);
+        game.record_usage(self.card, &usage, limit).await?;
+        game.game.event_span.close_untracked_span();
 
         effect
             .ctx()
@@ -2291,16 +3117,17 @@ impl EvaluateEvent for ActivateOshiSkill {
         let skill = &oshi.skills[self.skill_idx];
         let cost = skill.cost as usize;
         let effect = skill.effect.clone();
-        let prevent_life_time = match skill.kind {
-            OshiSkillKind::Normal => LifeTime::ThisTurn,
-            OshiSkillKind::Special => LifeTime::ThisGame,
-        };
+        let limit = skill.limit;
 
         // pay the cost of the oshi skill
         // TODO could have a buff that could pay for the skill
         game.game.event_span.open_untracked_span();
         let player = game.player_for_card(self.card);
         game.send_holo_power_to_archive(player, cost).await?;
+
+        //   - once per turn / once per game
+        let usage = format!("oshi_skill {}", self.skill_idx);
+        game.record_usage(self.card, &usage, limit).await?;
         game.game.event_span.close_untracked_span();
 
         effect
@@ -2310,16 +3137,6 @@ impl EvaluateEvent for ActivateOshiSkill {
             .evaluate_mut(game)
             .await?;
 
-        //   - once per turn / once per game
-        game.game.event_span.open_untracked_span();
-        game.add_modifier(
-            self.card,
-            PreventOshiSkill(self.skill_idx),
-            prevent_life_time,
-        )
-        .await?;
-        game.game.event_span.close_untracked_span();
-
         Ok(GameContinue)
     }
 }
@@ -2348,6 +3165,13 @@ impl EvaluateEvent for ActivateHoloMemberAbility {
 
         let ability = &mem.abilities[self.ability_idx];
         let effect = ability.effect.clone();
+        let limit = ability.limit;
+
+        // once per turn / once per game
+        game.game.event_span.open_untracked_span();
+        let usage = format!("ability {}", self.ability_idx);
+        game.record_usage(self.card, &usage, limit).await?;
+        game.game.event_span.close_untracked_span();
 
         effect
             .ctx()
@@ -2458,10 +3282,30 @@ impl EvaluateEvent for PerformArt {
     }
 }
 
+/// marker event before resolving simultaneous triggered effects, in order
+#[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
+pub struct ResolveTriggers {
+    pub player: Player,
+    pub triggers: Vec<PendingTrigger>,
+}
+impl EvaluateEvent for ResolveTriggers {
+    fn apply_state_change(&self, _state: &mut GameState) {
+        // no state change
+    }
+
+    async fn evaluate_event(&self, _game: &mut GameDirector) -> GameResult {
+        // no state change
+        Ok(GameContinue)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, GetSize, Encode, Decode)]
 pub struct WaitingForPlayerIntent {
     pub player: Player,
-    // reason?
+    /// the time left on the clock of the player, with timing rules
+    pub clock: Option<Duration>,
+    /// the time to respond, before the default response is used
+    pub deadline: Option<Duration>,
 }
 impl EvaluateEvent for WaitingForPlayerIntent {
     fn apply_state_change(&self, _state: &mut GameState) {
@@ -2469,8 +3313,8 @@ impl EvaluateEvent for WaitingForPlayerIntent {
     }
 
     async fn evaluate_event(&self, _game: &mut GameDirector) -> GameResult {
-        // TODO implement
-        unimplemented!()
+        // no effect
+        Ok(GameContinue)
     }
 }
 // Card effect events
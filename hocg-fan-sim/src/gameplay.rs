@@ -2,31 +2,46 @@ use std::collections::VecDeque;
 use std::fmt::Display;
 use std::iter;
 use std::num::NonZeroU16;
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
 
 use crate::card_effects::evaluate::{EvaluateContext, EvaluateEffect};
+use crate::card_effects::trace::EvaluationTracer;
 use crate::card_effects::{Condition, Trigger};
-use crate::events::{ClientReceive, ClientSend, EventSpan, IntentRequest, IntentResponse};
-use crate::library::{library, GlobalLibrary, Loadout};
+use crate::events::{
+    ClientReceive, ClientSend, EvaluateEvent, Event, EventSpan, IntentRequest, IntentResponse,
+    Reveal, SyncGameState,
+};
+use crate::library::{library, stable_hash, GlobalLibrary, Loadout};
+#[cfg(feature = "spectators")]
+use crate::spectators::Spectators;
+use crate::timing::Clocks;
+use crate::transport::{PlayerTransport, TransportError};
 
 use super::cards::*;
 use super::modifiers::*;
-use async_channel::{Receiver, Sender};
 use async_rwlock::RwLockReadGuard;
-use bincode::{Decode, Encode};
+use bincode::{config, Decode, Encode};
 use debug_ignore::DebugIgnore;
 use get_size::GetSize;
 use iter_tools::Itertools;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::RngCore;
+use rand::{RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 use ModifierKind::*;
 
 pub const STARTING_HAND_SIZE: usize = 7;
 pub const MAX_MEMBERS_ON_STAGE: usize = 6;
+/// the player forfeits the game after that many invalid responses to the same request
+pub const MAX_INVALID_RESPONSES: usize = 3;
 
 pub static PRIVATE_CARD: CardRef = CardRef(NonZeroU16::MAX);
+/// in a public view of the game, for the cards that are not revealed
+pub static HIDDEN_CARD_NUMBER: &str = "";
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, GetSize, Encode, Decode)]
 pub struct CardRef(pub(crate) NonZeroU16);
@@ -101,23 +116,40 @@ pub enum GameOverReason {
     EmptyStage,
     EmptyLife,
     Draw,
+    Disconnect,
+    Timeout,
+    Concede,
+    /// after [`MAX_INVALID_RESPONSES`] to the same request
+    InvalidResponses,
+    /// the game is a draw after [`GameDirector::max_turns`]
+    TurnLimit,
 }
 
 #[derive(Debug)]
 pub struct GameDirector {
-    pub rng: DebugIgnore<Box<dyn RngCore>>,
+    pub rng: DebugIgnore<Box<dyn RngCore + Send>>,
     pub game: Game,
     pub next_modifier_ref: u16,
-    pub player_1_channels: (Sender<ClientReceive>, Receiver<ClientSend>),
-    pub player_2_channels: (Sender<ClientReceive>, Receiver<ClientSend>),
+    pub player_1_transport: DebugIgnore<Box<dyn PlayerTransport>>,
+    pub player_2_transport: DebugIgnore<Box<dyn PlayerTransport>>,
+    pub transport_errors: Vec<(Player, TransportError)>,
+    /// opt-in, public feed of the events
+    #[cfg(feature = "spectators")]
+    pub spectators: Option<Spectators>,
+    /// opt-in, see [`crate::timing::TimingRules`]
+    pub clocks: Option<Clocks>,
+    /// opt-in, the game is a draw after that many turns
+    pub max_turns: Option<u8>,
+    /// after a sync of their view, the players only know the cards that were visible
+    known_cards: HashMap<Player, HashSet<CardRef>>,
 }
 
 impl GameDirector {
     pub async fn setup(
         player_1: &Loadout,
         player_2: &Loadout,
-        player_1_client: (Sender<ClientReceive>, Receiver<ClientSend>),
-        player_2_client: (Sender<ClientReceive>, Receiver<ClientSend>),
+        player_1_transport: impl PlayerTransport + 'static,
+        player_2_transport: impl PlayerTransport + 'static,
     ) -> GameDirector {
         let mut next_p1_card_ref = 1;
         let mut next_p2_card_ref = 1;
@@ -125,6 +157,7 @@ impl GameDirector {
         let game = Game {
             library: Some(library().await),
             event_span: EventSpan::new(),
+            tracer: None,
             state: GameState {
                 game_outcome: None,
                 player_1: GameBoard::setup(
@@ -146,20 +179,29 @@ impl GameDirector {
                 zone_modifiers: HashMap::new(),
                 card_modifiers: HashMap::new(),
                 card_damage_markers: HashMap::new(),
+                card_counters: HashMap::new(),
+                card_counter_life_times: HashMap::new(),
+                card_usages: HashMap::new(),
             },
         };
         GameDirector {
-            rng: DebugIgnore(Box::new(rand::thread_rng())),
+            rng: DebugIgnore(Box::new(StdRng::from_entropy())),
             game,
             next_modifier_ref: 1,
-            player_1_channels: player_1_client,
-            player_2_channels: player_2_client,
+            player_1_transport: DebugIgnore(Box::new(player_1_transport)),
+            player_2_transport: DebugIgnore(Box::new(player_2_transport)),
+            transport_errors: Vec::new(),
+            #[cfg(feature = "spectators")]
+            spectators: None,
+            clocks: None,
+            max_turns: None,
+            known_cards: HashMap::new(),
         }
     }
-    pub async fn with_game_state<R: RngCore + 'static>(
+    pub async fn with_game_state<R: RngCore + Send + 'static>(
         state: GameState,
-        player_1_client: (Sender<ClientReceive>, Receiver<ClientSend>),
-        player_2_client: (Sender<ClientReceive>, Receiver<ClientSend>),
+        player_1_transport: impl PlayerTransport + 'static,
+        player_2_transport: impl PlayerTransport + 'static,
         rng: R,
     ) -> Self {
         GameDirector {
@@ -167,22 +209,193 @@ impl GameDirector {
             game: Game {
                 library: Some(library().await),
                 event_span: EventSpan::new(),
+                tracer: None,
                 state,
             },
             next_modifier_ref: 1,
-            player_1_channels: player_1_client,
-            player_2_channels: player_2_client,
+            player_1_transport: DebugIgnore(Box::new(player_1_transport)),
+            player_2_transport: DebugIgnore(Box::new(player_2_transport)),
+            transport_errors: Vec::new(),
+            #[cfg(feature = "spectators")]
+            spectators: None,
+            clocks: None,
+            max_turns: None,
+            known_cards: HashMap::new(),
         }
     }
 
-    pub fn client(&mut self, player: Player) -> &mut (Sender<ClientReceive>, Receiver<ClientSend>) {
+    pub fn transport(&mut self, player: Player) -> &mut Box<dyn PlayerTransport> {
         match player {
-            Player::One => &mut self.player_1_channels,
-            Player::Two => &mut self.player_2_channels,
+            Player::One => &mut self.player_1_transport,
+            Player::Two => &mut self.player_2_transport,
             _ => unreachable!("both players cannot be active at the same time"),
         }
     }
 
+    fn is_disconnected(&self, player: Player) -> bool {
+        self.transport_errors.iter().any(|(p, _)| *p == player)
+    }
+
+    /// the player that cannot be reached loses the game
+    async fn transport_failed(&mut self, player: Player, error: TransportError) -> GameOutcome {
+        if !self.is_disconnected(player) {
+            error!("transport failed for {player:?}: {error}");
+            self.transport_errors.push((player, error));
+        }
+
+        self.player_lost(player, GameOverReason::Disconnect).await
+    }
+
+    /// the player loses outside of their turn, from a disconnection or a timeout
+    pub(crate) async fn player_lost(
+        &mut self,
+        player: Player,
+        reason: GameOverReason,
+    ) -> GameOutcome {
+        if let Some(outcome) = self.game.game_outcome() {
+            return outcome;
+        }
+        self.game.state.active_player = player;
+        match Box::pin(self.lose_game(reason)).await {
+            Ok(_) => unreachable!("the game should be over"),
+            Err(outcome) => outcome,
+        }
+    }
+
+    pub async fn send_to(&mut self, player: Player, message: ClientReceive) -> GameResult {
+        // the game continues without the player, until it's over
+        if self.is_disconnected(player) {
+            return Ok(GameContinue);
+        }
+        for message in self.with_reveals(player, message) {
+            if let Err(error) = self.transport(player).send(message).await {
+                return Err(self.transport_failed(player, error).await);
+            }
+        }
+        Ok(GameContinue)
+    }
+
+    /// after a sync of the view of the player, the cards are revealed when the player can see them
+    fn with_reveals(&mut self, player: Player, message: ClientReceive) -> Vec<ClientReceive> {
+        if let ClientReceive::Event(Event::SyncGameState(_)) = message {
+            self.known_cards.remove(&player);
+        }
+        let Some(known) = self.known_cards.get_mut(&player) else {
+            return vec![message];
+        };
+        match &message {
+            // the events are sent before they are applied
+            ClientReceive::Event(event) => {
+                let mut state = self.game.state.clone();
+                event.apply_state_change(&mut state);
+                state.with_reveals(player, message, known)
+            }
+            _ => self.game.state.with_reveals(player, message, known),
+        }
+    }
+
+    pub async fn send_intent_request(
+        &mut self,
+        player: Player,
+        req: IntentRequest,
+    ) -> Result<IntentResponse, GameOutcome> {
+        let mut violations = 0;
+        loop {
+            let resp = self.request_response(player, req.clone()).await?;
+            let Err(error) = req.validate(&resp) else {
+                return Ok(resp);
+            };
+
+            warn!("invalid response from {player:?}: {error}, {resp:?}");
+            violations += 1;
+            if violations >= MAX_INVALID_RESPONSES {
+                return Err(self
+                    .player_lost(player, GameOverReason::InvalidResponses)
+                    .await);
+            }
+            self.send_to(player, ClientReceive::InvalidIntentResponse(error))
+                .await?;
+        }
+    }
+
+    async fn request_response(
+        &mut self,
+        player: Player,
+        req: IntentRequest,
+    ) -> Result<IntentResponse, GameOutcome> {
+        #[cfg(feature = "timing")]
+        if self.clocks.is_some() {
+            return self.send_timed_intent_request(player, req).await;
+        }
+        self.send_request(player, req).await?;
+        self.receive_response(player).await
+    }
+
+    pub(crate) async fn send_request(&mut self, player: Player, req: IntentRequest) -> GameResult {
+        self.send_to(player, ClientReceive::IntentRequest(req))
+            .await?;
+        if self.is_disconnected(player) {
+            return Err(self
+                .transport_failed(player, TransportError::Disconnected)
+                .await);
+        }
+        Ok(GameContinue)
+    }
+
+    pub(crate) async fn receive_response(
+        &mut self,
+        player: Player,
+    ) -> Result<IntentResponse, GameOutcome> {
+        loop {
+            let message = self.transport(player).receive().await;
+            if let Some(resp) = self.handle_message(player, message).await? {
+                return Ok(resp);
+            }
+        }
+    }
+
+    /// the responses are returned, the other messages from the player are handled here
+    pub(crate) async fn handle_message(
+        &mut self,
+        player: Player,
+        message: Result<ClientSend, TransportError>,
+    ) -> Result<Option<IntentResponse>, GameOutcome> {
+        match message {
+            // accepted at any prompt
+            Ok(ClientSend::IntentResponse(IntentResponse::Concede { .. })) => {
+                info!("{player:?} conceded");
+                Err(self.player_lost(player, GameOverReason::Concede).await)
+            }
+            Ok(ClientSend::IntentResponse(resp)) => Ok(Some(resp)),
+            Ok(ClientSend::RequestSyncGameState) => {
+                warn!("{player:?} is out of sync");
+                // only what the player can see
+                let sync = SyncGameState {
+                    state: Box::new(self.game.state.player_view(player)),
+                };
+                self.send_to(player, ClientReceive::Event(sync.into()))
+                    .await?;
+                let known = self.game.state.visible_cards(Some(player));
+                self.known_cards
+                    .insert(player, known.map(|(_, _, c)| c).collect());
+                Ok(None)
+            }
+            Ok(message) => {
+                debug!("ignored message during the game: {message:?}");
+                Ok(None)
+            }
+            Err(error) => Err(self.transport_failed(player, error).await),
+        }
+    }
+
+    /// record the evaluation of effects and events, retrieve it with [`EvaluationTracer::take_trace`]
+    pub fn enable_tracer(&mut self) -> EvaluationTracer {
+        self.game
+            .tracer
+            .get_or_insert_with(EvaluationTracer::new)
+            .clone()
+    }
+
     pub fn active_board(&self) -> &GameBoard {
         self.game.active_board()
     }
@@ -259,7 +472,7 @@ impl GameDirector {
             let voluntary = player_draw == STARTING_HAND_SIZE;
             let force_mulligan = self.need_mulligan(self.board(player));
             info!("prompt mulligan: {player:?}");
-            let mulligan = force_mulligan || voluntary && self.prompt_for_mulligan(player).await;
+            let mulligan = force_mulligan || voluntary && self.prompt_for_mulligan(player).await?;
             if !mulligan {
                 break;
             }
@@ -384,11 +597,31 @@ impl GameDirector {
             self.end_turn().await?;
         }
 
+        self.send_checksums().await?;
+
         Ok(GameContinue)
     }
 
     pub async fn start_turn(&mut self) -> GameResult {
         self.game.state.turn_number += 1;
+        if self
+            .max_turns
+            .is_some_and(|max_turns| self.game.turn_number() > max_turns)
+        {
+            info!(
+                "the game is a draw after {} turns",
+                self.game.turn_number() - 1
+            );
+            let game_outcome = GameOutcome {
+                winning_player: None,
+                reason: GameOverReason::TurnLimit,
+            };
+            self.report_game_over(game_outcome).await?;
+            return Err(game_outcome);
+        }
+        if let Some(clocks) = &mut self.clocks {
+            clocks.start_turn();
+        }
 
         info!("active player: {:?}", self.game.active_player());
         self.report_start_turn(self.game.active_player()).await?;
@@ -421,7 +654,7 @@ impl GameDirector {
             // TODO request (intent)
             let back = self
                 .prompt_for_back_stage_to_center(self.game.active_player(), false)
-                .await;
+                .await?;
             self.send_to_center_stage(back).await?;
         }
 
@@ -460,7 +693,9 @@ impl GameDirector {
             info!("{} cards in hand", self.active_board().hand().count());
 
             // TODO request (intent) main action, all possible actions
-            let action = self.prompt_for_main_action(self.game.active_player()).await;
+            let action = self
+                .prompt_for_main_action(self.game.active_player())
+                .await?;
             debug!("ACTION = {action:?}");
             match action {
                 MainStepAction::BackStageMember(card) => {
@@ -495,7 +730,7 @@ impl GameDirector {
                     // TODO request attach card target (intent)
                     let card = self
                         .prompt_for_attach(self.game.active_player(), attach, i)
-                        .await;
+                        .await?;
                     self.attach_cards_to_card(vec![attach], card).await?;
                 }
                 MainStepAction::CollabMember(card) => {
@@ -521,7 +756,7 @@ impl GameDirector {
                     // TODO request (intent) select back stage member
                     let back = self
                         .prompt_for_back_stage_to_center(self.game.active_player(), true)
-                        .await;
+                        .await?;
                     // swap members
                     self.baton_pass(center, back).await?;
                 }
@@ -575,7 +810,9 @@ impl GameDirector {
             //     }
             // }
 
-            let action = self.prompt_for_art_action(self.game.active_player()).await;
+            let action = self
+                .prompt_for_art_action(self.game.active_player())
+                .await?;
             debug!("ACTION = {action:?}");
             match action {
                 PerformanceStepAction::UseArt {
@@ -606,7 +843,7 @@ impl GameDirector {
             // TODO request (intent)
             let back = self
                 .prompt_for_back_stage_to_center(self.game.active_player(), false)
-                .await;
+                .await?;
             self.send_to_center_stage(back).await?;
         }
 
@@ -673,20 +910,20 @@ impl GameDirector {
         Ok(GameContinue)
     }
 
-    pub async fn prompt_for_rps(&mut self, player: Player) -> Rps {
+    pub async fn prompt_for_rps(&mut self, player: Player) -> Result<Rps, GameOutcome> {
         // self.prompter.prompt_choice_rps(
         //     "choose rock, paper or scissor:",
         //     vec![Rps::Rock, Rps::Paper, Rps::Scissor],
         // )
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(IntentRequest::Rps {
+        let resp = self
+            .send_intent_request(
                 player,
-                select_rps: vec![Rps::Rock, Rps::Paper, Rps::Scissor],
-            }))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                IntentRequest::Rps {
+                    player,
+                    select_rps: vec![Rps::Rock, Rps::Paper, Rps::Scissor],
+                },
+            )
+            .await?;
         let choice = match resp {
             IntentResponse::Rps {
                 player: resp_player,
@@ -695,29 +932,26 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_rps
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!([Rps::Rock, Rps::Paper, Rps::Scissor].contains(&choice));
-        choice
+        Ok(choice)
     }
 
-    pub async fn prompt_for_mulligan(&mut self, player: Player) -> bool {
+    pub async fn prompt_for_mulligan(&mut self, player: Player) -> Result<bool, GameOutcome> {
         // self.prompter
         //     .prompt_choice("do you want to mulligan?", vec!["Yes", "No"])
         //     == "Yes"
 
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(IntentRequest::Mulligan {
+        let resp = self
+            .send_intent_request(
                 player,
-                select_yes_no: vec![true, false],
-            }))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                IntentRequest::Mulligan {
+                    player,
+                    select_yes_no: vec![true, false],
+                },
+            )
+            .await?;
         let choice = match resp {
             IntentResponse::Mulligan {
                 player: resp_player,
@@ -726,16 +960,13 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_yes_no
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!([true, false].contains(&choice));
-        choice
+        Ok(choice)
     }
 
-    pub async fn prompt_for_first_debut(&mut self, player: Player) -> CardRef {
+    pub async fn prompt_for_first_debut(&mut self, player: Player) -> Result<CardRef, GameOutcome> {
         // TODO extract that filtering to a reusable function
         let hand = self.board(player).hand().collect_vec();
         let debuts: Vec<_> = hand
@@ -752,9 +983,9 @@ impl GameDirector {
         //     .prompt_choice("choose first debut:", debuts)
         //     .card
 
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::LookSelectZoneToZone {
                     player,
                     from_zone: Zone::Hand,
@@ -764,10 +995,8 @@ impl GameDirector {
                     min_amount: 1,
                     max_amount: 1,
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let card = match resp {
             IntentResponse::LookSelectZoneToZone {
                 player: resp_player,
@@ -776,16 +1005,16 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_cards.swap_remove(0)
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(debuts.contains(&card));
-        card
+        Ok(card)
     }
 
-    pub async fn prompt_for_first_back_stage(&mut self, player: Player) -> Vec<CardRef> {
+    pub async fn prompt_for_first_back_stage(
+        &mut self,
+        player: Player,
+    ) -> Result<Vec<CardRef>, GameOutcome> {
         // TODO extract that filtering to a reusable function
         let hand = self.board(player).hand().collect_vec();
         let debuts: Vec<_> = hand
@@ -809,9 +1038,9 @@ impl GameDirector {
             //     .map(|c| c.card)
             //     .collect()
 
-            self.client(player)
-                .0
-                .send(ClientReceive::IntentRequest(
+            let resp = self
+                .send_intent_request(
+                    player,
                     IntentRequest::LookSelectZoneToZone {
                         player,
                         from_zone: Zone::Hand,
@@ -821,10 +1050,8 @@ impl GameDirector {
                         min_amount: 0,
                         max_amount: MAX_MEMBERS_ON_STAGE - 1,
                     },
-                ))
-                .await
-                .unwrap();
-            let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                )
+                .await?;
             let cards = match resp {
                 IntentResponse::LookSelectZoneToZone {
                     player: resp_player,
@@ -833,15 +1060,12 @@ impl GameDirector {
                     assert_eq!(player, resp_player);
                     select_cards
                 }
-                error => {
-                    error!("unexpected response: {:?}", error);
-                    panic!("unexpected response")
-                }
+                _ => unreachable!("the response should be validated"),
             };
             assert!(cards.iter().all(|c| debuts.contains(c)));
-            cards
+            Ok(cards)
         } else {
-            vec![]
+            Ok(vec![])
         }
     }
 
@@ -849,7 +1073,7 @@ impl GameDirector {
         &mut self,
         player: Player,
         baton_pass: bool,
-    ) -> CardRef {
+    ) -> Result<CardRef, GameOutcome> {
         // TODO extract that filtering to a reusable function
         let back = self.board(player).back_stage().collect_vec();
         let mut not_resting = back
@@ -879,9 +1103,9 @@ impl GameDirector {
         //     .prompt_choice("choose send to center stage:", back)
         //     .card
 
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::LookSelectZoneToZone {
                     player,
                     from_zone: Zone::Hand,
@@ -891,10 +1115,8 @@ impl GameDirector {
                     min_amount: 1,
                     max_amount: 1,
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let mem = match resp {
             IntentResponse::LookSelectZoneToZone {
                 player: resp_player,
@@ -903,16 +1125,16 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_cards.swap_remove(0)
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(not_resting.contains(&mem));
-        mem
+        Ok(mem)
     }
 
-    pub async fn prompt_for_cheer(&mut self, player: Player) -> Option<CardRef> {
+    pub async fn prompt_for_cheer(
+        &mut self,
+        player: Player,
+    ) -> Result<Option<CardRef>, GameOutcome> {
         // TODO extract that filtering to a reusable function
         let mems: Vec<_> = self
             .board(player)
@@ -921,18 +1143,16 @@ impl GameDirector {
             .collect();
 
         if !mems.is_empty() {
-            self.client(player)
-                .0
-                .send(ClientReceive::IntentRequest(
+            let resp = self
+                .send_intent_request(
+                    player,
                     IntentRequest::SelectToAttach {
                         player,
                         zones: vec![], // TODO not sure if that's needed
                         select_cards: mems.clone(),
                     },
-                ))
-                .await
-                .unwrap();
-            let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                )
+                .await?;
             let card = match resp {
                 IntentResponse::SelectToAttach {
                     player: resp_player,
@@ -941,24 +1161,24 @@ impl GameDirector {
                     assert_eq!(player, resp_player);
                     select_card
                 }
-                error => {
-                    error!("unexpected response: {:?}", error);
-                    panic!("unexpected response")
-                }
+                _ => unreachable!("the response should be validated"),
             };
             assert!(mems.contains(&card));
-            Some(
+            Ok(Some(
                 // self.prompter
                 //     .prompt_choice("choose receive cheer:", mems)
                 //     .card,
                 card,
-            )
+            ))
         } else {
-            None
+            Ok(None)
         }
     }
 
-    pub async fn prompt_for_main_action(&mut self, player: Player) -> MainStepAction {
+    pub async fn prompt_for_main_action(
+        &mut self,
+        player: Player,
+    ) -> Result<MainStepAction, GameOutcome> {
         // actions from hand
         let mut actions: Vec<_> = self
             .board(player)
@@ -1019,7 +1239,9 @@ impl GameDirector {
             self.board(player)
                 .back_stage()
                 // check condition for collab
-                .filter(|c| !self.has_modifier(*c, Resting))
+                .filter(|c| {
+                    !self.has_modifier(*c, Resting) || self.has_modifier(*c, CollabWhileResting)
+                })
                 .filter(|c| !self.has_modifier(*c, PreventCollab))
                 .filter_map(|c| match self.lookup_card(c) {
                     Card::OshiHoloMember(_) => unreachable!("oshi cannot be in the back stage"),
@@ -1078,17 +1300,15 @@ impl GameDirector {
         //     .prompt_choice("main step action:", actions)
         //     .action;
 
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::MainStepAction {
                     player,
                     select_actions: actions.clone(),
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let select_action = match resp {
             IntentResponse::MainStepAction {
                 player: resp_player,
@@ -1097,16 +1317,17 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_action
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(actions.contains(&select_action));
-        select_action
+        Ok(select_action)
     }
 
-    pub async fn prompt_for_bloom(&mut self, player: Player, card: CardRef) -> CardRef {
+    pub async fn prompt_for_bloom(
+        &mut self,
+        player: Player,
+        card: CardRef,
+    ) -> Result<CardRef, GameOutcome> {
         let bloom = self
             .lookup_holo_member(card)
             .expect("can only bloom from member");
@@ -1122,18 +1343,16 @@ impl GameDirector {
 
         assert!(!stage.is_empty());
         // self.prompter.prompt_choice("choose for bloom:", stage).card
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::SelectToAttach {
                     player,
                     zones: vec![], // TODO not sure if that's needed
                     select_cards: stage.clone(),
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let card = match resp {
             IntentResponse::SelectToAttach {
                 player: resp_player,
@@ -1142,13 +1361,10 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_card
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(stage.contains(&card));
-        card
+        Ok(card)
     }
 
     pub async fn prompt_for_attach(
@@ -1156,7 +1372,7 @@ impl GameDirector {
         player: Player,
         card: CardRef,
         effect_idx: usize,
-    ) -> CardRef {
+    ) -> Result<CardRef, GameOutcome> {
         let attach = self
             .lookup_support(card)
             .expect("can only attach from support");
@@ -1169,18 +1385,16 @@ impl GameDirector {
 
         assert!(!stage.is_empty());
         // self.prompter.prompt_choice("choose for attach:", stage).card
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::SelectToAttach {
                     player,
                     zones: vec![], // TODO not sure if that's needed
                     select_cards: stage.clone(),
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let card = match resp {
             IntentResponse::SelectToAttach {
                 player: resp_player,
@@ -1189,13 +1403,10 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_card
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(stage.contains(&card));
-        card
+        Ok(card)
     }
 
     pub async fn prompt_for_baton_pass(
@@ -1203,7 +1414,13 @@ impl GameDirector {
         player: Player,
         card: CardRef,
         cost: HoloMemberBatonPassCost,
-    ) -> Vec<CardRef> {
+    ) -> Result<Vec<CardRef>, GameOutcome> {
+        // the cost could be changed by effects
+        let cost = self.baton_pass_cost(card, cost);
+        if cost == 0 {
+            return Ok(vec![]);
+        }
+
         // TODO extract that filtering to a reusable function
         let cheers: Vec<_> = self
             .attached_cheers(card)
@@ -1217,9 +1434,9 @@ impl GameDirector {
             //     .map(|c| c.card)
             //     .collect()
             let zone = self.board(player).find_card_zone(card).unwrap();
-            self.client(player)
-                .0
-                .send(ClientReceive::IntentRequest(
+            let resp = self
+                .send_intent_request(
+                    player,
                     IntentRequest::SelectAttachments {
                         player,
                         card: (zone, card),
@@ -1227,10 +1444,8 @@ impl GameDirector {
                         min_amount: cost.into(),
                         max_amount: cost.into(),
                     },
-                ))
-                .await
-                .unwrap();
-            let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                )
+                .await?;
             let attachments = match resp {
                 IntentResponse::SelectAttachments {
                     player: resp_player,
@@ -1239,19 +1454,19 @@ impl GameDirector {
                     assert_eq!(player, resp_player);
                     select_attachments
                 }
-                error => {
-                    error!("unexpected response: {:?}", error);
-                    panic!("unexpected response")
-                }
+                _ => unreachable!("the response should be validated"),
             };
             assert!(attachments.iter().all(|c| cheers.contains(c)));
-            attachments
+            Ok(attachments)
         } else {
             panic!("baton pass should not be an option, if there is no cheers")
         }
     }
 
-    pub async fn prompt_for_art_action(&mut self, player: Player) -> PerformanceStepAction {
+    pub async fn prompt_for_art_action(
+        &mut self,
+        player: Player,
+    ) -> Result<PerformanceStepAction, GameOutcome> {
         let mut actions = vec![];
         for mem in self
             .board(player)
@@ -1289,17 +1504,15 @@ impl GameDirector {
         //     .prompt_choice("main step action:", actions)
         //     .action;
 
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::PerformanceStepAction {
                     player,
                     select_actions: actions.clone(),
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let select_action = match resp {
             IntentResponse::PerformanceStepAction {
                 player: resp_player,
@@ -1308,13 +1521,10 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_action
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!(actions.contains(&select_action));
-        select_action
+        Ok(select_action)
     }
 
     // pub fn prompt_for_art(&mut self, card: CardRef) -> Option<usize> {
@@ -1362,7 +1572,7 @@ impl GameDirector {
         ctx: &EvaluateContext,
         min: usize,
         max: usize,
-    ) -> Vec<CardRef> {
+    ) -> Result<Vec<CardRef>, GameOutcome> {
         let choices: Vec<_> = cards
             .iter()
             .copied()
@@ -1382,9 +1592,9 @@ impl GameDirector {
             //     .into_iter()
             //     .map(|c| c.card)
             //     .collect()
-            self.client(player)
-                .0
-                .send(ClientReceive::IntentRequest(
+            let resp = self
+                .send_intent_request(
+                    player,
                     IntentRequest::LookSelectZoneToZone {
                         player,
                         // TODO these zones are not correct
@@ -1395,10 +1605,8 @@ impl GameDirector {
                         min_amount: min,
                         max_amount: max,
                     },
-                ))
-                .await
-                .unwrap();
-            let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                )
+                .await?;
             let cards = match resp {
                 IntentResponse::LookSelectZoneToZone {
                     player: resp_player,
@@ -1407,33 +1615,31 @@ impl GameDirector {
                     assert_eq!(player, resp_player);
                     select_cards
                 }
-                error => {
-                    error!("unexpected response: {:?}", error);
-                    panic!("unexpected response")
-                }
+                _ => unreachable!("the response should be validated"),
             };
             assert!(cards.iter().all(|c| choices.contains(c)));
-            cards
+            Ok(cards)
         } else {
-            vec![]
+            Ok(vec![])
         }
     }
 
-    pub async fn prompt_for_optional_activate(&mut self, player: Player) -> bool {
+    pub async fn prompt_for_optional_activate(
+        &mut self,
+        player: Player,
+    ) -> Result<bool, GameOutcome> {
         // self.prompter
         //     .prompt_choice("do you want to activate the effect?", vec!["Yes", "No"])
         //     == "Yes"
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(
+        let resp = self
+            .send_intent_request(
+                player,
                 IntentRequest::ActivateEffect {
                     player,
                     select_yes_no: vec![true, false],
                 },
-            ))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+            )
+            .await?;
         let choice = match resp {
             IntentResponse::ActivateEffect {
                 player: resp_player,
@@ -1442,27 +1648,29 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_yes_no
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!([true, false].contains(&choice));
-        choice
+        Ok(choice)
     }
 
-    pub async fn prompt_for_number(&mut self, player: Player, min: usize, max: usize) -> usize {
+    pub async fn prompt_for_number(
+        &mut self,
+        player: Player,
+        min: usize,
+        max: usize,
+    ) -> Result<usize, GameOutcome> {
         // self.prompter
         //     .prompt_choice("choose a number:", (min..=max).collect_vec())
-        self.client(player)
-            .0
-            .send(ClientReceive::IntentRequest(IntentRequest::SelectNumber {
+        let resp = self
+            .send_intent_request(
                 player,
-                select_numbers: (min..=max).collect_vec(),
-            }))
-            .await
-            .unwrap();
-        let ClientSend::IntentResponse(resp) = self.client(player).1.recv().await.unwrap();
+                IntentRequest::SelectNumber {
+                    player,
+                    select_numbers: (min..=max).collect_vec(),
+                },
+            )
+            .await?;
         let choice = match resp {
             IntentResponse::SelectNumber {
                 player: resp_player,
@@ -1471,13 +1679,68 @@ impl GameDirector {
                 assert_eq!(player, resp_player);
                 select_number
             }
-            error => {
-                error!("unexpected response: {:?}", error);
-                panic!("unexpected response")
-            }
+            _ => unreachable!("the response should be validated"),
         };
         assert!((min..=max).collect_vec().contains(&choice));
-        choice
+        Ok(choice)
+    }
+
+    pub async fn prompt_for_option(
+        &mut self,
+        player: Player,
+        options: Vec<String>,
+    ) -> Result<usize, GameOutcome> {
+        let max = options.len();
+        let resp = self
+            .send_intent_request(
+                player,
+                IntentRequest::SelectOption {
+                    player,
+                    select_options: options,
+                },
+            )
+            .await?;
+        let choice = match resp {
+            IntentResponse::SelectOption {
+                player: resp_player,
+                select_option,
+            } => {
+                assert_eq!(player, resp_player);
+                select_option
+            }
+            _ => unreachable!("the response should be validated"),
+        };
+        assert!(choice < max);
+        Ok(choice)
+    }
+
+    pub async fn prompt_for_trigger_order(
+        &mut self,
+        player: Player,
+        triggers: Vec<PendingTrigger>,
+    ) -> Result<Vec<PendingTrigger>, GameOutcome> {
+        let resp = self
+            .send_intent_request(
+                player,
+                IntentRequest::OrderTriggers {
+                    player,
+                    select_triggers: triggers.clone(),
+                },
+            )
+            .await?;
+        let order = match resp {
+            IntentResponse::OrderTriggers {
+                player: resp_player,
+                select_order,
+            } => {
+                assert_eq!(player, resp_player);
+                select_order
+            }
+            _ => unreachable!("the response should be validated"),
+        };
+        assert_eq!(order.len(), triggers.len());
+        assert!(triggers.iter().all(|t| order.contains(t)));
+        Ok(order)
     }
 }
 
@@ -1794,7 +2057,7 @@ pub trait ZoneControl {
     fn add_bottom_card(&mut self, card: CardRef);
     fn replace_card(&mut self, from_card: CardRef, to_card: CardRef);
     fn is_in_zone(&self, card: CardRef) -> bool;
-    fn shuffle(&mut self, rng: &mut Box<dyn RngCore>);
+    fn shuffle(&mut self, rng: &mut Box<dyn RngCore + Send>);
 }
 
 impl ZoneControl for Option<CardRef> {
@@ -1856,7 +2119,7 @@ impl ZoneControl for Option<CardRef> {
         *self == Some(card)
     }
 
-    fn shuffle(&mut self, _rng: &mut Box<dyn RngCore>) {
+    fn shuffle(&mut self, _rng: &mut Box<dyn RngCore + Send>) {
         // nothing to shuffle
     }
 }
@@ -1916,7 +2179,7 @@ impl ZoneControl for VecDeque<CardRef> {
         self.iter().any(|c| *c == card)
     }
 
-    fn shuffle(&mut self, rng: &mut Box<dyn RngCore>) {
+    fn shuffle(&mut self, rng: &mut Box<dyn RngCore + Send>) {
         self.make_contiguous().shuffle(rng)
     }
 }
@@ -2125,7 +2388,7 @@ impl CardDisplay {
                     m.name,
                     m.level,
                     game.remaining_hp(card),
-                    m.hp,
+                    game.max_hp(card),
                     if game.attached_cheers(card).any(|_| true) {
                         format!(
                             " (cheers: {})",
@@ -2209,6 +2472,62 @@ impl Display for PerformanceStepActionDisplay {
     }
 }
 
+/// a triggered effect waiting to be resolved
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, GetSize, Encode, Decode)]
+pub enum PendingTrigger {
+    OshiSkill(CardRef, usize),
+    HoloMemberAbility(CardRef, usize),
+    SupportEffect(CardRef, usize),
+}
+
+impl PendingTrigger {
+    pub fn card(&self) -> CardRef {
+        match *self {
+            PendingTrigger::OshiSkill(card, _) => card,
+            PendingTrigger::HoloMemberAbility(card, _) => card,
+            PendingTrigger::SupportEffect(card, _) => card,
+        }
+    }
+
+    /// the player is asked before activating it
+    pub fn is_optional(&self) -> bool {
+        matches!(self, PendingTrigger::OshiSkill(_, _))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PendingTriggerDisplay {
+    pub trigger: PendingTrigger,
+    text: String,
+}
+
+impl PendingTriggerDisplay {
+    pub fn new(trigger: PendingTrigger, game: &Game) -> PendingTriggerDisplay {
+        let display = CardDisplay::new(trigger.card(), game);
+        let text = match trigger {
+            PendingTrigger::OshiSkill(card, idx) => {
+                let oshi = game.lookup_oshi(card).expect("it should be an oshi");
+                format!("skill: {} - {display}", oshi.skills[idx].name)
+            }
+            PendingTrigger::HoloMemberAbility(card, idx) => {
+                let mem = game
+                    .lookup_holo_member(card)
+                    .expect("it should be a member");
+                format!("ability: {} - {display}", mem.abilities[idx].name)
+            }
+            PendingTrigger::SupportEffect(_card, _idx) => format!("support: {display}"),
+        };
+
+        PendingTriggerDisplay { trigger, text }
+    }
+}
+
+impl Display for PendingTriggerDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ArtDisplay {
     card: CardRef,
@@ -2245,6 +2564,19 @@ impl Display for ArtDisplay {
     }
 }
 
+/// the options can have the same text, they are chosen by index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionDisplay {
+    pub index: usize,
+    pub text: String,
+}
+
+impl Display for OptionDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, GetSize, Encode, Decode)]
 pub struct GameState {
     pub game_outcome: Option<GameOutcome>,
@@ -2257,6 +2589,9 @@ pub struct GameState {
     pub zone_modifiers: HashMap<Player, Vec<(Zone, Modifier)>>,
     pub card_modifiers: HashMap<CardRef, Vec<Modifier>>,
     pub card_damage_markers: HashMap<CardRef, DamageMarkers>,
+    pub card_counters: HashMap<CardRef, HashMap<String, usize>>,
+    pub card_counter_life_times: HashMap<CardRef, HashMap<String, LifeTime>>,
+    pub card_usages: HashMap<CardRef, HashMap<String, LifeTime>>,
 }
 
 impl GameState {
@@ -2289,12 +2624,205 @@ impl GameState {
         let player = self.player_for_card(card);
         self.board_mut(player)
     }
+
+    /// the cards that anyone can see: oshi, stage, archive and attachments
+    pub fn public_cards(&self) -> impl Iterator<Item = (Player, Zone, CardRef)> + '_ {
+        [Player::One, Player::Two]
+            .into_iter()
+            .flat_map(move |player| {
+                let board = self.board(player);
+                [
+                    Zone::Oshi,
+                    Zone::CenterStage,
+                    Zone::Collab,
+                    Zone::BackStage,
+                    Zone::Archive,
+                    Zone::ActivateSupport,
+                ]
+                .into_iter()
+                .flat_map(move |zone| board.all_cards(zone).into_iter().map(move |c| (zone, c)))
+                .chain(board.attachments.keys().map(|c| (Zone::Stage, *c)))
+                .map(move |(zone, card)| (player, zone, card))
+            })
+    }
+
+    /// the public cards, with the hand of the player
+    pub fn visible_cards(
+        &self,
+        player: Option<Player>,
+    ) -> impl Iterator<Item = (Player, Zone, CardRef)> + '_ {
+        let hand = player.into_iter().flat_map(move |player| {
+            self.board(player)
+                .hand
+                .iter()
+                .map(move |card| (player, Zone::Hand, *card))
+        });
+        self.public_cards().chain(hand)
+    }
+
+    /// the state without the hidden cards, they are still owned by their player
+    pub fn public_view(&self) -> GameState {
+        self.view(None)
+    }
+
+    /// the public state, with the hand of the player
+    pub fn player_view(&self, player: Player) -> GameState {
+        self.view(Some(player))
+    }
+
+    /// the same view of the state gives the same hash, on the server and on the client. the
+    /// order of the decks is not known by the clients
+    pub fn checksum(&self, player: Player) -> u64 {
+        fn sorted_map<V: Clone>(map: &HashMap<CardRef, V>) -> Vec<(CardRef, V)> {
+            map.iter()
+                .map(|(c, v)| (*c, v.clone()))
+                .sorted_by_key(|(c, _)| *c)
+                .collect_vec()
+        }
+        fn sorted_board(board: &GameBoard) -> impl Encode {
+            let zones = (
+                board.oshi,
+                board.main_deck.iter().copied().sorted().collect_vec(),
+                board.center_stage,
+                board.collab,
+                board.back_stage.clone(),
+                board.life.clone(),
+                board.cheer_deck.iter().copied().sorted().collect_vec(),
+                board.holo_power.clone(),
+                board.archive.clone(),
+                board.hand.clone(),
+                board.activate_support.clone(),
+            );
+            (zones, sorted_map(&board.attachments))
+        }
+
+        let view = self.player_view(player);
+        let counters = sorted_map(&view.card_counters)
+            .into_iter()
+            .map(|(card, counters)| (card, counters.into_iter().sorted().collect_vec()))
+            .collect_vec();
+        let counter_life_times = sorted_map(&view.card_counter_life_times)
+            .into_iter()
+            .map(|(card, life_times)| {
+                let life_times = life_times
+                    .into_iter()
+                    .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                    .collect_vec();
+                (card, life_times)
+            })
+            .collect_vec();
+        let usages = sorted_map(&view.card_usages)
+            .into_iter()
+            .map(|(card, usages)| {
+                let usages = usages
+                    .into_iter()
+                    .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                    .collect_vec();
+                (card, usages)
+            })
+            .collect_vec();
+        let projection = (
+            view.game_outcome,
+            sorted_map(&view.card_map),
+            sorted_board(&view.player_1),
+            sorted_board(&view.player_2),
+            (view.active_player, view.active_step, view.turn_number),
+            view.zone_modifiers
+                .into_iter()
+                .sorted_by_key(|(p, _)| *p as u8)
+                .collect_vec(),
+            sorted_map(&view.card_modifiers),
+            sorted_map(&view.card_damage_markers),
+            counters,
+            counter_life_times,
+            usages,
+        );
+        let bytes = bincode::encode_to_vec(projection, config::standard())
+            .expect("the state can be encoded");
+        stable_hash(bytes)
+    }
+
+    fn view(&self, player: Option<Player>) -> GameState {
+        let visible: HashSet<_> = self.visible_cards(player).map(|(_, _, c)| c).collect();
+        let mut state = self.clone();
+        for (card, (_, card_number)) in &mut state.card_map {
+            if !visible.contains(card) {
+                *card_number = HIDDEN_CARD_NUMBER.into();
+            }
+        }
+        state
+    }
+
+    /// after a view of the state, reveal the cards that became visible since
+    pub fn reveal_visible_cards(
+        &self,
+        player: Option<Player>,
+        known: &mut HashSet<CardRef>,
+    ) -> Vec<Reveal> {
+        let visible = self.visible_cards(player).map(|(_, _, c)| c).collect_vec();
+        self.reveal_unknown_cards(visible, known)
+    }
+
+    /// the message, with the cards that the player can see after it. for a player that only
+    /// knows some of the cards, the state is after the message
+    pub fn with_reveals(
+        &self,
+        player: Player,
+        message: ClientReceive,
+        known: &mut HashSet<CardRef>,
+    ) -> Vec<ClientReceive> {
+        let reveals = match &message {
+            ClientReceive::IntentRequest(req) => {
+                self.reveal_unknown_cards(req.shown_cards(), known)
+            }
+            _ => self.reveal_visible_cards(Some(player), known),
+        };
+        let reveals = reveals
+            .into_iter()
+            .map(|reveal| ClientReceive::Event(reveal.into()));
+        match message {
+            // the cards are needed to respond
+            ClientReceive::IntentRequest(_) => reveals.chain(iter::once(message)).collect(),
+            // the cards are visible after the event
+            _ => iter::once(message).chain(reveals).collect(),
+        }
+    }
+
+    /// reveal the cards that are not known yet, from their current zone
+    pub fn reveal_unknown_cards(
+        &self,
+        cards: impl IntoIterator<Item = CardRef>,
+        known: &mut HashSet<CardRef>,
+    ) -> Vec<Reveal> {
+        cards
+            .into_iter()
+            .filter(|c| known.insert(*c))
+            .into_group_map_by(|c| {
+                let board = self.board_for_card(*c);
+                // attached cards are not in a zone
+                let zone = board.find_card_zone(*c).unwrap_or(Zone::Stage);
+                (self.player_for_card(*c), zone)
+            })
+            .into_iter()
+            .sorted_by_key(|((player, zone), _)| (*player as u8, *zone as u8))
+            .map(|((player, zone), cards)| Reveal {
+                player,
+                zone,
+                cards: cards
+                    .into_iter()
+                    .map(|c| (c, self.card_map[&c].1.clone()))
+                    .collect(),
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Default)]
 pub struct Game {
     pub library: Option<RwLockReadGuard<'static, GlobalLibrary>>,
     pub event_span: EventSpan,
+    /// opt-in, records the evaluation of effects and events
+    pub tracer: Option<EvaluationTracer>,
     pub state: GameState,
 }
 
@@ -2303,6 +2831,7 @@ impl Game {
         Game {
             library: Some(library().await),
             event_span: EventSpan::new(),
+            tracer: None,
             state: Default::default(),
         }
     }
@@ -0,0 +1,102 @@
+use std::fmt::{Debug, Display};
+
+use bincode::{Decode, Encode};
+
+use crate::library::GlobalLibrary;
+
+/// changes when the messages between the client and the server change
+pub const PROTOCOL_VERSION: u32 = 4;
+/// the rules are evaluated on both sides, with [`crate::events::EvaluateEvent::apply_state_change`]
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Sent by both sides before anything else. The protocol version stays the first field,
+/// so any version can read it
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct Handshake {
+    pub protocol_version: u32,
+    pub engine_version: String,
+    /// see [`GlobalLibrary::library_hash`]
+    pub library_hash: u64,
+}
+
+impl Handshake {
+    pub fn new(library: &GlobalLibrary) -> Self {
+        Handshake {
+            protocol_version: PROTOCOL_VERSION,
+            engine_version: ENGINE_VERSION.into(),
+            library_hash: library.library_hash(),
+        }
+    }
+
+    /// the peer can play with us. a different library could be replaced
+    pub fn check(&self, peer: &Handshake) -> Result<(), HandshakeError> {
+        if self.protocol_version != peer.protocol_version {
+            Err(HandshakeError::ProtocolVersion {
+                expected: self.protocol_version,
+                found: peer.protocol_version,
+            })
+        } else if self.engine_version != peer.engine_version {
+            Err(HandshakeError::EngineVersion {
+                expected: self.engine_version.clone(),
+                found: peer.engine_version.clone(),
+            })
+        } else if self.library_hash != peer.library_hash {
+            Err(HandshakeError::Library {
+                expected: self.library_hash,
+                found: peer.library_hash,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Sent by the server after the handshake of the client
+#[derive(Clone, PartialEq, Eq, Encode, Decode)]
+pub enum HandshakeResponse {
+    /// with the handshake of the server
+    Accepted(Handshake),
+    /// the library of the server, load it and send the handshake again
+    Library(Vec<u8>),
+    /// the connection is closed after that
+    Rejected(String),
+}
+
+// the library is too large to be logged
+impl Debug for HandshakeResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandshakeResponse::Accepted(server) => f.debug_tuple("Accepted").field(server).finish(),
+            HandshakeResponse::Library(bytes) => write!(f, "Library({} bytes)", bytes.len()),
+            HandshakeResponse::Rejected(e) => f.debug_tuple("Rejected").field(e).finish(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeError {
+    ProtocolVersion { expected: u32, found: u32 },
+    EngineVersion { expected: String, found: String },
+    Library { expected: u64, found: u64 },
+}
+
+impl Display for HandshakeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandshakeError::ProtocolVersion { expected, found } => write!(
+                f,
+                "incompatible protocol version {found}, the server uses version {expected}"
+            ),
+            HandshakeError::EngineVersion { expected, found } => write!(
+                f,
+                "incompatible engine version {found}, the server uses version {expected}"
+            ),
+            HandshakeError::Library { expected, found } => write!(
+                f,
+                "different card library {found:016x}, the server uses {expected:016x}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HandshakeError {}
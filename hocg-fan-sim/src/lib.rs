@@ -3,8 +3,14 @@ pub mod cards;
 pub mod client;
 pub mod events;
 pub mod gameplay;
+pub mod handshake;
 pub mod library;
+pub mod lobby;
 pub mod modifiers;
 pub mod prompters;
+#[cfg(feature = "spectators")]
+pub mod spectators;
 #[cfg(feature = "test")]
 pub mod tests;
+pub mod timing;
+pub mod transport;
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, fmt::Display, io::Write, sync::OnceLock};
 
 use crate::{
     card_effects::{Action, Condition, Error, ParseEffect, ParseTokens, SerializeEffect, Trigger},
@@ -6,8 +6,9 @@ use crate::{
 };
 use async_rwlock::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard};
 use bincode::{config, Decode, Encode};
-use flate2::read::GzDecoder;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use get_size::GetSize;
+use iter_tools::Itertools;
 use serde::{Deserialize, Serialize};
 use tracing::{error, warn};
 
@@ -49,12 +50,42 @@ pub struct GlobalLibrary {
     pub cards: HashMap<CardNumber, Card>,
 }
 
+/// FNV-1a, the same bytes give the same hash on any platform and build
+pub(crate) fn stable_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+    bytes.into_iter().fold(OFFSET, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
 impl GlobalLibrary {
+    /// the same library gives the same hash, on any platform and build
+    pub fn library_hash(&self) -> u64 {
+        // the cards are sorted because the map is not
+        let config = config::standard();
+        stable_hash(
+            self.cards
+                .iter()
+                .sorted_by_key(|(number, _)| *number)
+                .flat_map(|card| {
+                    bincode::encode_to_vec(card, config).expect("cards can be encoded")
+                }),
+        )
+    }
+
+    /// compressed, like the library file. used with [`load_library`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let config = config::standard();
+        let bin = bincode::encode_to_vec(self, config).expect("the library can be encoded");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bin).expect("writing to memory");
+        encoder.finish().expect("writing to memory")
+    }
+
     /// Any pre-processing of cards that could make my life easier later
     pub fn pre_process(&mut self) {
         // not sure if these are good ideas. might be better to be explicit
-        // TODO oshi skill once turn
-        // TODO special oshi skill once per game
         // TODO enough holo power to pay the cost for oshi skill
         // TODO enough cheers to perform art for members
         // TODO limited support
@@ -75,8 +106,12 @@ impl GlobalLibrary {
                         o.illustration_url.clone_from(&default_url);
                     };
                     o.skills.iter_mut().for_each(|s| {
+                        // oshi skill once per turn, special oshi skill once per game
+                        if s.limit.is_unlimited() {
+                            s.limit = s.kind.default_limit();
+                        }
                         if s.triggers.is_empty() {
-                            s.triggers.push(default_trigger)
+                            s.triggers.push(default_trigger.clone())
                         }
                         if s.condition.is_empty() {
                             s.condition.push(default_condition.clone())
@@ -113,7 +148,7 @@ impl GlobalLibrary {
                     };
                     s.effects.iter_mut().for_each(|s| {
                         if s.triggers.is_empty() {
-                            s.triggers.push(default_trigger)
+                            s.triggers.push(default_trigger.clone())
                         }
                         if s.condition.is_empty() {
                             s.condition.push(default_condition.clone())
@@ -256,3 +291,87 @@ pub struct Loadout {
     pub cheer_deck: Vec<CardNumber>,
     // cosmetic...
 }
+
+pub const MAIN_DECK_SIZE: usize = 50;
+pub const CHEER_DECK_SIZE: usize = 20;
+pub const MAX_CARD_COPIES: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoadoutError {
+    UnknownCard(CardNumber),
+    NotAnOshi(CardNumber),
+    MainDeckSize(usize),
+    NotInMainDeck(CardNumber),
+    CheerDeckSize(usize),
+    NotACheer(CardNumber),
+    TooManyCopies(CardNumber, usize),
+}
+
+impl Display for LoadoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadoutError::UnknownCard(c) => write!(f, "unknown card: {c}"),
+            LoadoutError::NotAnOshi(c) => write!(f, "not an oshi: {c}"),
+            LoadoutError::MainDeckSize(s) => {
+                write!(f, "the main deck has {s} cards instead of {MAIN_DECK_SIZE}")
+            }
+            LoadoutError::NotInMainDeck(c) => write!(f, "not allowed in the main deck: {c}"),
+            LoadoutError::CheerDeckSize(s) => {
+                write!(
+                    f,
+                    "the cheer deck has {s} cards instead of {CHEER_DECK_SIZE}"
+                )
+            }
+            LoadoutError::NotACheer(c) => write!(f, "not a cheer: {c}"),
+            LoadoutError::TooManyCopies(c, n) => {
+                write!(f, "{n} copies of {c}, the limit is {MAX_CARD_COPIES}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadoutError {}
+
+impl Loadout {
+    /// Check the deck building rules, before starting a game with this loadout
+    pub fn validate(&self, library: &GlobalLibrary) -> Result<(), LoadoutError> {
+        let lookup = |c: &CardNumber| {
+            library
+                .cards
+                .get(c)
+                .ok_or_else(|| LoadoutError::UnknownCard(c.clone()))
+        };
+
+        if !matches!(lookup(&self.oshi)?, Card::OshiHoloMember(_)) {
+            return Err(LoadoutError::NotAnOshi(self.oshi.clone()));
+        }
+
+        if self.main_deck.len() != MAIN_DECK_SIZE {
+            return Err(LoadoutError::MainDeckSize(self.main_deck.len()));
+        }
+        let mut copies: HashMap<&CardNumber, usize> = HashMap::new();
+        for card_number in &self.main_deck {
+            let limited = match lookup(card_number)? {
+                Card::HoloMember(m) => !m.attributes.contains(&HoloMemberExtraAttribute::Unlimited),
+                Card::Support(_) => true,
+                _ => return Err(LoadoutError::NotInMainDeck(card_number.clone())),
+            };
+            let count = copies.entry(card_number).or_default();
+            *count += 1;
+            if limited && *count > MAX_CARD_COPIES {
+                return Err(LoadoutError::TooManyCopies(card_number.clone(), *count));
+            }
+        }
+
+        if self.cheer_deck.len() != CHEER_DECK_SIZE {
+            return Err(LoadoutError::CheerDeckSize(self.cheer_deck.len()));
+        }
+        for card_number in &self.cheer_deck {
+            if !matches!(lookup(card_number)?, Card::Cheer(_)) {
+                return Err(LoadoutError::NotACheer(card_number.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use bincode::{Decode, Encode};
+
+use crate::{gameplay::Player, library::Loadout};
+
+/// Given when the game starts, to reconnect to the game
+pub type SessionToken = String;
+
+/// Sent by a player before the game starts
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum LobbyRequest {
+    /// needs to be sent first to play, the loadout is validated by the server
+    Register {
+        name: String,
+        loadout: Loadout,
+    },
+    ListTables,
+    CreateTable {
+        table: String,
+    },
+    JoinTable {
+        table: String,
+    },
+    /// wait for any other player, a table is created when there is a match
+    JoinQueue,
+    /// leave the current table or the queue
+    Leave,
+    /// the game starts when both players at the table are ready
+    Ready,
+    /// follow the game at the table, without playing. can be sent instead of registering,
+    /// without a loadout
+    Spectate {
+        table: String,
+    },
+    /// instead of registering, continue a game after losing the connection
+    Reconnect {
+        session: SessionToken,
+    },
+}
+
+/// Sent to a player before the game starts
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum LobbyResponse {
+    Registered,
+    Tables(Vec<TableInfo>),
+    /// joined, or someone else joined, left or is ready
+    TableUpdated(TableInfo),
+    /// left the table or the queue, or the table was closed
+    Left,
+    Queued,
+    /// the next messages are for the game
+    GameStarting {
+        player: Player,
+        opponent: String,
+        session: SessionToken,
+    },
+    /// the next messages are for the game, starting with its current state
+    Reconnected {
+        player: Player,
+    },
+    /// the next messages are the public events of the game
+    Spectating {
+        table: String,
+    },
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TableInfo {
+    pub name: String,
+    pub players: Vec<String>,
+    pub ready: Vec<String>,
+    /// the game started, it can be spectated
+    pub playing: bool,
+}
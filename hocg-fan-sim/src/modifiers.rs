@@ -1,15 +1,18 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Display;
+use std::iter;
 use std::num::NonZeroU16;
 
 use bincode::{Decode, Encode};
 use get_size::GetSize;
 
-use crate::card_effects::evaluate::EvaluateEffect;
+use crate::card_effects;
+use crate::card_effects::evaluate::{EvaluateEffect, EvaluateEffectMut};
 use crate::card_effects::Condition;
 
 use super::cards::*;
+use super::events::{AddCardUsage, AdjustEventOutcome, AdjustEventResult, Event};
 use super::gameplay::*;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, GetSize, Encode, Decode)]
@@ -54,10 +57,52 @@ pub enum ModifierKind {
     DealMoreDamage(usize),
     ReceiveLessDamage(usize),
     ReceiveMoreDamage(usize),
+    MoreHp(usize),
+    LessArtCost(Color, usize),
+    BatonPassCost(usize),
+    UntargetableByArts,
+    CollabWhileResting,
     NextDiceRoll(usize),
     NoLifeLoss,
     AsArtCost(Color, usize),
     AsCheer(Color, usize),
+    // replacement effects
+    Prevent(ReplacedEvent),
+    Instead(ReplacedEvent, Vec<card_effects::Action>),
+}
+
+/// the events that can be prevented or replaced, for each affected card
+#[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize, Encode, Decode)]
+pub enum ReplacedEvent {
+    ReceiveDamage,
+    KnockedOut,
+    SendToZone(Zone),
+}
+
+impl ReplacedEvent {
+    pub fn replaces(&self, event: ReplacedEvent) -> bool {
+        match (self, event) {
+            (ReplacedEvent::SendToZone(z), ReplacedEvent::SendToZone(e)) => z.includes(e),
+            _ => *self == event,
+        }
+    }
+}
+
+impl ModifierKind {
+    /// conditional modifiers only apply when the condition is met, for the queried card
+    pub fn for_card(self, card: CardRef, game: &Game) -> Option<ModifierKind> {
+        match self {
+            ModifierKind::Conditional(condition, kind) => {
+                if condition.ctx().with_card(card, game).evaluate(game) {
+                    // conditions can be nested
+                    kind.for_card(card, game)
+                } else {
+                    None
+                }
+            }
+            kind => Some(kind),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, GetSize, Encode, Decode)]
@@ -203,6 +248,54 @@ impl GameState {
             *self.card_damage_markers.entry(attachment).or_default() += dmg;
         }
     }
+
+    pub fn start_turn_counters(&mut self, player: Player) {
+        // becomes ThisTurn on player's next start turn
+        self.card_counter_life_times
+            .values_mut()
+            .flat_map(|ls| ls.values_mut())
+            .filter(|l| **l == LifeTime::NextTurn(player))
+            .for_each(|l| *l = LifeTime::ThisTurn);
+    }
+
+    pub fn remove_counter(&mut self, card: CardRef, name: &str) {
+        if let Some(counters) = self.card_counters.get_mut(&card) {
+            counters.remove(name);
+            if counters.is_empty() {
+                self.card_counters.remove(&card);
+            }
+        }
+        if let Some(life_times) = self.card_counter_life_times.get_mut(&card) {
+            life_times.remove(name);
+            if life_times.is_empty() {
+                self.card_counter_life_times.remove(&card);
+            }
+        }
+    }
+
+    pub fn remove_usage(&mut self, card: CardRef, usage: &str) {
+        if let Some(usages) = self.card_usages.get_mut(&card) {
+            usages.remove(usage);
+            if usages.is_empty() {
+                self.card_usages.remove(&card);
+            }
+        }
+    }
+
+    pub fn promote_counters(&mut self, attachment: CardRef, parent: CardRef) {
+        if let Some((_, counters)) = self.card_counters.remove_entry(&parent) {
+            let attachment_counters = self.card_counters.entry(attachment).or_default();
+            for (name, amount) in counters {
+                *attachment_counters.entry(name).or_default() += amount;
+            }
+        }
+        if let Some((_, life_times)) = self.card_counter_life_times.remove_entry(&parent) {
+            self.card_counter_life_times
+                .entry(attachment)
+                .or_default()
+                .extend(life_times);
+        }
+    }
 }
 
 impl Game {
@@ -227,16 +320,8 @@ impl Game {
             .filter(|m| m.is_active())
             .cloned()
             .filter_map(move |mut m| {
-                if let ModifierKind::Conditional(c, k) = m.kind {
-                    if c.ctx().with_card(card, self).evaluate(self) {
-                        m.kind = *k;
-                        Some(m)
-                    } else {
-                        None
-                    }
-                } else {
-                    Some(m)
-                }
+                m.kind = m.kind.for_card(card, self)?;
+                Some(m)
             })
     }
     pub fn find_player_modifiers(&self, player: Player) -> impl Iterator<Item = Modifier> + '_ {
@@ -289,11 +374,54 @@ impl Game {
 
     pub fn remaining_hp(&self, card: CardRef) -> HoloMemberHp {
         let dmg = self.get_damage(card);
+        let hp = self.max_hp(card);
+        hp.saturating_sub(dmg.to_hp())
+    }
+
+    pub fn max_hp(&self, card: CardRef) -> HoloMemberHp {
         let hp = self
             .lookup_holo_member(card)
             .expect("should be a member")
             .hp;
-        hp.saturating_sub(dmg.to_hp())
+        let more_hp: usize = self
+            .find_modifiers(card)
+            .filter_map(|m| match m.kind {
+                ModifierKind::MoreHp(more_hp) => Some(more_hp),
+                _ => None,
+            })
+            .sum();
+        hp.saturating_add(more_hp as HoloMemberHp)
+    }
+
+    // costs
+    pub fn art_cost(&self, card: CardRef, cost: &[Color]) -> Vec<Color> {
+        let mut cost = cost.to_vec();
+        for m in self.find_modifiers(card) {
+            if let ModifierKind::LessArtCost(color, amount) = m.kind {
+                for _ in 0..amount {
+                    if let Some(i) = cost.iter().position(|c| *c == color) {
+                        cost.remove(i);
+                    }
+                }
+            }
+        }
+        cost
+    }
+
+    pub fn baton_pass_cost(
+        &self,
+        card: CardRef,
+        cost: HoloMemberBatonPassCost,
+    ) -> HoloMemberBatonPassCost {
+        // the cheapest one applies, a modifier cannot make it more expensive
+        self.find_modifiers(card)
+            .filter_map(|m| match m.kind {
+                ModifierKind::BatonPassCost(cost) => Some(cost as HoloMemberBatonPassCost),
+                _ => None,
+            })
+            .chain(iter::once(cost))
+            .min()
+            .expect("there is at least the base cost")
     }
 
     pub fn get_damage(&self, card: CardRef) -> DamageMarkers {
@@ -303,6 +431,47 @@ impl Game {
             .copied()
             .unwrap_or_default()
     }
+
+    // counters
+    pub fn has_counter(&self, card: CardRef, name: &str) -> bool {
+        self.get_counter(card, name) > 0
+    }
+
+    pub fn get_counter(&self, card: CardRef, name: &str) -> usize {
+        self.state
+            .card_counters
+            .get(&card)
+            .and_then(|cs| cs.get(name))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // usage limits
+    /// where the usage is recorded, on the card or on the oshi when shared by name
+    ///
+    /// the usage stays on the card that was used. after a bloom, the abilities
+    /// of the new card have not been used yet, even if the limit is per turn
+    fn usage_key(&self, card: CardRef, usage: &str, limit: UsageLimit) -> (CardRef, String) {
+        if limit.is_per_name() {
+            let player = self.player_for_card(card);
+            let oshi = self.board(player).oshi().expect("oshi is always there");
+            let name = self.lookup_card(card).name();
+            (oshi, format!("{name} {usage}"))
+        } else {
+            (card, usage.into())
+        }
+    }
+
+    pub fn has_reached_usage_limit(&self, card: CardRef, usage: &str, limit: UsageLimit) -> bool {
+        if limit.is_unlimited() {
+            return false;
+        }
+        let (card, usage) = self.usage_key(card, usage, limit);
+        self.state
+            .card_usages
+            .get(&card)
+            .is_some_and(|us| us.contains_key(&usage))
+    }
 }
 
 impl GameDirector {
@@ -484,7 +653,70 @@ impl GameDirector {
             self.remove_many_modifiers_from_zone(p, z, m).await?;
         }
 
-        Ok(GameContinue)
+        // counters and usages share the same life times
+        self.remove_expiring_counters(life_time).await?;
+        self.remove_expiring_usages(life_time).await
+    }
+
+    // replacement effects
+    pub async fn replace_event(&mut self, event: &mut Event) -> AdjustEventResult {
+        let (cards, replaced) = match &*event {
+            Event::DealDamage(e) => (e.targets.clone(), ReplacedEvent::ReceiveDamage),
+            Event::HoloMemberKnockedOut(e) => (e.cards.clone(), ReplacedEvent::KnockedOut),
+            Event::SendToZone(e) => (e.cards.clone(), ReplacedEvent::SendToZone(e.zone)),
+            _ => return Ok(AdjustEventOutcome::ContinueEvent),
+        };
+
+        // the first replacement of each card applies, but never twice for the same event
+        let replacements = cards
+            .into_iter()
+            .filter_map(|card| {
+                self.find_modifiers(card)
+                    .filter(|m| !self.game.event_span.is_replacing(m.id, card))
+                    .find_map(|m| match m.kind {
+                        ModifierKind::Prevent(r) if r.replaces(replaced) => {
+                            Some((card, m.id, None))
+                        }
+                        ModifierKind::Instead(r, actions) if r.replaces(replaced) => {
+                            Some((card, m.id, Some(actions)))
+                        }
+                        _ => None,
+                    })
+            })
+            .collect::<Vec<_>>();
+        if replacements.is_empty() {
+            return Ok(AdjustEventOutcome::ContinueEvent);
+        }
+
+        // the replaced cards are no longer part of the event
+        let replaced_cards: Vec<_> = replacements.iter().map(|(c, _, _)| *c).collect();
+        let remaining = match event {
+            Event::DealDamage(e) => &mut e.targets,
+            Event::HoloMemberKnockedOut(e) => &mut e.cards,
+            Event::SendToZone(e) => &mut e.cards,
+            _ => unreachable!("only these events can be replaced"),
+        };
+        remaining.retain(|c| !replaced_cards.contains(c));
+        let is_empty = remaining.is_empty();
+
+        // do something else instead
+        for (card, id, actions) in replacements {
+            if let Some(actions) = actions {
+                self.game.event_span.open_replacement_span(id, card);
+                actions
+                    .ctx()
+                    .with_card(card, &self.game)
+                    .evaluate_mut(self)
+                    .await?;
+                self.game.event_span.close_replacement_span(id, card);
+            }
+        }
+
+        if is_empty {
+            Ok(AdjustEventOutcome::PreventEvent)
+        } else {
+            Ok(AdjustEventOutcome::ContinueEvent)
+        }
     }
 
     // damage markers
@@ -496,10 +728,27 @@ impl GameDirector {
         self.game.remaining_hp(card)
     }
 
+    pub fn max_hp(&self, card: CardRef) -> HoloMemberHp {
+        self.game.max_hp(card)
+    }
+
     pub fn get_damage(&self, card: CardRef) -> DamageMarkers {
         self.game.get_damage(card)
     }
 
+    // costs
+    pub fn art_cost(&self, card: CardRef, cost: &[Color]) -> Vec<Color> {
+        self.game.art_cost(card, cost)
+    }
+
+    pub fn baton_pass_cost(
+        &self,
+        card: CardRef,
+        cost: HoloMemberBatonPassCost,
+    ) -> HoloMemberBatonPassCost {
+        self.game.baton_pass_cost(card, cost)
+    }
+
     pub async fn add_damage_markers(
         &mut self,
         cards: Vec<CardRef>,
@@ -515,6 +764,103 @@ impl GameDirector {
     ) -> GameResult {
         self.remove_damage_markers_from_many_cards(cards, dmg).await
     }
+
+    // counters
+    pub fn has_counter(&self, card: CardRef, name: &str) -> bool {
+        self.game.has_counter(card, name)
+    }
+
+    pub fn get_counter(&self, card: CardRef, name: &str) -> usize {
+        self.game.get_counter(card, name)
+    }
+
+    pub async fn add_counter(
+        &mut self,
+        cards: Vec<CardRef>,
+        name: String,
+        amount: usize,
+        life_time: LifeTime,
+    ) -> GameResult {
+        self.add_counters_to_many_cards(cards, name, amount, life_time)
+            .await
+    }
+
+    pub async fn remove_counter(
+        &mut self,
+        cards: Vec<CardRef>,
+        name: String,
+        amount: usize,
+    ) -> GameResult {
+        self.remove_counters_from_many_cards(cards, name, amount)
+            .await
+    }
+
+    pub fn has_reached_usage_limit(&self, card: CardRef, usage: &str, limit: UsageLimit) -> bool {
+        self.game.has_reached_usage_limit(card, usage, limit)
+    }
+
+    pub async fn record_usage(
+        &mut self,
+        card: CardRef,
+        usage: &str,
+        limit: UsageLimit,
+    ) -> GameResult {
+        let Some(life_time) = limit.life_time() else {
+            return Ok(GameContinue);
+        };
+        let (card, usage) = self.game.usage_key(card, usage, limit);
+        self.send_event(
+            AddCardUsage {
+                card,
+                usage,
+                life_time,
+            }
+            .into(),
+        )
+        .await?;
+
+        Ok(GameContinue)
+    }
+
+    pub async fn remove_expiring_counters(&mut self, life_time: LifeTime) -> GameResult {
+        let expiring: HashMap<_, Vec<_>> = self
+            .game
+            .state
+            .card_counter_life_times
+            .iter()
+            .flat_map(|(c, ls)| ls.iter().map(move |(n, l)| (c, n, l)))
+            .filter(|(_, _, l)| **l == life_time)
+            .fold(HashMap::new(), |mut n_c, (c, n, _)| {
+                n_c.entry(n.clone()).or_default().push(*c);
+                n_c
+            });
+        for (name, cards) in expiring {
+            self.clear_counters_from_many_cards(cards, Some(name))
+                .await?;
+        }
+
+        Ok(GameContinue)
+    }
+
+    pub async fn remove_expiring_usages(&mut self, life_time: LifeTime) -> GameResult {
+        let expiring: HashMap<_, Vec<_>> = self
+            .game
+            .state
+            .card_usages
+            .iter()
+            .flat_map(|(c, us)| us.iter().map(move |(u, l)| (c, u, l)))
+            .filter(|(_, _, l)| **l == life_time)
+            .fold(HashMap::new(), |mut u_c, (c, u, _)| {
+                u_c.entry(u.clone()).or_default().push(*c);
+                u_c
+            });
+        for (usage, cards) in expiring {
+            self.clear_usages_from_many_cards(cards, Some(usage))
+                .await?;
+        }
+
+        Ok(GameContinue)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, GetSize, Encode, Decode)]
@@ -7,7 +7,10 @@ use tracing::info;
 use crate::{
     client::IntentRequestHandler,
     events::{IntentRequest, IntentResponse},
-    gameplay::{CardDisplay, Game, MainStepActionDisplay, PerformanceStepActionDisplay},
+    gameplay::{
+        CardDisplay, Game, MainStepActionDisplay, OptionDisplay, PendingTriggerDisplay,
+        PerformanceStepActionDisplay,
+    },
 };
 
 #[derive(Debug, Default)]
@@ -344,6 +347,46 @@ impl<P: Prompter> IntentRequestHandler for P {
                     select_number: number,
                 }
             }
+            IntentRequest::SelectOption {
+                player,
+                select_options,
+            } => {
+                let select_options = select_options
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, text)| OptionDisplay { index, text })
+                    .collect_vec();
+                let option = self.prompt_choice("choose one:", select_options);
+                IntentResponse::SelectOption {
+                    player,
+                    select_option: option.index,
+                }
+            }
+            IntentRequest::OrderTriggers {
+                player,
+                select_triggers,
+            } => {
+                let mut select_triggers = select_triggers
+                    .into_iter()
+                    .map(|t| PendingTriggerDisplay::new(t, game))
+                    .collect_vec();
+                let mut order = Vec::new();
+                while select_triggers.len() > 1 {
+                    let next = self
+                        .prompt_choice(
+                            "choose the next effect to resolve:",
+                            select_triggers.clone(),
+                        )
+                        .trigger;
+                    select_triggers.retain(|t| t.trigger != next);
+                    order.push(next);
+                }
+                order.extend(select_triggers.into_iter().map(|t| t.trigger));
+                IntentResponse::OrderTriggers {
+                    player,
+                    select_order: order,
+                }
+            }
         }
     }
 }
@@ -0,0 +1,190 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Debug,
+};
+
+use async_channel::{Receiver, Sender, TrySendError};
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::{
+    events::{ClientReceive, EvaluateEvent, Event, SyncGameState},
+    gameplay::{CardRef, GameDirector, GameState},
+    transport::PlayerTransport,
+};
+
+/// the events waiting to be sent to a spectator. a spectator that falls further behind is
+/// disconnected, instead of holding up the game
+pub const SPECTATOR_QUEUE_SIZE: usize = 256;
+
+/// Read-only subscribers to a game. They receive a public feed of the events, that can be
+/// delayed, and never receive intent requests
+pub struct Spectators {
+    /// the events are released after that many newer events
+    pub delay: usize,
+    pending: VecDeque<Event>,
+    /// the public state, after the released events. sent to the late joiners
+    state: GameState,
+    /// the cards already revealed in the feed
+    revealed: HashSet<CardRef>,
+    joining: Receiver<Box<dyn PlayerTransport>>,
+    subscribers: Vec<Subscriber>,
+}
+
+/// The events are sent to the spectator from its own task, the game never waits for it
+struct Subscriber {
+    queue: Sender<ClientReceive>,
+    task: JoinHandle<()>,
+}
+
+impl Subscriber {
+    fn new(mut transport: Box<dyn PlayerTransport>) -> Self {
+        let (queue, queued) = async_channel::bounded(SPECTATOR_QUEUE_SIZE);
+        let task = tokio::spawn(async move {
+            while let Ok(message) = queued.recv().await {
+                if transport.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Subscriber { queue, task }
+    }
+
+    /// false if the spectator is gone or too far behind
+    fn send(&self, message: ClientReceive) -> bool {
+        match self.queue.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                debug!("spectator is too far behind");
+                // it could be stuck on the connection
+                self.task.abort();
+                false
+            }
+            Err(TrySendError::Closed(_)) => {
+                debug!("spectator disconnected");
+                false
+            }
+        }
+    }
+}
+
+impl Debug for Spectators {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spectators")
+            .field("delay", &self.delay)
+            .field("pending", &self.pending.len())
+            .field("subscribers", &self.subscribers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Spectators {
+    pub fn new(delay: usize) -> (Self, Sender<Box<dyn PlayerTransport>>) {
+        let (join, joining) = async_channel::unbounded();
+        let spectators = Spectators {
+            delay,
+            pending: VecDeque::new(),
+            state: GameState::default(),
+            revealed: HashSet::new(),
+            joining,
+            subscribers: Vec::new(),
+        };
+        (spectators, join)
+    }
+
+    /// the events not yet released
+    pub fn delayed(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn subscribers(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    pub fn push(&mut self, event: Event) {
+        // the hidden cards are removed from the state
+        let event = match event {
+            Event::SyncGameState(sync) => {
+                let state = sync.state.public_view();
+                self.revealed = state.visible_cards(None).map(|(_, _, c)| c).collect();
+                SyncGameState {
+                    state: Box::new(state),
+                }
+                .into()
+            }
+            event => event,
+        };
+
+        self.pending.push_back(event);
+        while self.pending.len() > self.delay {
+            let event = self
+                .pending
+                .pop_front()
+                .expect("there is more than the delay");
+            self.release(event);
+        }
+    }
+
+    /// at the end of the game, the delay is not needed anymore
+    pub fn flush(&mut self) {
+        while let Some(event) = self.pending.pop_front() {
+            self.release(event);
+        }
+    }
+
+    fn release(&mut self, event: Event) {
+        // the late joiners start from the current public state
+        while let Ok(spectator) = self.joining.try_recv() {
+            let spectator = Subscriber::new(spectator);
+            if let Event::SyncGameState(_) = event {
+                // already a full state
+                self.subscribers.push(spectator);
+                continue;
+            }
+            let sync = SyncGameState {
+                state: Box::new(self.state.clone()),
+            };
+            if spectator.send(ClientReceive::Event(sync.into())) {
+                self.subscribers.push(spectator);
+            }
+        }
+
+        event.apply_state_change(&mut self.state);
+        self.subscribers
+            .retain(|spectator| spectator.send(ClientReceive::Event(event.clone())));
+    }
+}
+
+impl GameDirector {
+    /// returns a sender to add spectators, even after the game started
+    pub fn enable_spectators(&mut self, delay: usize) -> Sender<Box<dyn PlayerTransport>> {
+        let (spectators, join) = Spectators::new(delay);
+        self.spectators = Some(spectators);
+        join
+    }
+
+    pub(crate) fn spectate_event(&mut self, event: &Event) {
+        if let Some(spectators) = &mut self.spectators {
+            spectators.push(event.clone());
+        }
+    }
+
+    /// reveal the cards that are now public, after an event
+    pub(crate) fn spectate_reveals(&mut self) {
+        let Some(spectators) = &mut self.spectators else {
+            return;
+        };
+
+        let reveals = self
+            .game
+            .state
+            .reveal_visible_cards(None, &mut spectators.revealed);
+        for reveal in reveals {
+            spectators.push(reveal.into());
+        }
+
+        if self.game.game_outcome().is_some() {
+            spectators.flush();
+        }
+    }
+}
@@ -1,7 +1,12 @@
-use std::{collections::HashMap, env};
+use std::{collections::HashMap, env, iter};
 
 use crate::{
-    cards::CardNumber, client::*, gameplay::*, library::load_library, modifiers::*,
+    card_effects::trace::EvaluationTracer,
+    cards::CardNumber,
+    client::*,
+    gameplay::*,
+    library::{load_library, Loadout},
+    modifiers::*,
     prompters::BufferedPrompter,
 };
 use rand::{rngs::StdRng, SeedableRng};
@@ -242,6 +247,25 @@ pub async fn setup_test_game(
     (game, p1_client, p2_client)
 }
 
+// a simple deck, for full games played by random prompters
+pub fn test_loadout(oshi: &str) -> Loadout {
+    Loadout {
+        oshi: oshi.into(),
+        main_deck: ["hSD01-003", "hSD01-004", "hSD01-005", "hSD01-006"]
+            .into_iter()
+            .cycle()
+            .take(50)
+            .map(Into::into)
+            .collect(),
+        cheer_deck: iter::repeat_n("hY01-001".into(), 20).collect(),
+    }
+}
+
+// print the trace with `tracer.take_trace()` when a card misbehaves
+pub fn setup_test_tracer(game: &mut GameDirector) -> EvaluationTracer {
+    game.enable_tracer()
+}
+
 pub fn setup_test_logs() -> tracing_appender::non_blocking::WorkerGuard {
     // --------------- setup logs ---------------------
     env::set_var("RUST_BACKTRACE", "1");
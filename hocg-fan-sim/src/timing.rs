@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+#[cfg(feature = "timing")]
+use tracing::{info, warn};
+
+use crate::gameplay::Player;
+#[cfg(feature = "timing")]
+use crate::{
+    events::{IntentRequest, IntentResponse, WaitingForPlayerIntent},
+    gameplay::{GameContinue, GameDirector, GameOutcome, GameOverReason, GameResult},
+};
+
+/// Limits on the time the players take to respond. Nothing is limited by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimingRules {
+    /// the time to respond to each intent request, then the default response is used
+    pub intent_timeout: Option<Duration>,
+    /// chess clock, the total time of each player for the game. the player loses when it runs out
+    pub clock: Option<Duration>,
+    /// the time of the active player in their turn, then the default responses are used
+    /// until the end of the turn
+    pub turn_limit: Option<Duration>,
+}
+
+/// The time left of each player, only the time spent waiting for a response counts
+#[derive(Debug, Clone)]
+pub struct Clocks {
+    pub rules: TimingRules,
+    player_1: Option<Duration>,
+    player_2: Option<Duration>,
+    /// the time used by the active player in the current turn
+    turn_used: Duration,
+    /// the requests that timed out, their responses are still coming
+    late_responses: [usize; 2],
+}
+
+impl Clocks {
+    pub fn new(rules: TimingRules) -> Self {
+        Clocks {
+            rules,
+            player_1: rules.clock,
+            player_2: rules.clock,
+            turn_used: Duration::ZERO,
+            late_responses: [0, 0],
+        }
+    }
+
+    fn index(player: Player) -> usize {
+        match player {
+            Player::One => 0,
+            Player::Two => 1,
+            _ => unreachable!("only one player can respond"),
+        }
+    }
+
+    /// the time left on the clock of the player
+    pub fn remaining(&self, player: Player) -> Option<Duration> {
+        match player {
+            Player::One => self.player_1,
+            Player::Two => self.player_2,
+            _ => unreachable!("only one player can respond"),
+        }
+    }
+
+    /// the time left in the turn, for the active player
+    pub fn turn_remaining(&self) -> Option<Duration> {
+        self.rules
+            .turn_limit
+            .map(|limit| limit.saturating_sub(self.turn_used))
+    }
+
+    /// the time the player has to respond, before the default response is used
+    pub fn deadline(&self, player: Player, active: bool) -> Option<Duration> {
+        [
+            self.rules.intent_timeout,
+            self.remaining(player),
+            self.turn_remaining().filter(|_| active),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    pub fn spend(&mut self, player: Player, active: bool, elapsed: Duration) {
+        let remaining = match player {
+            Player::One => &mut self.player_1,
+            Player::Two => &mut self.player_2,
+            _ => unreachable!("only one player can respond"),
+        };
+        if let Some(remaining) = remaining {
+            *remaining = remaining.saturating_sub(elapsed);
+        }
+        if active {
+            self.turn_used += elapsed;
+        }
+    }
+
+    pub fn start_turn(&mut self) {
+        self.turn_used = Duration::ZERO;
+    }
+
+    pub fn is_out_of_time(&self, player: Player) -> bool {
+        self.remaining(player) == Some(Duration::ZERO)
+    }
+
+    /// the player will still answer the request that timed out, before the next one
+    pub fn late_response(&mut self, player: Player) {
+        self.late_responses[Self::index(player)] += 1;
+    }
+
+    /// returns true if the response was for a request that timed out
+    pub fn discard_late_response(&mut self, player: Player) -> bool {
+        let late = &mut self.late_responses[Self::index(player)];
+        if *late > 0 {
+            *late -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(feature = "timing")]
+impl GameDirector {
+    /// the timing rules apply to the next intent requests
+    pub fn enable_timing(&mut self, rules: TimingRules) {
+        self.clocks = Some(Clocks::new(rules));
+    }
+
+    /// the remaining time is sent to both players, so they can show the clocks
+    async fn report_waiting_for_player_intent(
+        &mut self,
+        player: Player,
+        deadline: Option<Duration>,
+    ) -> GameResult {
+        let clocks = self.clocks.as_ref().expect("timing should be enabled");
+        let event = WaitingForPlayerIntent {
+            player,
+            clock: clocks.remaining(player),
+            deadline,
+        };
+        // can be sent while evaluating other events
+        Box::pin(self.send_event(event.into())).await?;
+
+        Ok(GameContinue)
+    }
+
+    /// wait for the response until the deadline, then use the default response. the player
+    /// loses when the clock runs out
+    pub(crate) async fn send_timed_intent_request(
+        &mut self,
+        player: Player,
+        req: IntentRequest,
+    ) -> Result<IntentResponse, GameOutcome> {
+        let active = player == self.game.active_player();
+        let deadline = self
+            .clocks
+            .as_ref()
+            .expect("timing should be enabled")
+            .deadline(player, active);
+        self.report_waiting_for_player_intent(player, deadline)
+            .await?;
+        let default_response = req.default_response();
+        self.send_request(player, req).await?;
+
+        // only the wait is interrupted at the deadline, not the handling of the messages
+        let start = std::time::Instant::now();
+        let deadline_at = deadline.map(|deadline| tokio::time::Instant::now() + deadline);
+        let received = loop {
+            let message = match deadline_at {
+                Some(deadline_at) => {
+                    let receive = self.transport(player).receive();
+                    match tokio::time::timeout_at(deadline_at, receive).await {
+                        Ok(message) => message,
+                        Err(_) => break None,
+                    }
+                }
+                None => self.transport(player).receive().await,
+            };
+            if let Some(resp) = self.handle_message(player, message).await? {
+                let clocks = self.clocks.as_mut().expect("timing should be enabled");
+                if !clocks.discard_late_response(player) {
+                    break Some(resp);
+                }
+            }
+        };
+
+        let clocks = self.clocks.as_mut().expect("timing should be enabled");
+        clocks.spend(player, active, start.elapsed());
+        match received {
+            Some(resp) => Ok(resp),
+            None if clocks.is_out_of_time(player) => {
+                info!("{player:?} ran out of time");
+                Err(self.player_lost(player, GameOverReason::Timeout).await)
+            }
+            None => {
+                warn!("{player:?} did not respond in time, using the default response");
+                clocks.late_response(player);
+                Ok(default_response)
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    future::{self, Future},
+    pin::{pin, Pin},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use async_channel::{Receiver, Sender};
+use tracing::warn;
+
+use crate::{
+    client::{EventHandler, IntentRequestHandler},
+    events::{ClientReceive, ClientSend, EvaluateEvent},
+    gameplay::Game,
+};
+
+pub type TransportFuture<'a, T> =
+    Pin<Box<dyn Future<Output = Result<T, TransportError>> + Send + 'a>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    /// the player is not connected anymore
+    Disconnected,
+    /// the message could not be encoded, decoded or delivered
+    InvalidMessage(String),
+    /// a direct handler needed to wait for its response
+    HandlerPending,
+}
+
+impl Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransportError::Disconnected => write!(f, "the player is disconnected"),
+            TransportError::InvalidMessage(e) => write!(f, "invalid message: {e}"),
+            TransportError::HandlerPending => {
+                write!(f, "the handler could not respond without waiting")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// How the [`crate::gameplay::GameDirector`] communicates with a player
+pub trait PlayerTransport: Send {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()>;
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend>;
+}
+
+impl PlayerTransport for Box<dyn PlayerTransport> {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        self.as_mut().send(message)
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        self.as_mut().receive()
+    }
+}
+
+/// In-memory, the other side of the channels is usually a [`crate::client::Client`]
+impl PlayerTransport for (Sender<ClientReceive>, Receiver<ClientSend>) {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            self.0
+                .send(message)
+                .await
+                .map_err(|_| TransportError::Disconnected)
+        })
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        Box::pin(async move {
+            self.1
+                .recv()
+                .await
+                .map_err(|_| TransportError::Disconnected)
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportRecord {
+    Sent(ClientReceive),
+    Received(ClientSend),
+}
+
+/// The messages that went through a [`RecordedTransport`], shared with the transport
+#[derive(Debug, Clone, Default)]
+pub struct TransportLog(Arc<Mutex<Vec<TransportRecord>>>);
+
+impl TransportLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&self, record: TransportRecord) {
+        self.0.lock().unwrap().push(record);
+    }
+
+    pub fn records(&self) -> Vec<TransportRecord> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Keeps a log of the messages of another transport
+pub struct RecordedTransport<T> {
+    pub inner: T,
+    pub log: TransportLog,
+}
+
+impl<T: PlayerTransport> RecordedTransport<T> {
+    pub fn new(inner: T) -> Self {
+        RecordedTransport {
+            inner,
+            log: TransportLog::new(),
+        }
+    }
+}
+
+impl<T: PlayerTransport> PlayerTransport for RecordedTransport<T> {
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        Box::pin(async move {
+            self.log.push(TransportRecord::Sent(message.clone()));
+            self.inner.send(message).await
+        })
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        Box::pin(async move {
+            let message = self.inner.receive().await?;
+            self.log.push(TransportRecord::Received(message.clone()));
+            Ok(message)
+        })
+    }
+}
+
+/// In-process, the handlers are called without channels. Used for bots and tests, the handlers
+/// need to respond without waiting, like the [`crate::prompters::Prompter`]s
+pub struct DirectTransport<E, I> {
+    pub game: Game,
+    pub event_handler: E,
+    pub intent_handler: I,
+    responses: VecDeque<ClientSend>,
+}
+
+impl<E, I> DirectTransport<E, I>
+where
+    E: EventHandler + Send,
+    I: IntentRequestHandler + Send,
+{
+    pub async fn new(event_handler: E, intent_handler: I) -> Self {
+        DirectTransport {
+            game: Game::new().await,
+            event_handler,
+            intent_handler,
+            responses: VecDeque::new(),
+        }
+    }
+
+    fn handle(&mut self, message: ClientReceive) -> Result<(), TransportError> {
+        match message {
+            ClientReceive::Event(event) => {
+                // sync state with the director
+                event.apply_state_change(&mut self.game.state);
+                poll_ready(self.event_handler.handle_event(&self.game, event))
+            }
+            ClientReceive::IntentRequest(req) => {
+                let resp = poll_ready(self.intent_handler.handle_intent_request(&self.game, req))?;
+                self.responses.push_back(ClientSend::IntentResponse(resp));
+                Ok(())
+            }
+            ClientReceive::InvalidIntentResponse(error) => {
+                warn!("the response was rejected: {error}");
+                Ok(())
+            }
+            // not in a lobby, the state is not compared
+            ClientReceive::StateChecksum(_)
+            | ClientReceive::Lobby(_)
+            | ClientReceive::Handshake(_) => Ok(()),
+        }
+    }
+}
+
+impl<E, I> PlayerTransport for DirectTransport<E, I>
+where
+    E: EventHandler + Send,
+    I: IntentRequestHandler + Send,
+{
+    fn send(&mut self, message: ClientReceive) -> TransportFuture<'_, ()> {
+        // handled right away, the handler futures don't need to be Send
+        Box::pin(future::ready(self.handle(message)))
+    }
+    fn receive(&mut self) -> TransportFuture<'_, ClientSend> {
+        // the response was prepared with the request
+        let resp = self
+            .responses
+            .pop_front()
+            .ok_or(TransportError::Disconnected);
+        Box::pin(future::ready(resp))
+    }
+}
+
+fn poll_ready<F: Future>(future: F) -> Result<F::Output, TransportError> {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => Ok(output),
+        Poll::Pending => Err(TransportError::HandlerPending),
+    }
+}
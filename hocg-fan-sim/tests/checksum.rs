@@ -0,0 +1,145 @@
+use hocg_fan_sim::{
+    client::{Client, DefaultEventHandler},
+    events::{ClientReceive, ClientSend, Event},
+    gameplay::{GameDirector, HIDDEN_CARD_NUMBER},
+    library::load_library,
+    prompters::RandomPrompter,
+    tests::*,
+};
+use pretty_assertions::assert_eq;
+
+type TestClient = Client<DefaultEventHandler, RandomPrompter>;
+
+async fn setup_game() -> (GameDirector, TestClient, TestClient) {
+    let p1_channel_1 = async_channel::bounded(10);
+    let p1_channel_2 = async_channel::bounded(10);
+    let p2_channel_1 = async_channel::bounded(10);
+    let p2_channel_2 = async_channel::bounded(10);
+
+    let game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        (p1_channel_1.0, p1_channel_2.1),
+        (p2_channel_1.0, p2_channel_2.1),
+    )
+    .await;
+    let p1_client = Client::new(
+        (p1_channel_2.0, p1_channel_1.1),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    let p2_client = Client::new(
+        (p2_channel_2.0, p2_channel_1.1),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    (game, p1_client, p2_client)
+}
+
+#[tokio::test]
+/// The clients stay in sync with the server for a whole game
+async fn checksums_match() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (mut game, mut p1_client, mut p2_client) = setup_game().await;
+    let p1 = tokio::spawn(async move {
+        p1_client.play().await;
+        p1_client
+    });
+    let p2 = tokio::spawn(async move {
+        p2_client.play().await;
+        p2_client
+    });
+
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    for client in [p1, p2] {
+        assert_eq!(0, client.await.unwrap().desyncs);
+    }
+}
+
+#[tokio::test]
+/// A client with a different state requests the state again
+async fn desync_resolved() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (mut game, p1_client, mut p2_client) = setup_game().await;
+    let p1 = tokio::spawn(p1_client.receive_requests());
+    let p2 = tokio::spawn(async move {
+        while p2_client.game.state.turn_number == 0 {
+            p2_client.handle_request().await.unwrap();
+        }
+        // as if an event was not applied correctly
+        p2_client.game.state.turn_number += 10;
+        p2_client.play().await;
+        p2_client
+    });
+
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    let outcome = game.game.game_outcome().expect("the game should be over");
+    assert_eq!(outcome, p1.await.unwrap());
+    let p2_client = p2.await.unwrap();
+    assert!(p2_client.desyncs > 0);
+    assert_eq!(
+        game.game.state.turn_number,
+        p2_client.game.state.turn_number
+    );
+}
+
+#[tokio::test]
+/// The state sent again only has the cards that the player can see
+async fn desync_hides_cards() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (mut game, p1_client, mut p2_client) = setup_game().await;
+    tokio::spawn(p1_client.receive_requests());
+    let p2 = tokio::spawn(async move {
+        while p2_client.game.state.turn_number == 0 {
+            p2_client.handle_request().await.unwrap();
+        }
+        p2_client
+            .send
+            .send(ClientSend::RequestSyncGameState)
+            .await
+            .unwrap();
+        loop {
+            match p2_client.receive.recv().await.unwrap() {
+                ClientReceive::Event(Event::SyncGameState(sync)) => return sync.state,
+                // the state is requested with the next response
+                ClientReceive::IntentRequest(req) => {
+                    let resp = req.default_response();
+                    let _ = p2_client.send.send(ClientSend::IntentResponse(resp)).await;
+                }
+                _ => {}
+            }
+        }
+    });
+
+    game.start_game().await.unwrap();
+    while !p2.is_finished() && game.next_step().await.is_ok() {}
+
+    let state = p2.await.unwrap();
+    let opponent = &state.player_1;
+    assert!(opponent
+        .hand
+        .iter()
+        .chain(&opponent.main_deck)
+        .chain(&opponent.cheer_deck)
+        .all(|c| state.card_map[c].1 == HIDDEN_CARD_NUMBER));
+    assert!(state
+        .player_2
+        .hand
+        .iter()
+        .all(|c| state.card_map[c].1 != HIDDEN_CARD_NUMBER));
+}
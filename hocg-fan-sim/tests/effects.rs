@@ -0,0 +1,1449 @@
+use hocg_fan_sim::{
+    card_effects::{
+        self,
+        evaluate::{EvaluateContext, EvaluateEffect, EvaluateEffectMut},
+        text::EffectText,
+        trace::TraceKind,
+        *,
+    },
+    cards::UsageLimit,
+    client::IntentRequestHandler,
+    events::{
+        AttachToCard, BatonPass, Bloom, Collab, DealDamage, Draw, Event, EventSpan,
+        HoloMemberKnockedOut, IntentRequest, IntentResponse, LoseLives, RollDice, SendToZone,
+        TriggeredEvent,
+    },
+    gameplay::{CardRef, Game, GameDirector, Player, Step, Zone, ZoneAddLocation},
+    modifiers::{DamageMarkers, LifeTime, Modifier, ModifierKind},
+    prompters::BufferedPrompter,
+    tests::*,
+};
+use pretty_assertions::assert_eq;
+
+fn round_trip<T>(s: &str) -> T
+where
+    T: ParseTokens + Into<Tokens> + Clone + PartialEq,
+{
+    let parsed: T = s.parse_effect().expect(s);
+    let serialized = parsed.clone().serialize_effect();
+    let reparsed: T = serialized.parse_effect().expect(&serialized);
+    assert!(
+        parsed == reparsed,
+        "round trip mismatch: {s} -> {serialized}"
+    );
+    parsed
+}
+
+#[test]
+fn parameterized_conditions() {
+    assert_eq!(
+        round_trip::<Condition>(r#"is_named "Tokino Sora""#),
+        Condition::IsNamed(StringLiteral("Tokino Sora".into()))
+    );
+    assert_eq!(
+        round_trip::<Condition>("has_tag gen_0"),
+        Condition::HasTag(HashTag::Gen0)
+    );
+    assert_eq!(
+        round_trip::<Condition>("is_level debut"),
+        Condition::IsLevel(Level::Debut)
+    );
+    assert_eq!(
+        round_trip::<Condition>("is_support_kind fan"),
+        Condition::IsSupportKind(SupportKind::Fan)
+    );
+    assert_eq!(round_trip::<Condition>("is_oshi"), Condition::IsOshi);
+    assert_eq!(
+        round_trip::<Condition>("has_attribute buzz"),
+        Condition::HasAttribute(Attribute::Buzz)
+    );
+    assert_eq!(
+        round_trip::<Condition>(r#"is_member and is_named "AZKi""#),
+        Condition::And(
+            Box::new(Condition::IsMember),
+            Box::new(Condition::IsNamed(StringLiteral("AZKi".into())))
+        )
+    );
+
+    // the old atoms are kept as aliases
+    assert_eq!(
+        round_trip::<Condition>("is_named_azki"),
+        Condition::IsNamedAzki
+    );
+    assert_eq!(
+        round_trip::<Condition>("is_level_first"),
+        Condition::IsLevelFirst
+    );
+
+    assert_eq!(
+        r#"is_named "AZKi"#.parse_effect::<Condition>(),
+        Err(Error::UnclosedString)
+    );
+}
+
+async fn evaluate_effect(game: &mut GameDirector, card: CardRef, effect: &str) {
+    let effect: Vec<Action> = effect.parse_effect().expect(effect);
+    effect
+        .ctx()
+        .with_card(card, &game.game)
+        .evaluate_mut(game)
+        .await
+        .unwrap();
+}
+
+fn test_board() -> TestGameBoard {
+    TestGameBoard {
+        oshi: Some("hSD01-001".into()),
+        center_stage: Some("hSD01-003".into()),
+        back_stage: ["hSD01-008".into()].into(),
+        hand: ["hSD01-005".into(), "hSD01-016".into()].into(),
+        main_deck: ["hSD01-009".into(), "hSD01-004".into(), "hSD01-010".into()].into(),
+        cheer_deck: ["hY01-001".into()].into(),
+        life: ["hY01-001".into()].into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn extended_actions() {
+    round_trip::<Vec<Action>>("archive_attached_cheers this_card");
+    round_trip::<Vec<Action>>("bloom $bloom this_card");
+    round_trip::<Vec<Action>>("discard $cards");
+    round_trip::<Vec<Action>>("heal from center_stage 20");
+    round_trip::<Vec<Action>>("return_to_hand this_card");
+    round_trip::<Vec<Action>>("search 1 (is_member and is_level debut)");
+    round_trip::<Vec<Action>>("swap_center $back_mem");
+}
+
+#[tokio::test]
+async fn action_heal() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_damage_markers(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            DamageMarkers::from_hp(50),
+        )
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state.clone(),
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    evaluate_effect(&mut game, center, "heal this_card 30").await;
+
+    let mut expected_state = state.clone();
+    expected_state
+        .card_damage_markers
+        .insert(center, DamageMarkers::from_hp(20));
+
+    assert_eq!(expected_state, game.game.state);
+}
+
+#[tokio::test]
+async fn action_return_to_hand() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_attachments(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            ["hSD01-004".into(), "hY01-001".into()].into(),
+        )
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let attachments = state.player_1.attachments(center);
+    let stack = attachments
+        .iter()
+        .copied()
+        .find(|a| state.card_map[a].1 == "hSD01-004")
+        .unwrap();
+    let cheer = attachments
+        .iter()
+        .copied()
+        .find(|a| state.card_map[a].1 == "hY01-001")
+        .unwrap();
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state.clone(),
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    evaluate_effect(&mut game, center, "return_to_hand this_card").await;
+
+    let mut expected_state = state.clone();
+    expected_state.player_1.center_stage = None;
+    expected_state.player_1.attachments.clear();
+    expected_state.player_1.hand.extend([center, stack]);
+    expected_state.player_1.archive.extend([cheer]);
+
+    assert_eq!(expected_state, game.game.state);
+}
+
+#[tokio::test]
+async fn action_search() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let first = state.player_1.main_deck[2];
+
+    let p1_p = BufferedPrompter::new(&[
+        // AZKi (1st)
+        &[0],
+    ]);
+    let (mut game, p1_client, p2_client) =
+        setup_test_game(state.clone(), p1_p, BufferedPrompter::new(&[])).await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    evaluate_effect(&mut game, oshi, "search 1 (is_member and is_level first)").await;
+
+    assert!(game.game.state.player_1.hand.contains(&first));
+    assert!(!game.game.state.player_1.main_deck.contains(&first));
+    assert_eq!(game.game.state.player_1.main_deck.len(), 2);
+}
+
+#[tokio::test]
+async fn action_discard_bloom_swap_and_archive_cheers() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_attachments(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            ["hY01-001".into()].into(),
+        )
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+    let bloom = state.player_1.hand[0];
+    let support = state.player_1.hand[1];
+    let cheer = state.player_1.attachments(center)[0];
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state.clone(),
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "
+        let $support = filter from hand is_support_kind staff
+        discard $support
+        let $bloom = filter from hand is_member
+        let $center = filter from center_stage is_member
+        archive_attached_cheers $center
+        bloom $bloom $center
+        let $back = filter from back_stage is_member
+        swap_center $back
+        ",
+    )
+    .await;
+
+    let mut expected_state = state.clone();
+    expected_state.player_1.hand = [].into();
+    expected_state.player_1.archive = [cheer, support].into();
+    expected_state.player_1.center_stage = Some(back);
+    expected_state.player_1.back_stage = [bloom].into();
+    expected_state.player_1.attachments.clear();
+    expected_state.player_1.attachments.insert(center, bloom);
+    expected_state.card_modifiers.insert(
+        bloom,
+        [Modifier {
+            id: "m_0001".into(),
+            kind: ModifierKind::PreventBloom,
+            life_time: LifeTime::ThisTurn,
+        }]
+        .into(),
+    );
+
+    assert_eq!(expected_state, game.game.state);
+}
+
+#[test]
+fn loops_and_modal_choices() {
+    round_trip::<Vec<Action>>("for_each $mem in from stage (heal $mem 10)");
+    round_trip::<Vec<Action>>("for_each $mem in from stage ((heal $mem 10) (draw 1))");
+    round_trip::<Vec<Action>>("repeat 2 (draw 1)");
+    round_trip::<Vec<Action>>("repeat (1 + 1) ((draw 1) (shuffle main_deck))");
+    round_trip::<Vec<Action>>("choose_one ((draw 1) (heal this_card 10))");
+    round_trip::<Vec<Action>>("choose_one (((draw 1) (draw 1)) (heal this_card 10))");
+
+    assert_eq!(
+        round_trip::<Vec<Action>>("for_each $mem in from stage (heal $mem 10)"),
+        vec![Action::ForEach(
+            Var("$mem".into()),
+            CardReferences::From(card_effects::Zone::Stage),
+            vec![Action::Heal(
+                CardReferences::Var(Var("$mem".into())),
+                Number::Literal(NumberLiteral(10))
+            )]
+        )]
+    );
+    assert_eq!(
+        round_trip::<Vec<Action>>("choose_one (((draw 1) (draw 1)) (heal this_card 10))"),
+        vec![Action::ChooseOne(vec![
+            vec![
+                Action::Draw(Number::Literal(NumberLiteral(1))),
+                Action::Draw(Number::Literal(NumberLiteral(1)))
+            ],
+            vec![Action::Heal(
+                CardReferences::ThisCard,
+                Number::Literal(NumberLiteral(10))
+            )]
+        ])]
+    );
+}
+
+#[tokio::test]
+async fn action_for_each_repeat_and_choose_one() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_damage_markers(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            DamageMarkers::from_hp(50),
+        )
+        .with_damage_markers(Player::One, Zone::BackStage, 0, DamageMarkers::from_hp(50))
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+
+    let p1_p = BufferedPrompter::new(&[
+        // heal 10, twice
+        &[1],
+    ]);
+    let (mut game, p1_client, p2_client) =
+        setup_test_game(state.clone(), p1_p, BufferedPrompter::new(&[])).await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "
+        for_each $mem in from stage (heal $mem 10)
+        choose_one ((draw 1) (repeat 2 (heal from back_stage 10)))
+        ",
+    )
+    .await;
+
+    let mut expected_state = state.clone();
+    expected_state
+        .card_damage_markers
+        .insert(center, DamageMarkers::from_hp(40));
+    expected_state
+        .card_damage_markers
+        .insert(back, DamageMarkers::from_hp(20));
+
+    assert_eq!(expected_state, game.game.state);
+}
+
+#[tokio::test]
+/// The options are chosen by index, even with the same text
+async fn choose_one_same_options() {
+    let game = Game::new().await;
+    let mut prompter = BufferedPrompter::new(&[&[1]]);
+    let resp = prompter
+        .handle_intent_request(
+            &game,
+            IntentRequest::SelectOption {
+                player: Player::One,
+                select_options: vec!["Draw 1 card.".into(), "Draw 1 card.".into()],
+            },
+        )
+        .await;
+    assert_eq!(
+        IntentResponse::SelectOption {
+            player: Player::One,
+            select_option: 1,
+        },
+        resp
+    );
+}
+
+#[test]
+fn operator_precedence() {
+    fn lit(n: usize) -> Box<Number> {
+        Box::new(Number::Literal(NumberLiteral(n)))
+    }
+
+    // multiply binds tighter than plus
+    assert_eq!(
+        round_trip::<Number>("2 * 3 + 1"),
+        Number::Plus(Box::new(Number::Multiply(lit(2), lit(3))), lit(1))
+    );
+    assert_eq!(
+        round_trip::<Number>("1 + 2 * 3"),
+        Number::Plus(lit(1), Box::new(Number::Multiply(lit(2), lit(3))))
+    );
+    // same precedence is left associative
+    assert_eq!(
+        round_trip::<Number>("10 - 3 - 2"),
+        Number::Minus(Box::new(Number::Minus(lit(10), lit(3))), lit(2))
+    );
+    assert_eq!(
+        round_trip::<Number>("7 / 2 * 2"),
+        Number::Multiply(Box::new(Number::Divide(lit(7), lit(2))), lit(2))
+    );
+    // parentheses still group
+    assert_eq!(
+        round_trip::<Number>("2 * (3 + 1)"),
+        Number::Multiply(lit(2), Box::new(Number::Plus(lit(3), lit(1))))
+    );
+
+    // and binds tighter than or
+    assert_eq!(
+        round_trip::<Condition>("is_member and is_cheer or is_oshi"),
+        Condition::Or(
+            Box::new(Condition::And(
+                Box::new(Condition::IsMember),
+                Box::new(Condition::IsCheer)
+            )),
+            Box::new(Condition::IsOshi)
+        )
+    );
+    assert_eq!(
+        round_trip::<Condition>("is_oshi or is_member and is_cheer"),
+        Condition::Or(
+            Box::new(Condition::IsOshi),
+            Box::new(Condition::And(
+                Box::new(Condition::IsMember),
+                Box::new(Condition::IsCheer)
+            ))
+        )
+    );
+
+    // comparisons take whole expressions
+    assert_eq!(
+        round_trip::<Condition>("count from stage > 2"),
+        Condition::GreaterThan(
+            Number::Count(CardReferences::From(card_effects::Zone::Stage)),
+            Number::Literal(NumberLiteral(2))
+        )
+    );
+    round_trip::<Condition>("1 + 1 < 3");
+    round_trip::<Condition>("dmg_amount != 0");
+    round_trip::<Number>("div_round_up 5 2");
+    round_trip::<Number>("min 1 (max 2 3)");
+    round_trip::<Number>("sum from stage dmg_amount");
+}
+
+#[tokio::test]
+async fn number_operators() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_damage_markers(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            DamageMarkers::from_hp(50),
+        )
+        .with_damage_markers(Player::One, Zone::BackStage, 0, DamageMarkers::from_hp(30))
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let (game, _p1_client, _p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    let game = &game.game;
+
+    let number = |effect: &str| -> usize {
+        let number: Number = effect.parse_effect().expect(effect);
+        number.ctx().with_card(oshi, game).evaluate(game)
+    };
+    assert_eq!(number("7 / 2"), 3);
+    assert_eq!(number("7 / 0"), 0);
+    assert_eq!(number("div_round_up 7 2"), 4);
+    assert_eq!(number("div_round_up 6 2"), 3);
+    assert_eq!(number("div_round_up 7 0"), 0);
+    assert_eq!(number("min 4 (2 * 3)"), 4);
+    assert_eq!(number("max 4 (2 * 3)"), 6);
+    assert_eq!(number("2 * 3 + 1"), 7);
+    assert_eq!(number("sum from stage dmg_amount"), 80);
+
+    let condition = |effect: &str| -> bool {
+        let condition: Condition = effect.parse_effect().expect(effect);
+        condition.ctx().with_card(oshi, game).evaluate(game)
+    };
+    assert!(condition("count from stage > 1"));
+    assert!(!condition("count from stage > 2"));
+    assert!(condition("count from stage < 3"));
+    assert!(condition("count from stage != 3"));
+    assert!(!condition("count from stage != 2"));
+}
+
+#[test]
+fn player_zones() {
+    assert_eq!(
+        round_trip::<CardReferences>("from opponent collab"),
+        CardReferences::From(card_effects::Zone::Of(
+            card_effects::Player::Opponent,
+            Box::new(card_effects::Zone::Collab)
+        ))
+    );
+    assert_eq!(
+        round_trip::<Condition>("is_in_zone you life"),
+        Condition::IsInZone(card_effects::Zone::Of(
+            card_effects::Player::You,
+            Box::new(card_effects::Zone::Life)
+        ))
+    );
+    round_trip::<CardReferences>("from oshi");
+    round_trip::<CardReferences>("from activate_support");
+    round_trip::<Number>("count from opponent archive");
+    round_trip::<Vec<Action>>("let $mem = select_one from opponent main_stage is_member");
+}
+
+#[tokio::test]
+async fn player_zones_resolve_against_card_owner() {
+    let mut p2_board = test_board();
+    p2_board.collab = Some("hSD01-009".into());
+    p2_board.archive = ["hSD01-016".into(), "hY01-001".into()].into();
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(p2_board)
+        .build();
+    let p1_oshi = state.player_1.oshi.unwrap();
+    let p2_oshi = state.player_2.oshi.unwrap();
+    let p2_collab = state.player_2.collab.unwrap();
+    let p2_life = state.player_2.life[0];
+
+    let (game, _p1_client, _p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    let game = &game.game;
+
+    let cards = |card: CardRef, effect: &str| -> Vec<CardRef> {
+        let cards: CardReferences = effect.parse_effect().expect(effect);
+        cards.ctx().with_card(card, game).evaluate(game)
+    };
+    assert_eq!(cards(p1_oshi, "from opponent collab"), vec![p2_collab]);
+    assert_eq!(cards(p1_oshi, "from you collab"), vec![]);
+    assert_eq!(cards(p2_oshi, "from collab"), vec![p2_collab]);
+    assert_eq!(cards(p2_oshi, "from opponent oshi"), vec![p1_oshi]);
+    assert_eq!(cards(p1_oshi, "from opponent life"), vec![p2_life]);
+    assert_eq!(cards(p2_oshi, "from oshi"), vec![p2_oshi]);
+
+    let number = |card: CardRef, effect: &str| -> usize {
+        let number: Number = effect.parse_effect().expect(effect);
+        number.ctx().with_card(card, game).evaluate(game)
+    };
+    assert_eq!(number(p1_oshi, "count from opponent archive"), 2);
+    assert_eq!(number(p2_oshi, "count from opponent archive"), 0);
+    assert_eq!(number(p1_oshi, "count from opponent hand"), 2);
+
+    let condition = |card: CardRef, effect: &str| -> bool {
+        let condition: Condition = effect.parse_effect().expect(effect);
+        condition.ctx().with_card(card, game).evaluate(game)
+    };
+    // the zone is relative to the owner of the effect, not the tested card
+    assert!(condition(
+        p1_oshi,
+        "any from opponent stage (is_in_zone opponent collab)"
+    ));
+    assert!(!condition(
+        p1_oshi,
+        "any from opponent stage (is_in_zone collab)"
+    ));
+    assert!(condition(p2_oshi, "any from stage (is_in_zone main_stage)"));
+    assert!(condition(
+        p1_oshi,
+        "all from opponent life (is_in_zone opponent life)"
+    ));
+}
+
+#[tokio::test]
+async fn event_triggers() {
+    let state = GameStateBuilder::new()
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+    let hand = state.player_1.hand[0];
+    let cheer = state.player_1.life[0];
+
+    let (game, _p1_client, _p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    let game = &game.game;
+
+    let events: Vec<(Event, Trigger, Trigger)> = vec![
+        (
+            Bloom {
+                from_card: hand,
+                to_card: center,
+            }
+            .into(),
+            Trigger::OnBeforeBloom,
+            Trigger::OnAfterBloom,
+        ),
+        (
+            Collab {
+                card: back,
+                holo_power_amount: 1,
+            }
+            .into(),
+            Trigger::OnBeforeCollab,
+            Trigger::OnAfterCollab,
+        ),
+        (
+            BatonPass {
+                from_card: center,
+                to_card: back,
+            }
+            .into(),
+            Trigger::OnBeforeBatonPass,
+            Trigger::OnAfterBatonPass,
+        ),
+        (
+            HoloMemberKnockedOut {
+                cards: vec![center],
+            }
+            .into(),
+            Trigger::OnBeforeHoloMemberKnockedOut,
+            Trigger::OnAfterHoloMemberKnockedOut,
+        ),
+        (
+            AttachToCard {
+                attachments: vec![cheer],
+                card: center,
+            }
+            .into(),
+            Trigger::OnBeforeAttachToCard,
+            Trigger::OnAfterAttachToCard,
+        ),
+        (
+            DealDamage {
+                card: center,
+                targets: vec![back],
+                dmg: DamageMarkers::from_hp(10),
+                is_special: false,
+            }
+            .into(),
+            Trigger::OnBeforeDealDamage,
+            Trigger::OnAfterDealDamage,
+        ),
+        (
+            LoseLives {
+                player: Player::One,
+                amount: 1,
+            }
+            .into(),
+            Trigger::OnBeforeLoseLives,
+            Trigger::OnAfterLoseLives,
+        ),
+        (
+            Draw {
+                player: Player::One,
+                amount: 1,
+            }
+            .into(),
+            Trigger::OnBeforeDraw,
+            Trigger::OnAfterDraw,
+        ),
+        (
+            SendToZone {
+                cards: vec![hand],
+                zone: Zone::Archive,
+                zone_location: ZoneAddLocation::Top,
+            }
+            .into(),
+            Trigger::OnBeforeSendToZone,
+            Trigger::OnAfterSendToZone,
+        ),
+    ];
+
+    let activate = |trigger: &Trigger, event: TriggeredEvent| -> bool {
+        trigger.should_activate(oshi, &event, game)
+    };
+    for (event, before, after) in &events {
+        assert!(activate(before, TriggeredEvent::Before(event)));
+        assert!(!activate(before, TriggeredEvent::After(event)));
+        assert!(activate(after, TriggeredEvent::After(event)));
+        assert!(!activate(after, TriggeredEvent::Before(event)));
+
+        // only the matching event kind
+        for (other, other_before, other_after) in &events {
+            if other != event {
+                assert!(!activate(other_before, TriggeredEvent::Before(event)));
+                assert!(!activate(other_after, TriggeredEvent::After(event)));
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn scoped_triggers() {
+    let state = GameStateBuilder::new()
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let p1_oshi = state.player_1.oshi.unwrap();
+    let p1_center = state.player_1.center_stage.unwrap();
+    let p1_back = state.player_1.back_stage[0];
+    let p1_hand = state.player_1.hand[0];
+    let p2_oshi = state.player_2.oshi.unwrap();
+
+    let (mut game, _p1_client, _p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+
+    let bloom: Event = Bloom {
+        from_card: p1_hand,
+        to_card: p1_center,
+    }
+    .into();
+    let bloom = TriggeredEvent::After(&bloom);
+
+    let this_card = Trigger::OnAfterBloom.scoped(TriggerScope::ThisCard);
+    assert!(this_card.should_activate(p1_hand, &bloom, &game.game));
+    assert!(!this_card.should_activate(p1_back, &bloom, &game.game));
+
+    let yours = Trigger::OnAfterBloom.scoped(TriggerScope::Yours);
+    assert!(yours.should_activate(p1_oshi, &bloom, &game.game));
+    assert!(!yours.should_activate(p2_oshi, &bloom, &game.game));
+
+    let opponent = Trigger::OnAfterBloom.scoped(TriggerScope::Opponent);
+    assert!(!opponent.should_activate(p1_oshi, &bloom, &game.game));
+    assert!(opponent.should_activate(p2_oshi, &bloom, &game.game));
+
+    // still needs the right event
+    assert!(!Trigger::OnBeforeBloom
+        .scoped(TriggerScope::Yours)
+        .should_activate(p1_oshi, &bloom, &game.game));
+
+    // events about a player
+    let draw: Event = Draw {
+        player: Player::Two,
+        amount: 1,
+    }
+    .into();
+    let draw = TriggeredEvent::Before(&draw);
+    let opponent = Trigger::OnBeforeDraw.scoped(TriggerScope::Opponent);
+    assert!(opponent.should_activate(p1_oshi, &draw, &game.game));
+    assert!(!opponent.should_activate(p2_oshi, &draw, &game.game));
+
+    // a condition on the cards concerned by the event
+    let collab: Event = Collab {
+        card: p1_back,
+        holo_power_amount: 1,
+    }
+    .into();
+    let collab = TriggeredEvent::After(&collab);
+    let azki = Trigger::OnAfterCollab.scoped(TriggerScope::Condition(
+        r#"is_named "AZKi" and yours"#.parse_effect().unwrap(),
+    ));
+    assert!(azki.should_activate(p1_oshi, &collab, &game.game));
+    assert!(!azki.should_activate(p2_oshi, &collab, &game.game));
+    let sora = Trigger::OnAfterCollab.scoped(TriggerScope::Condition(
+        r#"is_named "Tokino Sora""#.parse_effect().unwrap(),
+    ));
+    assert!(!sora.should_activate(p1_oshi, &collab, &game.game));
+
+    // without cards, the event concerns its origin
+    let roll_dice: Event = RollDice {
+        player: Player::One,
+        number: 3,
+    }
+    .into();
+    let roll_dice = TriggeredEvent::After(&roll_dice);
+    let this_card = Trigger::OnAfterRollDice.scoped(TriggerScope::ThisCard);
+    assert!(!this_card.should_activate(p1_center, &roll_dice, &game.game));
+    game.game.event_span.open_card_span(p1_center);
+    assert!(this_card.should_activate(p1_center, &roll_dice, &game.game));
+    let origin = Trigger::OnAfterRollDice.scoped(TriggerScope::Condition(
+        "all event_origin is_member and yours"
+            .parse_effect()
+            .unwrap(),
+    ));
+    assert!(origin.should_activate(p1_oshi, &roll_dice, &game.game));
+    assert!(!origin.should_activate(p2_oshi, &roll_dice, &game.game));
+    game.game.event_span.close_card_span(p1_center);
+}
+
+#[test]
+fn event_origin_for_triggered_effects() {
+    let state = GameStateBuilder::new()
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let center = state.player_1.center_stage.unwrap();
+
+    let mut span = EventSpan::new();
+    // the center member blooms
+    span.open_card_span(center);
+
+    // the trigger condition is checked with the event origin
+    let ctx = EvaluateContext::default();
+    assert_eq!(span.event_origin_for_evaluate(&ctx), Some(center));
+
+    // the triggered oshi skill still sees the member as the origin
+    span.open_trigger_span();
+    let ctx = EvaluateContext {
+        is_triggered: true,
+        ..Default::default()
+    };
+    assert_eq!(span.event_origin_for_evaluate(&ctx), Some(center));
+    span.open_card_span(oshi);
+    assert_eq!(span.event_origin_for_evaluate(&ctx), Some(center));
+    span.close_card_span(oshi);
+    span.close_trigger_span();
+
+    span.close_card_span(center);
+    assert_eq!(span.current_card(), None);
+}
+
+#[tokio::test]
+async fn simultaneous_triggers_in_chosen_order() {
+    let p1 = TestGameBoard {
+        oshi: Some("hSD01-001".into()),
+        center_stage: Some("hBP01-038".into()),
+        life: ["hY01-001".into()].into(),
+        ..Default::default()
+    };
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(p1.clone())
+        .with_attachments(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            ["hBP01-123".into(), "hBP01-123".into()].into(),
+        )
+        .with_player_2(p1)
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+
+    let p1_p = BufferedPrompter::new(&[
+        // resolve the second fan first
+        &[1],
+        // activate reroll
+        &[0],
+        // do not activate the first fan
+        &[1],
+    ]);
+    let (mut game, p1_client, p2_client) =
+        setup_test_game(state.clone(), p1_p, BufferedPrompter::new(&[])).await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    let fans = game.game.board(Player::One).attachments(center);
+    assert_eq!(fans.len(), 2);
+
+    evaluate_effect(&mut game, center, "let $roll = roll_dice").await;
+
+    // only the second fan was used, and archived
+    assert_eq!(game.game.state.player_1.archive, vec![fans[1]]);
+    assert_eq!(
+        game.game.board(Player::One).attachments(center),
+        vec![fans[0]]
+    );
+}
+
+#[tokio::test]
+async fn replacement_modifiers() {
+    round_trip::<Vec<Action>>("add_mod this_card prevent receive_dmg this_turn");
+    round_trip::<Vec<Action>>("add_zone_mod back_stage prevent knocked_out this_turn");
+    round_trip::<Vec<Action>>(
+        "add_mod this_card instead_of send_to archive (send_to hand this_card) this_turn",
+    );
+
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    // only the protected member is spared
+    evaluate_effect(
+        &mut game,
+        center,
+        "add_mod this_card prevent receive_dmg this_turn",
+    )
+    .await;
+    evaluate_effect(&mut game, center, "deal_damage from stage 20").await;
+    assert_eq!(game.game.get_damage(center), DamageMarkers(0));
+    assert_eq!(game.game.get_damage(back), DamageMarkers(2));
+
+    // something else happens instead, no life is lost
+    evaluate_effect(
+        &mut game,
+        back,
+        "add_mod this_card instead_of knocked_out (send_to hand this_card) this_turn",
+    )
+    .await;
+    evaluate_effect(&mut game, back, "knock_out this_card").await;
+    assert!(game.game.state.player_1.hand.contains(&back));
+    assert!(game.game.state.player_1.back_stage.is_empty());
+    assert_eq!(game.game.state.player_1.life.len(), 1);
+
+    // a replacement does not apply to its own replacing event
+    evaluate_effect(
+        &mut game,
+        back,
+        "add_mod this_card instead_of send_to archive (send_to archive this_card) this_turn",
+    )
+    .await;
+    evaluate_effect(&mut game, back, "send_to archive this_card").await;
+    assert!(game.game.state.player_1.archive.contains(&back));
+    assert!(!game.game.state.player_1.hand.contains(&back));
+}
+
+#[tokio::test]
+async fn card_counters() {
+    round_trip::<Vec<Action>>(r#"add_counter this_card "used" 1 this_turn"#);
+    round_trip::<Vec<Action>>(r#"remove_counter this_card "used" 1"#);
+    round_trip::<Vec<Action>>(r#"if counter "used" < 2 (draw 1)"#);
+
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+    let sora_first = state.player_1.hand[0];
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    let counter = |game: &GameDirector, card: CardRef| -> usize {
+        let number: Number = r#"counter "used""#.parse_effect().unwrap();
+        number
+            .ctx()
+            .with_card(card, &game.game)
+            .evaluate(&game.game)
+    };
+
+    // counters add up, and are removed when empty
+    evaluate_effect(
+        &mut game,
+        center,
+        r#"add_counter this_card "used" 2 this_turn"#,
+    )
+    .await;
+    evaluate_effect(
+        &mut game,
+        center,
+        r#"add_counter this_card "used" 1 this_turn"#,
+    )
+    .await;
+    assert_eq!(counter(&game, center), 3);
+    evaluate_effect(&mut game, center, r#"remove_counter this_card "used" 1"#).await;
+    assert_eq!(counter(&game, center), 2);
+    evaluate_effect(&mut game, center, r#"remove_counter this_card "used" 5"#).await;
+    assert_eq!(counter(&game, center), 0);
+    assert!(game.game.state.card_counters.is_empty());
+
+    // counters follow the member when it blooms
+    evaluate_effect(
+        &mut game,
+        center,
+        r#"add_counter this_card "used" 1 until_removed"#,
+    )
+    .await;
+    game.bloom_holo_member(sora_first, center).await.unwrap();
+    assert_eq!(counter(&game, sora_first), 1);
+    assert_eq!(counter(&game, center), 0);
+
+    // counters expire with their life time
+    evaluate_effect(
+        &mut game,
+        back,
+        r#"add_counter this_card "used" 1 this_turn"#,
+    )
+    .await;
+    game.remove_expiring_modifiers(LifeTime::ThisTurn)
+        .await
+        .unwrap();
+    assert_eq!(counter(&game, back), 0);
+    assert_eq!(counter(&game, sora_first), 1);
+
+    // counters are lost when leaving the stage
+    evaluate_effect(&mut game, back, "send_to hand this_card").await;
+    evaluate_effect(
+        &mut game,
+        back,
+        r#"add_counter this_card "used" 1 this_game"#,
+    )
+    .await;
+    evaluate_effect(&mut game, back, "send_to archive this_card").await;
+    assert_eq!(counter(&game, back), 0);
+}
+
+#[tokio::test]
+async fn usage_limits() {
+    let mut p1 = test_board();
+    p1.back_stage = ["hSD01-003".into()].into();
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(p1)
+        .with_player_2(test_board())
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+    let sora_first = state.player_1.hand[0];
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    // unlimited is never reached
+    game.record_usage(center, "ability 0", UsageLimit::Unlimited)
+        .await
+        .unwrap();
+    assert!(!game.has_reached_usage_limit(center, "ability 0", UsageLimit::Unlimited));
+
+    // once per turn, for this card only
+    game.record_usage(center, "ability 0", UsageLimit::OncePerTurn)
+        .await
+        .unwrap();
+    assert!(game.has_reached_usage_limit(center, "ability 0", UsageLimit::OncePerTurn));
+    assert!(!game.has_reached_usage_limit(center, "ability 1", UsageLimit::OncePerTurn));
+    assert!(!game.has_reached_usage_limit(back, "ability 0", UsageLimit::OncePerTurn));
+    game.remove_expiring_modifiers(LifeTime::ThisTurn)
+        .await
+        .unwrap();
+    assert!(!game.has_reached_usage_limit(center, "ability 0", UsageLimit::OncePerTurn));
+
+    // once per game, shared by name
+    game.record_usage(center, "ability 0", UsageLimit::OncePerGamePerName)
+        .await
+        .unwrap();
+    assert!(game.has_reached_usage_limit(back, "ability 0", UsageLimit::OncePerGamePerName));
+    game.remove_expiring_modifiers(LifeTime::ThisTurn)
+        .await
+        .unwrap();
+    assert!(game.has_reached_usage_limit(back, "ability 0", UsageLimit::OncePerGamePerName));
+
+    // the usage stays on the card, the bloom has its own abilities
+    game.record_usage(center, "ability 0", UsageLimit::OncePerTurn)
+        .await
+        .unwrap();
+    game.bloom_holo_member(sora_first, center).await.unwrap();
+    assert!(game.has_reached_usage_limit(center, "ability 0", UsageLimit::OncePerTurn));
+    assert!(!game.has_reached_usage_limit(sora_first, "ability 0", UsageLimit::OncePerTurn));
+    assert!(game.has_reached_usage_limit(sora_first, "ability 0", UsageLimit::OncePerGamePerName));
+
+    // the usages are not counters
+    assert!(game.game.state.card_counters.is_empty());
+}
+
+#[tokio::test]
+async fn hp_and_cost_modifiers() {
+    round_trip::<Vec<Action>>("add_mod this_card more_hp 20 this_turn");
+    round_trip::<Vec<Action>>("add_mod this_card less_art_cost 1 colorless this_turn");
+    round_trip::<Vec<Action>>("add_mod this_card baton_pass_cost 0 this_turn");
+    round_trip::<Vec<Action>>("add_mod this_card untargetable_by_arts this_turn");
+    round_trip::<Vec<Action>>("add_mod this_card collab_while_resting this_turn");
+
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let back = state.player_1.back_stage[0];
+    let op_center = state.player_2.center_stage.unwrap();
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    // hp bonus
+    let hp = game.remaining_hp(center);
+    evaluate_effect(&mut game, center, "add_mod this_card more_hp 20 this_turn").await;
+    assert_eq!(game.remaining_hp(center), hp + 20);
+
+    // art cost reduction, the art costs a single colorless
+    let can_use_art = |game: &GameDirector| {
+        let mem = game.lookup_holo_member(center).unwrap();
+        mem.can_use_art(center, 0, op_center, game)
+    };
+    assert!(!can_use_art(&game));
+    evaluate_effect(
+        &mut game,
+        center,
+        "add_mod this_card less_art_cost 1 colorless this_turn",
+    )
+    .await;
+    assert!(can_use_art(&game));
+
+    // cannot be targeted by arts
+    evaluate_effect(
+        &mut game,
+        op_center,
+        "add_mod this_card untargetable_by_arts this_turn",
+    )
+    .await;
+    assert!(!can_use_art(&game));
+
+    // baton pass for free, without attached cheers
+    let can_baton_pass = |game: &GameDirector| {
+        let mem = game.lookup_holo_member(center).unwrap();
+        mem.can_baton_pass(center, game)
+    };
+    assert!(!can_baton_pass(&game));
+    // a higher cost does not make it more expensive
+    evaluate_effect(
+        &mut game,
+        center,
+        "add_mod this_card baton_pass_cost 2 this_turn",
+    )
+    .await;
+    assert_eq!(game.baton_pass_cost(center, 1), 1);
+    evaluate_effect(
+        &mut game,
+        center,
+        "add_mod this_card baton_pass_cost 0 this_turn",
+    )
+    .await;
+    assert!(can_baton_pass(&game));
+    assert_eq!(game.baton_pass_cost(center, 1), 0);
+    game.baton_pass(center, back).await.unwrap();
+    assert_eq!(game.game.state.player_1.center_stage, Some(back));
+    assert_eq!(game.game.state.player_1.back_stage, vec![center]);
+
+    // collab while resting
+    game.add_modifier(center, ModifierKind::Resting, LifeTime::UntilRemoved)
+        .await
+        .unwrap();
+    evaluate_effect(
+        &mut game,
+        center,
+        "add_mod this_card collab_while_resting this_turn",
+    )
+    .await;
+    game.send_to_collab(center).await.unwrap();
+    assert_eq!(game.game.state.player_1.collab, Some(center));
+}
+
+#[tokio::test]
+async fn bloom_with_more_hp() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .with_damage_markers(
+            Player::One,
+            Zone::CenterStage,
+            0,
+            DamageMarkers::from_hp(150),
+        )
+        .build();
+    let center = state.player_1.center_stage.unwrap();
+    let bloom = state.player_1.hand[0];
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    let can_bloom = |game: &GameDirector| {
+        let mem = game.lookup_holo_member(bloom).unwrap();
+        let target = game.lookup_holo_member(center).unwrap();
+        mem.can_bloom_target(bloom, game, (center, target))
+    };
+
+    // the damage is the same as the hp of the bloom member
+    assert!(!can_bloom(&game));
+
+    // the hp bonus is kept after the bloom
+    evaluate_effect(&mut game, center, "add_mod this_card more_hp 20 this_turn").await;
+    assert!(can_bloom(&game));
+    game.bloom_holo_member(bloom, center).await.unwrap();
+    assert_eq!(game.max_hp(bloom), 170);
+    assert_eq!(game.remaining_hp(bloom), 20);
+}
+
+#[tokio::test]
+async fn zone_conditional_modifiers() {
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(test_board())
+        .with_player_2(test_board())
+        .build();
+    let oshi = state.player_1.oshi.unwrap();
+    let white_center = state.player_1.center_stage.unwrap();
+    let green_back = state.player_1.back_stage[0];
+    let cheer = state.player_1.cheer_deck[0];
+    let op_center = state.player_2.center_stage.unwrap();
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state,
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    let more_dmg = |game: &GameDirector, card: CardRef| {
+        game.has_modifier(card, ModifierKind::DealMoreDamage(50))
+    };
+
+    // the condition is checked for each card in the zone
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "add_zone_mod center_stage when is_color white deal_more_dmg 50 this_turn",
+    )
+    .await;
+    assert!(more_dmg(&game, white_center));
+    assert!(!more_dmg(&game, green_back));
+
+    // and again when the cards move
+    evaluate_effect(&mut game, green_back, "swap_center this_card").await;
+    assert!(!more_dmg(&game, white_center));
+    assert!(!more_dmg(&game, green_back));
+    evaluate_effect(&mut game, white_center, "swap_center this_card").await;
+    assert!(more_dmg(&game, white_center));
+
+    // and when the state of the card changes
+    let hp = game.remaining_hp(white_center);
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "add_zone_mod stage when has_cheers more_hp 20 this_turn",
+    )
+    .await;
+    assert_eq!(game.remaining_hp(white_center), hp);
+    game.attach_cards_to_card(vec![cheer], white_center)
+        .await
+        .unwrap();
+    assert_eq!(game.remaining_hp(white_center), hp + 20);
+
+    // conditions can be nested
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "add_zone_mod stage when is_member (when is_color green recv_less_dmg 10) this_turn",
+    )
+    .await;
+    assert!(game.has_modifier(green_back, ModifierKind::ReceiveLessDamage(10)));
+    assert!(!game.has_modifier(white_center, ModifierKind::ReceiveLessDamage(10)));
+
+    // the condition is evaluated from the point of view of the card's owner
+    evaluate_effect(&mut game, green_back, "swap_center this_card").await;
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "add_zone_mod opponent stage when any from center_stage is_color green recv_more_dmg 10 this_turn",
+    )
+    .await;
+    assert!(!game.has_modifier(op_center, ModifierKind::ReceiveMoreDamage(10)));
+    evaluate_effect(
+        &mut game,
+        oshi,
+        "add_zone_mod opponent stage when any from center_stage is_color white recv_more_dmg 20 this_turn",
+    )
+    .await;
+    assert!(game.has_modifier(op_center, ModifierKind::ReceiveMoreDamage(20)));
+}
+
+#[test]
+fn effect_text() {
+    let effect: Vec<Action> = r"
+        let $roll = roll_dice
+        if is_odd $roll (
+            add_mod this_card deal_more_dmg 50 this_art
+        )
+    "
+    .parse_effect()
+    .unwrap();
+    assert_eq!(
+        effect.to_text(),
+        "Roll a die. If the result is odd, this card gains +50 damage for this art."
+    );
+
+    let effect: Vec<Action> = r"
+        let $cheer = select_one from archive is_cheer
+        let $mem = select_one from stage (is_member and is_color green)
+        attach_cards $cheer $mem
+    "
+    .parse_effect()
+    .unwrap();
+    assert_eq!(
+        effect.to_text(),
+        "Choose 1 of the cards in your archive that are cheers. \
+        Choose 1 of the cards in your stage that are holomem and are green. \
+        Attach the chosen card to the chosen card (mem)."
+    );
+
+    let condition: Vec<Condition> = "exists from hand".parse_effect().unwrap();
+    assert_eq!(
+        condition.to_text(),
+        "Only if there is at least one card among the cards in your hand."
+    );
+
+    let modifier: card_effects::Modifier = "when is_color white recv_less_dmg 10"
+        .parse_effect()
+        .unwrap();
+    assert_eq!(modifier.to_text(), "-10 damage received when it is white");
+}
+
+#[tokio::test]
+async fn evaluation_trace() {
+    // let _guard = setup_test_logs();
+
+    let p1 = test_board();
+    let p2 = test_board();
+
+    let state = GameStateBuilder::new()
+        .with_active_player(Player::One)
+        .with_active_step(Step::Main)
+        .with_player_1(p1)
+        .with_player_2(p2)
+        .build();
+
+    let (mut game, p1_client, p2_client) = setup_test_game(
+        state.clone(),
+        BufferedPrompter::new(&[]),
+        BufferedPrompter::new(&[]),
+    )
+    .await;
+
+    tokio::spawn(p1_client.receive_requests());
+    tokio::spawn(p2_client.receive_requests());
+
+    let tracer = setup_test_tracer(&mut game);
+    let p1_center = game
+        .board(Player::One)
+        .get_zone(Zone::CenterStage)
+        .all_cards()[0];
+
+    evaluate_effect(
+        &mut game,
+        p1_center,
+        r"
+            let $roll = roll_dice
+            if is_odd $roll (
+                draw 1
+            )
+        ",
+    )
+    .await;
+
+    let trace = tracer.take_trace();
+    assert_eq!(trace.0.len(), 1);
+    let effect = &trace.0[0];
+    assert_eq!(effect.kind, TraceKind::Effect);
+    assert_eq!(effect.card, Some(p1_center.to_string()));
+
+    // the roll is bound, with the event it emitted
+    let roll = &effect.children[0];
+    assert_eq!(roll.kind, TraceKind::Action);
+    assert_eq!(roll.label, "let $roll = roll_dice");
+    assert_eq!(roll.details, ["$roll = 5"]);
+    assert_eq!(roll.children[0].kind, TraceKind::Event);
+    assert_eq!(roll.children[0].origin, Some(p1_center.to_string()));
+
+    // the condition result, then the draw event
+    let if_odd = &effect.children[1];
+    assert_eq!(if_odd.children[0].kind, TraceKind::Condition);
+    assert_eq!(if_odd.children[0].label, "is_odd $roll");
+    assert_eq!(if_odd.children[0].details, ["true"]);
+    let draw = &if_odd.children[1];
+    assert_eq!(draw.label, "draw 1");
+    assert_eq!(draw.children[0].kind, TraceKind::Event);
+
+    // printable as text and json
+    assert!(trace.to_text().contains("    condition: is_odd $roll"));
+    assert!(trace.to_json().contains(r#""kind": "condition""#));
+
+    // nothing left after retrieving it
+    assert_eq!(tracer.take_trace().0, vec![]);
+}
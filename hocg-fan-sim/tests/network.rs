@@ -0,0 +1,109 @@
+use futures_util::SinkExt;
+use hocg_fan_sim::{
+    client::{
+        websocket::{self, CLIENT_MESSAGE_LIMIT, SERVER_MESSAGE_LIMIT},
+        Client, DefaultEventHandler,
+    },
+    events::ClientReceive,
+    gameplay::GameDirector,
+    handshake::HandshakeResponse,
+    library::load_library,
+    prompters::RandomPrompter,
+    tests::*,
+    transport::{PlayerTransport, TransportError},
+};
+use pretty_assertions::assert_eq;
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test]
+/// Plays a random game between two clients connected over WebSocket
+async fn websocket_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+
+    // server
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let p1 = websocket::accept(stream).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let p2 = websocket::accept(stream).await.unwrap();
+
+        let mut game = GameDirector::setup(
+            &test_loadout("hSD01-001"),
+            &test_loadout("hSD01-002"),
+            p1,
+            p2,
+        )
+        .await;
+        game.start_game().await.unwrap();
+        while game.next_step().await.is_ok() {}
+        game.game.game_outcome()
+    });
+
+    // clients
+    let p1_client = Client::new(
+        websocket::connect(&url).await.unwrap(),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    let p1_outcome = tokio::spawn(p1_client.receive_requests());
+    let p2_client = Client::new(
+        websocket::connect(&url).await.unwrap(),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    let p2_outcome = tokio::spawn(p2_client.receive_requests());
+
+    let outcome = server.await.unwrap().expect("the game should be over");
+    assert_eq!(outcome, p1_outcome.await.unwrap());
+    assert_eq!(outcome, p2_outcome.await.unwrap());
+}
+
+#[test]
+/// The library pushed by the server is larger than the messages of the clients
+fn message_size_limits() {
+    let library = vec![0; 1024 * 1024];
+    let bytes = websocket::encode_message(ClientReceive::Handshake(HandshakeResponse::Library(
+        library.clone(),
+    )));
+
+    assert_eq!(
+        None,
+        websocket::decode_message::<ClientReceive, CLIENT_MESSAGE_LIMIT>(&bytes)
+    );
+    assert_eq!(
+        Some(ClientReceive::Handshake(HandshakeResponse::Library(
+            library
+        ))),
+        websocket::decode_message::<ClientReceive, SERVER_MESSAGE_LIMIT>(&bytes)
+    );
+}
+
+#[tokio::test]
+/// The server closes the connection before buffering a message that is too large
+async fn message_too_large() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut transport = websocket::accept(stream).await.unwrap();
+        transport.receive().await
+    });
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+    let message = Message::Binary(vec![0; CLIENT_MESSAGE_LIMIT + 1]);
+    // the server could close the connection before the whole message is sent
+    let _ = socket.send(message).await;
+
+    assert!(matches!(
+        server.await.unwrap(),
+        Err(TransportError::InvalidMessage(_))
+    ));
+}
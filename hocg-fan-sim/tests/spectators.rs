@@ -0,0 +1,174 @@
+use std::time::Duration;
+
+use hocg_fan_sim::{
+    client::DefaultEventHandler,
+    events::{ClientReceive, EvaluateEvent, Event},
+    gameplay::{GameDirector, GameState, HIDDEN_CARD_NUMBER},
+    library::load_library,
+    prompters::RandomPrompter,
+    tests::*,
+    transport::{DirectTransport, PlayerTransport},
+};
+use pretty_assertions::assert_eq;
+
+fn spectator() -> (
+    Box<dyn PlayerTransport>,
+    async_channel::Receiver<ClientReceive>,
+) {
+    let (sender, receiver) = async_channel::unbounded();
+    // spectators never respond
+    let (_, responses) = async_channel::unbounded();
+    (Box::new((sender, responses)), receiver)
+}
+
+/// The state known by the spectator, from the public feed
+async fn spectate(feed: &async_channel::Receiver<ClientReceive>) -> GameState {
+    // the events are sent from the task of the spectator
+    tokio::task::yield_now().await;
+    let mut state = GameState::default();
+    let mut first = true;
+    while let Ok(message) = feed.try_recv() {
+        let ClientReceive::Event(event) = message else {
+            panic!("spectators only receive events: {message:?}");
+        };
+        if first {
+            let Event::SyncGameState(sync) = &event else {
+                panic!("the first event should be the game state: {event:?}");
+            };
+            assert!(sync
+                .state
+                .card_map
+                .values()
+                .any(|(_, n)| n == HIDDEN_CARD_NUMBER));
+            first = false;
+        }
+        event.apply_state_change(&mut state);
+    }
+    state
+}
+
+/// the order of the decks is not known by the clients
+fn assert_same_boards(expected: &GameState, state: &GameState) {
+    for (expected, board) in [
+        (&expected.player_1, &state.player_1),
+        (&expected.player_2, &state.player_2),
+    ] {
+        let (mut expected, mut board) = (expected.clone(), board.clone());
+        for board in [&mut expected, &mut board] {
+            board.main_deck.make_contiguous().sort();
+            board.cheer_deck.make_contiguous().sort();
+        }
+        assert_eq!(expected, board);
+    }
+}
+
+async fn setup_game() -> GameDirector {
+    let p1 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let p2 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        p1,
+        p2,
+    )
+    .await
+}
+
+#[tokio::test]
+/// The spectator follows a random game, without the hidden cards
+async fn spectate_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let mut game = setup_game().await;
+    let (transport, feed) = spectator();
+    game.enable_spectators(0).send(transport).await.unwrap();
+
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {
+        tokio::task::yield_now().await;
+    }
+
+    let state = spectate(&feed).await;
+    assert_eq!(game.game.game_outcome(), state.game_outcome);
+    assert_same_boards(&game.game.state, &state);
+
+    // the public cards are known
+    for (_, _, card) in game.game.state.public_cards() {
+        assert_eq!(
+            game.game.state.card_map[&card], state.card_map[&card],
+            "{card:?}"
+        );
+    }
+    // the cheer decks and life are not
+    let board = &game.game.state.player_1;
+    assert!(board
+        .cheer_deck
+        .iter()
+        .chain(&board.life)
+        .all(|c| state.card_map[c].1 == HIDDEN_CARD_NUMBER));
+}
+
+#[tokio::test]
+/// The feed is behind the game, and late spectators start from the delayed state
+async fn delayed_spectators() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let mut game = setup_game().await;
+    let join = game.enable_spectators(5);
+    let (transport, feed) = spectator();
+    join.send(transport).await.unwrap();
+
+    game.start_game().await.unwrap();
+    assert_eq!(5, game.spectators.as_ref().unwrap().delayed());
+    let early = spectate(&feed).await;
+    assert_ne!(game.game.state.player_1, early.player_1);
+
+    let (transport, late_feed) = spectator();
+    join.send(transport).await.unwrap();
+    while game.next_step().await.is_ok() {
+        tokio::task::yield_now().await;
+    }
+
+    // the delay is flushed at the end of the game
+    assert_eq!(0, game.spectators.as_ref().unwrap().delayed());
+    assert_eq!(2, game.spectators.as_ref().unwrap().subscribers());
+    let late = spectate(&late_feed).await;
+    assert_eq!(game.game.game_outcome(), late.game_outcome);
+    assert_same_boards(&game.game.state, &late);
+}
+
+#[tokio::test]
+/// A spectator that stops reading is disconnected, the game and the other spectators go on
+async fn stalled_spectator() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let mut game = setup_game().await;
+    let join = game.enable_spectators(0);
+    let (transport, feed) = spectator();
+    join.send(transport).await.unwrap();
+    // never read
+    let (sender, _stalled) = async_channel::bounded(1);
+    let (_, responses) = async_channel::unbounded();
+    join.send(Box::new((sender, responses))).await.unwrap();
+
+    let played = async {
+        game.start_game().await.unwrap();
+        while game.next_step().await.is_ok() {
+            tokio::task::yield_now().await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), played)
+        .await
+        .expect("the game should not wait for the spectator");
+
+    assert_eq!(1, game.spectators.as_ref().unwrap().subscribers());
+    let state = spectate(&feed).await;
+    assert_eq!(game.game.game_outcome(), state.game_outcome);
+    assert_same_boards(&game.game.state, &state);
+}
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use hocg_fan_sim::{
+    client::DefaultEventHandler,
+    events::{ClientReceive, ClientSend, Event},
+    gameplay::{GameDirector, GameOutcome, GameOverReason, Player},
+    library::load_library,
+    prompters::RandomPrompter,
+    tests::*,
+    timing::{Clocks, TimingRules},
+    transport::{DirectTransport, PlayerTransport},
+};
+use pretty_assertions::assert_eq;
+
+/// A player who walked away, the requests are never answered
+struct SilentPlayer {
+    received: async_channel::Receiver<ClientReceive>,
+    _responses: async_channel::Sender<ClientSend>,
+}
+
+fn silent_player() -> (Box<dyn PlayerTransport>, SilentPlayer) {
+    let (sender, received) = async_channel::unbounded();
+    let (_responses, responses) = async_channel::unbounded();
+    (
+        Box::new((sender, responses)),
+        SilentPlayer {
+            received,
+            _responses,
+        },
+    )
+}
+
+async fn setup_game(rules: TimingRules) -> (GameDirector, SilentPlayer) {
+    let p1 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let (p2, silent) = silent_player();
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        p1,
+        p2,
+    )
+    .await;
+    game.enable_timing(rules);
+    (game, silent)
+}
+
+#[tokio::test]
+/// The default responses are used for the silent player, and the game goes on until the end
+async fn intent_timeout() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (mut game, p2) = setup_game(TimingRules {
+        intent_timeout: Some(Duration::from_millis(5)),
+        ..Default::default()
+    })
+    .await;
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    let outcome = game.game.game_outcome().expect("the game should be over");
+    assert_ne!(GameOverReason::Timeout, outcome.reason);
+    assert!(game.transport_errors.is_empty());
+
+    // the clocks are shown before each request
+    let mut requests = 0;
+    let mut waiting = None;
+    while let Ok(message) = p2.received.try_recv() {
+        match message {
+            ClientReceive::Event(Event::WaitingForPlayerIntent(event)) => waiting = Some(event),
+            ClientReceive::IntentRequest(_) => {
+                let event = waiting.take().expect("waiting before the request");
+                assert_eq!(Player::Two, event.player);
+                assert_eq!(None, event.clock);
+                assert_eq!(Some(Duration::from_millis(5)), event.deadline);
+                requests += 1;
+            }
+            _ => {}
+        }
+    }
+    assert!(requests > 0);
+}
+
+#[tokio::test]
+/// The player loses when the clock runs out
+async fn clock_runs_out() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (mut game, _p2) = setup_game(TimingRules {
+        clock: Some(Duration::from_millis(20)),
+        ..Default::default()
+    })
+    .await;
+    if game.start_game().await.is_ok() {
+        while game.next_step().await.is_ok() {}
+    }
+
+    assert_eq!(
+        Some(GameOutcome {
+            winning_player: Some(Player::One),
+            reason: GameOverReason::Timeout,
+        }),
+        game.game.game_outcome()
+    );
+    let clocks = game.clocks.as_ref().unwrap();
+    assert_eq!(Some(Duration::ZERO), clocks.remaining(Player::Two));
+    assert!(clocks.remaining(Player::One).unwrap() > Duration::ZERO);
+}
+
+#[test]
+/// The shortest limit applies, the turn limit only for the active player
+fn clock_deadlines() {
+    let mut clocks = Clocks::new(TimingRules {
+        intent_timeout: Some(Duration::from_secs(30)),
+        clock: Some(Duration::from_secs(600)),
+        turn_limit: Some(Duration::from_secs(60)),
+    });
+    assert_eq!(
+        Some(Duration::from_secs(30)),
+        clocks.deadline(Player::One, true)
+    );
+
+    clocks.spend(Player::One, true, Duration::from_secs(50));
+    assert_eq!(
+        Some(Duration::from_secs(10)),
+        clocks.deadline(Player::One, true)
+    );
+    assert_eq!(
+        Some(Duration::from_secs(30)),
+        clocks.deadline(Player::Two, false)
+    );
+    assert_eq!(
+        Some(Duration::from_secs(550)),
+        clocks.remaining(Player::One)
+    );
+
+    // responses in the turn of the opponent
+    clocks.spend(Player::Two, false, Duration::from_secs(590));
+    assert_eq!(
+        Some(Duration::from_secs(10)),
+        clocks.deadline(Player::Two, false)
+    );
+    clocks.start_turn();
+    assert_eq!(
+        Some(Duration::from_secs(30)),
+        clocks.deadline(Player::One, true)
+    );
+    clocks.spend(Player::Two, false, Duration::from_secs(20));
+    assert!(clocks.is_out_of_time(Player::Two));
+}
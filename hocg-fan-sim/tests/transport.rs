@@ -0,0 +1,332 @@
+use hocg_fan_sim::{
+    client::{Client, DefaultEventHandler, IntentRequestHandler},
+    events::{ClientReceive, Event, IntentRequest, IntentResponse},
+    gameplay::{
+        CardRef, Game, GameDirector, GameOutcome, GameOverReason, MainStepAction, Player, Zone,
+        MAX_INVALID_RESPONSES,
+    },
+    library::load_library,
+    prompters::RandomPrompter,
+    tests::*,
+    transport::{
+        DirectTransport, RecordedTransport, TransportError, TransportLog, TransportRecord,
+    },
+};
+use pretty_assertions::assert_eq;
+
+#[tokio::test]
+/// Plays a random game without channels, and keeps the messages sent to player 1
+async fn direct_transport_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let p1 = RecordedTransport::new(
+        DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await,
+    );
+    let p1_log = p1.log.clone();
+    let p2 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        p1,
+        p2,
+    )
+    .await;
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    let outcome = game.game.game_outcome().expect("the game should be over");
+    assert!(game.transport_errors.is_empty());
+
+    let records = p1_log.records();
+    assert!(records
+        .iter()
+        .any(|r| matches!(r, TransportRecord::Received(_))));
+    let Some(TransportRecord::Sent(ClientReceive::Event(Event::GameOver(game_over)))) =
+        records.last()
+    else {
+        panic!("the last message should be the game over");
+    };
+    assert_eq!(game_over.game_outcome, outcome);
+}
+
+#[tokio::test]
+/// The player that cannot be reached loses, without panicking
+async fn disconnected_player() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let p1_channel_1 = async_channel::bounded(10);
+    let p1_channel_2 = async_channel::bounded(10);
+    let p2_channel_1 = async_channel::bounded(10);
+    let p2_channel_2 = async_channel::bounded(10);
+
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        (p1_channel_1.0, p1_channel_2.1),
+        (p2_channel_1.0, p2_channel_2.1),
+    )
+    .await;
+
+    // Player 1
+    let p1_client = Client::new(
+        (p1_channel_2.0, p1_channel_1.1),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    let p1_outcome = tokio::spawn(p1_client.receive_requests());
+
+    // Player 2 is gone
+    drop(p2_channel_1.1);
+    drop(p2_channel_2.0);
+
+    let expected = GameOutcome {
+        winning_player: Some(Player::One),
+        reason: GameOverReason::Disconnect,
+    };
+    assert_eq!(Err(expected), game.start_game().await);
+    assert_eq!(
+        vec![(Player::Two, TransportError::Disconnected)],
+        game.transport_errors
+    );
+    assert_eq!(expected, p1_outcome.await.unwrap());
+}
+
+/// Plays randomly, then concedes at the first main step
+struct ConcedingPlayer(RandomPrompter);
+impl IntentRequestHandler for ConcedingPlayer {
+    async fn handle_intent_request(&mut self, game: &Game, req: IntentRequest) -> IntentResponse {
+        match req {
+            IntentRequest::MainStepAction { player, .. } => IntentResponse::Concede { player },
+            req => self.0.handle_intent_request(game, req).await,
+        }
+    }
+}
+
+#[tokio::test]
+/// The player that concedes loses, and both clients know the outcome
+async fn concede_game() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let p1_channel_1 = async_channel::bounded(10);
+    let p1_channel_2 = async_channel::bounded(10);
+    let p2_channel_1 = async_channel::bounded(10);
+    let p2_channel_2 = async_channel::bounded(10);
+
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        (p1_channel_1.0, p1_channel_2.1),
+        (p2_channel_1.0, p2_channel_2.1),
+    )
+    .await;
+
+    let p1_client = Client::new(
+        (p1_channel_2.0, p1_channel_1.1),
+        DefaultEventHandler::new(),
+        RandomPrompter::new(),
+    )
+    .await;
+    let p1_outcome = tokio::spawn(p1_client.receive_requests());
+    let p2_client = Client::new(
+        (p2_channel_2.0, p2_channel_1.1),
+        DefaultEventHandler::new(),
+        ConcedingPlayer(RandomPrompter::new()),
+    )
+    .await;
+    let p2_outcome = tokio::spawn(p2_client.receive_requests());
+
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    let expected = GameOutcome {
+        winning_player: Some(Player::One),
+        reason: GameOverReason::Concede,
+    };
+    assert_eq!(Some(expected), game.game.game_outcome());
+    assert!(game.transport_errors.is_empty());
+    assert_eq!(expected, p1_outcome.await.unwrap());
+    assert_eq!(expected, p2_outcome.await.unwrap());
+}
+
+#[tokio::test]
+/// The game is a draw after the maximum number of turns
+async fn turn_limit() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let p1 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let p2 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        p1,
+        p2,
+    )
+    .await;
+    game.max_turns = Some(2);
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+
+    assert_eq!(
+        Some(GameOutcome {
+            winning_player: None,
+            reason: GameOverReason::TurnLimit,
+        }),
+        game.game.game_outcome()
+    );
+    assert_eq!(3, game.game.turn_number());
+}
+
+/// Answers with a player that was not prompted, a few times
+struct CheatingPlayer {
+    cheats: usize,
+    prompter: RandomPrompter,
+}
+impl IntentRequestHandler for CheatingPlayer {
+    async fn handle_intent_request(&mut self, game: &Game, req: IntentRequest) -> IntentResponse {
+        match req {
+            IntentRequest::MainStepAction { player, .. } if self.cheats > 0 => {
+                self.cheats -= 1;
+                IntentResponse::MainStepAction {
+                    player: player.opponent(),
+                    select_action: MainStepAction::Done,
+                }
+            }
+            req => self.prompter.handle_intent_request(game, req).await,
+        }
+    }
+}
+
+async fn cheating_game(cheats: usize) -> (GameDirector, TransportLog) {
+    let p1 = DirectTransport::new(DefaultEventHandler::new(), RandomPrompter::new()).await;
+    let p2 = RecordedTransport::new(
+        DirectTransport::new(
+            DefaultEventHandler::new(),
+            CheatingPlayer {
+                cheats,
+                prompter: RandomPrompter::new(),
+            },
+        )
+        .await,
+    );
+    let p2_log = p2.log.clone();
+    let mut game = GameDirector::setup(
+        &test_loadout("hSD01-001"),
+        &test_loadout("hSD01-002"),
+        p1,
+        p2,
+    )
+    .await;
+    game.start_game().await.unwrap();
+    while game.next_step().await.is_ok() {}
+    (game, p2_log)
+}
+
+fn rejected_responses(log: &TransportLog) -> usize {
+    log.records()
+        .iter()
+        .filter(|r| {
+            matches!(
+                r,
+                TransportRecord::Sent(ClientReceive::InvalidIntentResponse(_))
+            )
+        })
+        .count()
+}
+
+#[tokio::test]
+/// The invalid responses are rejected, and the player is prompted again
+async fn invalid_response_prompted_again() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (game, p2_log) = cheating_game(MAX_INVALID_RESPONSES - 1).await;
+
+    let outcome = game.game.game_outcome().expect("the game should be over");
+    assert_ne!(GameOverReason::InvalidResponses, outcome.reason);
+    assert_eq!(MAX_INVALID_RESPONSES - 1, rejected_responses(&p2_log));
+}
+
+#[tokio::test]
+/// The player forfeits after repeated invalid responses
+async fn invalid_responses_forfeit() {
+    // let _guard = setup_test_logs();
+
+    load_library(&include_bytes!("../../hocg-fan-lib.gz")[..]).await;
+
+    let (game, p2_log) = cheating_game(MAX_INVALID_RESPONSES).await;
+
+    assert_eq!(
+        Some(GameOutcome {
+            winning_player: Some(Player::One),
+            reason: GameOverReason::InvalidResponses,
+        }),
+        game.game.game_outcome()
+    );
+    assert_eq!(MAX_INVALID_RESPONSES - 1, rejected_responses(&p2_log));
+}
+
+#[test]
+/// The responses are checked against the request
+fn validate_responses() {
+    let card = |n: &str| CardRef::from(n);
+    let req = IntentRequest::LookSelectZoneToZone {
+        player: Player::One,
+        from_zone: Zone::MainDeck,
+        to_zone: Zone::Hand,
+        look_cards: vec![card("c_0001"), card("c_0002"), card("c_0003")],
+        select_cards: vec![card("c_0001"), card("c_0002")],
+        min_amount: 1,
+        max_amount: 2,
+    };
+    let resp = |player, select_cards| IntentResponse::LookSelectZoneToZone {
+        player,
+        select_cards,
+    };
+
+    assert_eq!(
+        Ok(()),
+        req.validate(&resp(Player::One, vec![card("c_0002")]))
+    );
+    assert_eq!(
+        Ok(()),
+        req.validate(&resp(Player::One, vec![card("c_0001"), card("c_0002")]))
+    );
+    // from the other player
+    assert!(req
+        .validate(&resp(Player::Two, vec![card("c_0001")]))
+        .is_err());
+    // not enough or too many
+    assert!(req.validate(&resp(Player::One, vec![])).is_err());
+    assert!(req
+        .validate(&resp(
+            Player::One,
+            vec![card("c_0001"), card("c_0002"), card("c_0002")]
+        ))
+        .is_err());
+    // can be looked at, but not selected
+    assert!(req
+        .validate(&resp(Player::One, vec![card("c_0003")]))
+        .is_err());
+    // twice the same card
+    assert!(req
+        .validate(&resp(Player::One, vec![card("c_0001"), card("c_0001")]))
+        .is_err());
+    // another request
+    assert!(req
+        .validate(&IntentResponse::SelectNumber {
+            player: Player::One,
+            select_number: 1
+        })
+        .is_err());
+}
@@ -12,7 +12,7 @@ use hocg_fan_sim::{
     client::{Client, DefaultEventHandler, EventHandler, IntentRequestHandler},
     events::{Event, IntentRequest, IntentResponse, Shuffle},
     gameplay::{
-        CardDisplay, CardRef, Game, GameDirector, MainStepActionDisplay,
+        CardDisplay, CardRef, Game, GameDirector, MainStepActionDisplay, PendingTriggerDisplay,
         PerformanceStepActionDisplay, Player, Zone,
     },
     library::{load_library, Loadout},
@@ -1173,6 +1173,66 @@ fn Intent(player: Player) -> Element {
                     }
                 })
                 .collect_vec(),
+            IntentRequest::SelectOption {
+                player,
+                select_options,
+            } => select_options
+                .iter()
+                .enumerate()
+                .map(|(idx, option)| {
+                    let player = *player;
+                    let select_option = idx;
+                    rsx! {
+                        button {
+                            onclick: move |_event| {
+                                INTENT_RESPONSE
+                                    .write()
+                                    .as_mut()
+                                    .unwrap()
+                                    .send(IntentResponse::SelectOption {
+                                        player,
+                                        select_option,
+                                    })
+                                    .expect("should send correctly");
+                            },
+                            class: "btn btn-neutral",
+                            "{option}"
+                        }
+                    }
+                })
+                .collect_vec(),
+            IntentRequest::OrderTriggers {
+                player,
+                select_triggers,
+            } => select_triggers
+                .iter()
+                .map(|trigger| {
+                    let player = *player;
+                    // resolve this one first, then the rest in the same order
+                    let select_order = Some(*trigger)
+                        .into_iter()
+                        .chain(select_triggers.iter().copied().filter(|t| t != trigger))
+                        .collect_vec();
+                    let trigger_display = PendingTriggerDisplay::new(*trigger, &game);
+                    rsx! {
+                        button {
+                            onclick: move |_event| {
+                                INTENT_RESPONSE
+                                    .write()
+                                    .as_mut()
+                                    .unwrap()
+                                    .send(IntentResponse::OrderTriggers {
+                                        player,
+                                        select_order: select_order.clone(),
+                                    })
+                                    .expect("should send correctly");
+                            },
+                            class: "btn btn-neutral",
+                            "{trigger_display}"
+                        }
+                    }
+                })
+                .collect_vec(),
         };
         let buttons = options.into_iter().map(|o| {
             rsx! {